@@ -1,58 +1,20 @@
 use std::time::{Duration, Instant};
 
 use custos::{
-    prelude::Float, AddOperation, Alloc, Autograd, Base, Buffer, Cached, Cursor, Device, HasId, IsShapeIndep, MayTapeActions, MayToWgslSource, OnNewBuffer, OpenCL, TapeActions, ZeroGrad, CPU
+    prelude::Float, AddOperation, Alloc, ApplyFunction, Autograd, Base, Buffer, Cached, Cursor,
+    Device, HasId, IsShapeIndep, MayToWgslSource, OnNewBuffer, OpenCL, TapeActions, CPU,
 };
 
 use graplot::Plot;
 use sliced::{
-    BinaryElementWise, BinaryOpsMayGrad, Clip, GemmMayGrad, Matrix, Mean, Onehot, RandOp,
-    RowOpMayGrad, SumCols, SumColsMayGrad,
+    io::load_mnist,
+    metrics::accuracy,
+    nn::{Linear, Module},
+    optim::{Optimizer, Scheduler, StepLR, SGD},
+    BinaryElementWise, BinaryOpsMayGrad, ClipMayGrad, Matrix, Mean, Onehot, SumCols,
+    SumColsMayGrad,
 };
 
-pub struct Linear<'a, T, D: Device, const I: usize, const O: usize> {
-    weights: Matrix<'a, T, D>,
-    bias: Matrix<'a, T, D>,
-}
-
-impl<'a, T: Float, D: Device + OnNewBuffer<T, D>, const I: usize, const O: usize>
-    Linear<'a, T, D, I, O>
-{
-    pub fn new(device: &'a D) -> Self
-    where
-        D: RandOp<T> + Alloc<T>,
-    {
-        let mut weights = Matrix::new(device, I, O).require_grad();
-        device.rand(&mut weights, T::from_f64(-0.1), T::from_f64(0.1));
-        // let mut weights = Matrix::from((device, I, O, vec![T::from_f64(0.01); I * O])).require_grad();
-        // device.rand(&mut weights, -T::one() / T::two(), T::one() / T::two());
-        //let mut weights = Matrix::from((device, I, O, vec![T::one(); I*O]));
-
-        Linear {
-            weights,
-            bias: Matrix::new(device, 1, O).require_grad(),
-        }
-    }
-
-    #[inline]
-    pub fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D>
-    where
-        D: GemmMayGrad<T> + RowOpMayGrad<T>,
-    {
-        //inputs.gemm(&self.weights).add_row(&self.bias)
-        let mut out = inputs.gemm(&self.weights);
-        out.add_row_mut(&self.bias);
-        out
-    }
-
-    pub fn params<'b>(&'b mut self) -> Vec<Param<'b, 'a, T, D>>
-    where
-        D: IsShapeIndep,
-    {
-        vec![Param::new(&mut self.weights), Param::new(&mut self.bias)]
-    }
-}
-
 pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     device: &'a D,
     min: usize,
@@ -73,52 +35,6 @@ pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     (x, y)
 }
 
-pub struct Param<'a, 'b, T, D: Device> {
-    param: &'a mut Buffer<'b, T, D>,
-}
-
-impl<'a, 'b, T, D: Device> Param<'a, 'b, T, D> {
-    pub fn new(param: &'a mut Buffer<'b, T, D>) -> Self {
-        Param { param }
-    }
-}
-
-pub struct SGD<T> {
-    lr: T,
-}
-
-#[cfg(feature = "autograd")]
-use custos::prelude::{ClearBuf, One, WriteBuf};
-
-#[cfg(feature = "autograd")]
-use core::ops::{Mul, SubAssign};
-use std::ops::{Deref, DerefMut};
-
-#[cfg(feature = "autograd")]
-impl<T: Copy + One + Mul<Output = T> + SubAssign + 'static> SGD<T> {
-    pub fn zero_grad<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: MayTapeActions + ZeroGrad<T> + WriteBuf<T> + Alloc<T> + ClearBuf<T> + 'static,
-    {
-        for param in params {
-            param.param.grad_mut().clear();
-        }
-    }
-
-    pub fn step<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: WriteBuf<T> + ZeroGrad<T> + Alloc<T> + MayTapeActions + 'static,
-        D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
-    {
-        for param in params {
-            let grad = param.param.grad();
-            for (value, grad) in param.param.iter_mut().zip(grad.iter()) {
-                *value -= *grad * self.lr
-            }
-        }
-    }
-}
-
 use custos::Combiner;
 
 pub fn cce<'a, T, D>(
@@ -128,7 +44,7 @@ pub fn cce<'a, T, D>(
 ) -> Buffer<'a, T, D>
 where
     T: Float + MayToWgslSource,
-    D: Device + Clip<T, ()> + AddOperation + BinaryElementWise<T> + SumCols<T>,
+    D: Device + ClipMayGrad<T> + AddOperation + BinaryElementWise<T> + SumCols<T>,
 {
     let device = preds.device();
     let preds = device.clip(preds, T::as_generic(1E-7), T::as_generic(1. - 1E-7));
@@ -144,7 +60,7 @@ pub fn cce_grad<'a, T, D>(
 ) -> Buffer<'a, T, D>
 where
     T: Float + custos::ToWgslSource,
-    D: Device + Clip<T, ()> + AddOperation + BinaryElementWise<T>,
+    D: Device + ApplyFunction<T> + AddOperation + BinaryElementWise<T>,
 {
     let device = preds.device();
     let grad = device.div(targets, preds);
@@ -160,27 +76,23 @@ fn mnist() {
     let mut lin2 = Linear::<f32, _, 128, 10>::new(&device);
     let mut lin3 = Linear::<f32, _, 10, 10>::new(&device);
 
-    let loader = purpur::CSVLoader::new(true);
-    let Ok(loaded_data) =
-        loader.load::<f32, _>("../gradients-fallback/datasets/digit-recognizer/train.csv")
+    // the standard IDX files, e.g. train-images-idx3-ubyte
+    let Ok((x, labels)) =
+        load_mnist::<f32, _>(&device, "../gradients-fallback/datasets/mnist", true)
     else {
         return;
     };
+    let x = x.no_grad();
 
-    let mut x = Matrix::from((&device, loaded_data.sample_count, 28 * 28, loaded_data.x)).no_grad();
-    for i in 0..x.len() {
-        x[i] /= 255.;
-    }
-
-    let y = Matrix::from((&device, loaded_data.sample_count, 1, loaded_data.y.clone()));
-    let y = device.onehot(&y);
-    let y = Matrix::from((y, loaded_data.sample_count, 10)).no_grad();
+    let y = device.onehot(&labels);
+    let y = Matrix::from((y, x.rows(), 10)).no_grad();
 
     assert!(!y.as_buf().requires_grad());
 
     let start = Instant::now();
 
-    let sgd = SGD { lr: 0.1 };
+    let mut sgd = SGD::new(0.1);
+    let mut scheduler = StepLR::new(20, 0.5);
     for epoch in device.range(0..50) {
         #[cfg(feature = "autograd")]
         unsafe {
@@ -192,23 +104,7 @@ fn mnist() {
         let out = lin2.forward(&out).relu();
         let out = lin3.forward(&out).softmax();
 
-        let mut correct_count = 0;
-        let out_slice = out.as_slice();
-        for row in 0..out.rows() {
-            let correct_idx = loaded_data.y[row].round() as usize;
-            let mut max = out_slice[row * out.cols()];
-            let mut max_idx = 0;
-            for col in 1..out.cols() {
-                if out_slice[row * out.cols() + col] > max {
-                    max = out_slice[row * out.cols() + col];
-                    max_idx = col
-                }
-            }
-            if correct_idx == max_idx {
-                correct_count += 1;
-            }
-        }
-        let acc = correct_count as f32 / loaded_data.sample_count as f32;
+        let acc = accuracy(&out, &labels);
 
         let loss = cce(&out, &y, out.cols());
         let grad = cce_grad(&out, &y, out.rows());
@@ -235,6 +131,7 @@ fn mnist() {
         sgd.step(lin1.params());
         sgd.step(lin2.params());
         sgd.step(lin3.params());
+        scheduler.step(&mut sgd);
 
         if start.elapsed() >= Duration::from_secs_f64(31.834260042) {
             break;
@@ -244,6 +141,7 @@ fn mnist() {
 }
 
 fn main() {
+    sliced::set_seed(0);
     mnist();
     return;
     let device = CPU::<Autograd<custos::Base>>::new();
@@ -264,7 +162,7 @@ fn main() {
     let mut lin8 = Linear::<f32, _, 512, 1>::new(&device);
 
     let (x, y) = create_sine(&device, 0, 100000);
-    let sgd = SGD { lr: 0.0001 };
+    let mut sgd = SGD::new(0.0001);
 
     let start = Instant::now();
 
@@ -1,51 +1,13 @@
 use custos::{
-    prelude::Float, AddOperation, Alloc, AsNoId, Autograd, Base, Buffer, Cached, Cursor, Device,
-    ExecNow, HasId, IsShapeIndep, Lazy, MayTapeActions, OnNewBuffer, Run, TapeActions, ZeroGrad,
+    AddOperation, Alloc, AsNoId, Autograd, Base, Cached, Cursor, ExecNow, HasId, IsShapeIndep,
+    Lazy, OnNewBuffer, Run, TapeActions,
 };
 
-use sliced::{GemmMayGrad, Matrix, Mean, RandOp, RowOpMayGrad};
-
-pub struct Linear<'a, T, D: Device, const I: usize, const O: usize> {
-    weights: Matrix<'a, T, D>,
-    bias: Matrix<'a, T, D>,
-}
-
-impl<'a, T: Float, D: Device + OnNewBuffer<T, D>, const I: usize, const O: usize>
-    Linear<'a, T, D, I, O>
-{
-    pub fn new(device: &'a D) -> Self
-    where
-        D: RandOp<T> + Alloc<T>,
-    {
-        let mut weights = Matrix::new(device, I, O).require_grad();
-        // device.rand(&mut weights, T::from_f64(-0.1), T::from);
-        device.rand(&mut weights, -T::one() / T::two(), T::one() / T::two());
-        //let mut weights = Matrix::from((device, I, O, vec![T::one(); I*O]));
-
-        Linear {
-            weights,
-            bias: Matrix::new(device, 1, O).require_grad(),
-        }
-    }
-
-    #[inline]
-    pub fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D>
-    where
-        D: GemmMayGrad<T> + RowOpMayGrad<T>,
-    {
-        //inputs.gemm(&self.weights).add_row(&self.bias)
-        let mut out = inputs.gemm(&self.weights);
-        out.add_row_mut(&self.bias);
-        out
-    }
-
-    pub fn params<'b>(&'b mut self) -> Vec<Param<'b, 'a, T, D>>
-    where
-        D: IsShapeIndep,
-    {
-        vec![Param::new(&mut self.weights), Param::new(&mut self.bias)]
-    }
-}
+use sliced::{
+    nn::{Linear, Module},
+    optim::{Optimizer, SGD},
+    Matrix, Mean,
+};
 
 pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     device: &'a D,
@@ -67,54 +29,7 @@ pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     (x.no_grad(), y.no_grad())
 }
 
-pub struct Param<'a, 'b, T, D: Device> {
-    param: &'a mut Buffer<'b, T, D>,
-}
-
-impl<'a, 'b, T, D: Device> Param<'a, 'b, T, D> {
-    pub fn new(param: &'a mut Buffer<'b, T, D>) -> Self {
-        Param { param }
-    }
-}
-
-pub struct SGD<T> {
-    lr: T,
-}
-
-#[cfg(feature = "autograd")]
-use custos::prelude::{ClearBuf, One, WriteBuf};
-
-#[cfg(feature = "autograd")]
-use core::ops::{Mul, SubAssign};
-use std::{
-    ops::{Deref, DerefMut},
-    rc::Rc,
-};
-
-#[cfg(feature = "autograd")]
-impl<T: Copy + One + Mul<Output = T> + SubAssign + 'static + std::fmt::Debug> SGD<T> {
-    pub fn zero_grad<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: MayTapeActions + WriteBuf<T> + Alloc<T> + ZeroGrad<T> + ClearBuf<T> + 'static,
-    {
-        for param in params {
-            param.param.grad_mut().clear();
-        }
-    }
-
-    pub fn step<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: WriteBuf<T> + Alloc<T> + MayTapeActions + ZeroGrad<T> + 'static,
-        D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
-    {
-        for param in params {
-            let grad = param.param.grad();
-            for (value, grad) in param.param.iter_mut().zip(grad.iter()) {
-                *value -= *grad * self.lr
-            }
-        }
-    }
-}
+use std::rc::Rc;
 
 fn sine_net() {
     use std::time::Instant;
@@ -128,7 +43,7 @@ fn sine_net() {
 
     let (x, y) = create_sine(&dev, 0, 1000);
 
-    let sgd = SGD { lr: 0.0001 };
+    let mut sgd = SGD::new(0.0001);
 
     let start = Instant::now();
 
@@ -186,7 +101,7 @@ fn sine_net_lazy2() {
     let mut lin3 = Linear::<f32, _, 64, 1>::new(&*dev);
 
     let (x, y) = create_sine(&*dev, 0, 1000);
-    let sgd = SGD { lr: 0.0001 };
+    let mut sgd = SGD::new(0.0001);
 
     let start = Instant::now();
 
@@ -244,7 +159,7 @@ fn sine_net_lazy() {
 
     let (x, y) = create_sine(&*dev, 0, 1000);
 
-    let sgd = SGD { lr: 0.0001 };
+    let mut sgd = SGD::new(0.0001);
 
     let start = Instant::now();
 
@@ -295,6 +210,7 @@ fn sine_net_lazy() {
 }
 
 fn main() {
+    sliced::set_seed(0);
     sine_net_lazy();
     sine_net();
 }
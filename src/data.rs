@@ -0,0 +1,167 @@
+//! Minibatching of datasets.
+
+use custos::{Alloc, Buffer, Device, OnNewBuffer};
+
+use crate::{with_rng, IndexSelectRowsMayGrad, Matrix, Rng};
+
+/// Splits features and labels into (shuffled) minibatches.
+/// The rows of a batch are gathered on the device with [`Matrix::index_select_rows`].
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{data::DataLoader, Matrix, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let x = Matrix::from((&device, 5, 2, [0., 0., 1., 1., 2., 2., 3., 3., 4., 4.]));
+/// let y = Matrix::from((&device, 5, 1, [0., 1., 2., 3., 4.]));
+///
+/// let mut loader = DataLoader::new(&x, &y, 2).seed(0);
+/// assert_eq!(loader.len(), 3);
+///
+/// for (x_batch, y_batch) in loader.iter() {
+///     assert_eq!(x_batch.cols(), 2);
+///     assert_eq!(x_batch.rows(), y_batch.rows());
+///     assert_eq!(x_batch.read()[0], y_batch.read()[0]);
+/// }
+/// ```
+pub struct DataLoader<'l, 'a, T, D: Device> {
+    features: &'l Matrix<'a, T, D>,
+    labels: &'l Matrix<'a, T, D>,
+    pub batch_size: usize,
+    /// Shuffles the samples at the start of every epoch.
+    pub shuffle: bool,
+    /// Drops the last batch if it contains less than `batch_size` samples.
+    pub drop_last: bool,
+    rng: Rng,
+}
+
+impl<'l, 'a, T, D: Device> DataLoader<'l, 'a, T, D> {
+    /// Creates a shuffling loader. The generator is forked from the default generator, see [`set_seed`](crate::set_seed).
+    ///
+    /// # Panics
+    /// If `batch_size` is zero or the number of feature and label rows differs.
+    pub fn new(
+        features: &'l Matrix<'a, T, D>,
+        labels: &'l Matrix<'a, T, D>,
+        batch_size: usize,
+    ) -> Self {
+        assert!(batch_size > 0, "The batch size must be greater than zero");
+        assert_eq!(
+            features.rows(),
+            labels.rows(),
+            "The features and labels must have the same number of rows"
+        );
+
+        DataLoader {
+            features,
+            labels,
+            batch_size,
+            shuffle: true,
+            drop_last: false,
+            rng: with_rng(|rng| rng.fork()),
+        }
+    }
+
+    #[inline]
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    #[inline]
+    pub fn drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    /// Shuffles with a generator seeded with `seed`.
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Returns the number of samples.
+    #[inline]
+    pub fn samples(&self) -> usize {
+        self.features.rows()
+    }
+
+    /// Returns the number of batches per epoch.
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.drop_last {
+            self.samples() / self.batch_size
+        } else {
+            self.samples().div_ceil(self.batch_size)
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Starts an epoch: returns an iterator over `(features, labels)` batches.
+    pub fn iter(&mut self) -> Batches<'l, 'a, T, D> {
+        let mut order = (0..self.samples() as i32).collect::<Vec<_>>();
+        if self.shuffle {
+            self.rng.shuffle(&mut order);
+        }
+
+        Batches {
+            features: self.features,
+            labels: self.labels,
+            order,
+            batch_size: self.batch_size,
+            batches: self.len(),
+            batch: 0,
+        }
+    }
+}
+
+/// The batches of one epoch, created by [`DataLoader::iter`].
+pub struct Batches<'l, 'a, T, D: Device> {
+    features: &'l Matrix<'a, T, D>,
+    labels: &'l Matrix<'a, T, D>,
+    order: Vec<i32>,
+    batch_size: usize,
+    batches: usize,
+    batch: usize,
+}
+
+impl<'l, 'a, T, D> Iterator for Batches<'l, 'a, T, D>
+where
+    D: IndexSelectRowsMayGrad<T, i32> + Alloc<i32> + OnNewBuffer<i32, D>,
+{
+    type Item = (Matrix<'a, T, D>, Matrix<'a, T, D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batch == self.batches {
+            return None;
+        }
+
+        let start = self.batch * self.batch_size;
+        let end = (start + self.batch_size).min(self.order.len());
+        self.batch += 1;
+
+        let indices = Buffer::from((self.features.device(), self.order[start..end].to_vec()));
+        Some((
+            self.features.index_select_rows(&indices),
+            self.labels.index_select_rows(&indices),
+        ))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.batches - self.batch;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'l, 'a, T, D> ExactSizeIterator for Batches<'l, 'a, T, D> where
+    D: IndexSelectRowsMayGrad<T, i32> + Alloc<i32> + OnNewBuffer<i32, D>
+{
+}
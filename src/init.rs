@@ -0,0 +1,215 @@
+//! Weight initialisation schemes for [`Matrix`].
+//!
+//! All initialisers draw from the default generator of the current thread, see [`set_seed`](crate::set_seed).
+//! For a layer mapping `fan_in` inputs to `fan_out` outputs, the weight matrix has the dims `fan_in x fan_out`.
+//!
+//! # Example
+#![cfg_attr(feature = "cpu", doc = "```")]
+#![cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+//! use sliced::{init, Matrix, CPU};
+//!
+//! let device = CPU::<custos::Base>::new();
+//!
+//! let mut weights = Matrix::<f32, _>::new(&device, 784, 128);
+//! init::kaiming_uniform(&mut weights, 784);
+//!
+//! let bound = (6f32 / 784.).sqrt();
+//! assert!(weights.iter().all(|w| w.abs() <= bound));
+//! ```
+
+use custos::{prelude::Float, Read, Shape, WriteBuf};
+
+use crate::{Distributions, Matrix, RandOp};
+
+#[inline]
+fn scaled<T: Float>(numerator: f64, fan: usize) -> T {
+    T::as_generic((numerator / fan as f64).sqrt())
+}
+
+/// Xavier/Glorot uniform: `U(-a, a)` with `a = sqrt(6 / (fan_in + fan_out))`.
+pub fn xavier_uniform<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize, fan_out: usize)
+where
+    T: Float,
+    D: RandOp<T, S>,
+    S: Shape,
+{
+    let bound = scaled::<T>(6., fan_in + fan_out);
+    matrix.rand(-bound, bound);
+}
+
+/// Xavier/Glorot normal: `N(0, std²)` with `std = sqrt(2 / (fan_in + fan_out))`.
+pub fn xavier_normal<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize, fan_out: usize)
+where
+    T: Float,
+    D: Distributions<T, S>,
+    S: Shape,
+{
+    let std = scaled::<T>(2., fan_in + fan_out);
+    matrix.randn(T::zero(), std);
+}
+
+/// Kaiming/He uniform for ReLU networks: `U(-a, a)` with `a = sqrt(6 / fan_in)`.
+pub fn kaiming_uniform<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize)
+where
+    T: Float,
+    D: RandOp<T, S>,
+    S: Shape,
+{
+    let bound = scaled::<T>(6., fan_in);
+    matrix.rand(-bound, bound);
+}
+
+/// Kaiming/He normal for ReLU networks: `N(0, std²)` with `std = sqrt(2 / fan_in)`.
+pub fn kaiming_normal<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize)
+where
+    T: Float,
+    D: Distributions<T, S>,
+    S: Shape,
+{
+    let std = scaled::<T>(2., fan_in);
+    matrix.randn(T::zero(), std);
+}
+
+/// LeCun uniform: `U(-a, a)` with `a = sqrt(3 / fan_in)`.
+pub fn lecun_uniform<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize)
+where
+    T: Float,
+    D: RandOp<T, S>,
+    S: Shape,
+{
+    let bound = scaled::<T>(3., fan_in);
+    matrix.rand(-bound, bound);
+}
+
+/// LeCun normal: `N(0, std²)` with `std = sqrt(1 / fan_in)`.
+pub fn lecun_normal<T, D, S>(matrix: &mut Matrix<T, D, S>, fan_in: usize)
+where
+    T: Float,
+    D: Distributions<T, S>,
+    S: Shape,
+{
+    let std = scaled::<T>(1., fan_in);
+    matrix.randn(T::zero(), std);
+}
+
+/// Sets every element of `matrix` to `value`.
+pub fn constant<T, D, S>(matrix: &mut Matrix<T, D, S>, value: T)
+where
+    T: Clone,
+    D: WriteBuf<T, S>,
+    S: Shape,
+{
+    let data = vec![value; matrix.len()];
+    matrix.device().write(matrix, &data);
+}
+
+/// Fills `matrix` with a (semi-)orthogonal matrix scaled by `gain`.
+/// The rows are orthonormal if there are fewer rows than columns, otherwise the columns are.
+///
+/// A normal matrix is orthonormalised with the modified Gram–Schmidt process on the host.
+pub fn orthogonal<T, D, S>(matrix: &mut Matrix<T, D, S>, gain: T)
+where
+    T: Float,
+    D: Distributions<T, S> + Read<T, S> + WriteBuf<T, S>,
+    S: Shape,
+{
+    let (rows, cols) = (matrix.rows(), matrix.cols());
+    matrix.randn(T::zero(), T::one());
+
+    let mut data = matrix.read_to_vec();
+
+    // orthonormalise the shorter dimension, i.e. rows or columns
+    let (vectors, len) = if rows <= cols {
+        (rows, cols)
+    } else {
+        (cols, rows)
+    };
+    let idx = |vector: usize, at: usize| {
+        if rows <= cols {
+            vector * cols + at
+        } else {
+            at * cols + vector
+        }
+    };
+
+    for vector in 0..vectors {
+        for prev in 0..vector {
+            let dot = (0..len).fold(T::zero(), |acc, at| {
+                acc + data[idx(vector, at)] * data[idx(prev, at)]
+            });
+            for at in 0..len {
+                data[idx(vector, at)] = data[idx(vector, at)] - dot * data[idx(prev, at)];
+            }
+        }
+
+        let norm = (0..len)
+            .fold(T::zero(), |acc, at| {
+                acc + data[idx(vector, at)] * data[idx(vector, at)]
+            })
+            .sqrt();
+        for at in 0..len {
+            data[idx(vector, at)] = data[idx(vector, at)] / norm;
+        }
+    }
+
+    for value in data.iter_mut() {
+        *value = *value * gain;
+    }
+
+    matrix.device().write(matrix, &data);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{init, Matrix};
+    use custos::CPU;
+
+    #[test]
+    fn test_xavier_uniform_bounds() {
+        let device = CPU::<custos::Base>::new();
+        let mut weights = Matrix::<f64, _>::new(&device, 20, 30);
+
+        init::xavier_uniform(&mut weights, 20, 30);
+
+        let bound = (6f64 / 50.).sqrt();
+        assert!(weights.iter().all(|w| w.abs() <= bound));
+        assert!(weights.iter().any(|w| w.abs() > bound / 2.));
+    }
+
+    #[test]
+    fn test_constant() {
+        let device = CPU::<custos::Base>::new();
+        let mut bias = Matrix::<f32, _>::new(&device, 1, 4);
+
+        init::constant(&mut bias, 0.1);
+        assert_eq!(&**bias, [0.1; 4]);
+    }
+
+    #[test]
+    fn test_orthogonal() {
+        let device = CPU::<custos::Base>::new();
+
+        for (rows, cols) in [(4, 6), (6, 4), (5, 5)] {
+            let mut weights = Matrix::<f64, _>::new(&device, rows, cols);
+            init::orthogonal(&mut weights, 2.);
+
+            // W^T W (or W W^T) equals gain² * I
+            let (vectors, len) = (rows.min(cols), rows.max(cols));
+            let at = |vector: usize, i: usize| {
+                if rows <= cols {
+                    weights[vector * cols + i]
+                } else {
+                    weights[i * cols + vector]
+                }
+            };
+
+            for lhs in 0..vectors {
+                for rhs in 0..vectors {
+                    let dot = (0..len).map(|i| at(lhs, i) * at(rhs, i)).sum::<f64>();
+                    let expected = if lhs == rhs { 4. } else { 0. };
+                    assert!((dot - expected).abs() < 1e-9, "{dot} != {expected}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Numeric CSV files.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use custos::{Alloc, Buffer, Device, OnNewBuffer, Read};
+
+use crate::Matrix;
+
+use super::invalid_data;
+
+/// Loads numeric CSV files into features and labels.
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{io::CsvLoader, CPU};
+///
+/// let csv = "label,a,b\n1,0.5,2\n0,1.5,-3\n";
+/// let data = CsvLoader::new().read::<f32>(csv.as_bytes()).unwrap();
+///
+/// let device = CPU::<custos::Base>::new();
+/// let x = data.features_matrix(&device);
+///
+/// assert_eq!((x.rows(), x.cols()), (2, 2));
+/// assert_eq!(x.read(), [0.5, 2., 1.5, -3.]);
+/// assert_eq!(data.labels, [1., 0.]);
+/// assert_eq!(data.header.unwrap(), ["label", "a", "b"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvLoader {
+    pub delimiter: char,
+    /// Whether the first line contains column names.
+    pub has_header: bool,
+    /// The column containing the labels. If `None`, every column is a feature.
+    pub label_column: Option<usize>,
+}
+
+/// The samples of a CSV file, stored on the host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvData<T> {
+    /// The number of samples.
+    pub rows: usize,
+    /// The number of feature columns.
+    pub cols: usize,
+    /// The features in row major order.
+    pub features: Vec<T>,
+    /// One label per sample. Empty if no label column was configured.
+    pub labels: Vec<T>,
+    pub header: Option<Vec<String>>,
+}
+
+impl Default for CsvLoader {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvLoader {
+    /// Creates a loader for comma separated files with a header and the labels in the first column.
+    #[inline]
+    pub fn new() -> Self {
+        CsvLoader {
+            delimiter: ',',
+            has_header: true,
+            label_column: Some(0),
+        }
+    }
+
+    #[inline]
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    #[inline]
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    #[inline]
+    pub fn label_column(mut self, label_column: Option<usize>) -> Self {
+        self.label_column = label_column;
+        self
+    }
+
+    /// Loads the CSV file at `path`.
+    #[inline]
+    pub fn load<T: FromStr>(&self, path: impl AsRef<Path>) -> io::Result<CsvData<T>> {
+        self.read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads CSV data. Empty lines are skipped, every other line must have the same number of fields.
+    pub fn read<T: FromStr>(&self, reader: impl BufRead) -> io::Result<CsvData<T>> {
+        let mut data = CsvData {
+            rows: 0,
+            cols: 0,
+            features: Vec::new(),
+            labels: Vec::new(),
+            header: None,
+        };
+        let mut fields_per_line = None;
+
+        for (line_idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = split_line(line, self.delimiter);
+            let line_number = line_idx + 1;
+
+            match fields_per_line {
+                None => {
+                    if let Some(label_column) = self.label_column {
+                        if label_column >= fields.len() {
+                            return Err(invalid_data(format!(
+                                "Line {line_number}: the label column {label_column} does not exist"
+                            )));
+                        }
+                    }
+                    fields_per_line = Some(fields.len());
+                    data.cols = fields.len() - self.label_column.is_some() as usize;
+
+                    if self.has_header {
+                        data.header = Some(fields);
+                        continue;
+                    }
+                }
+                Some(expected) if expected != fields.len() => {
+                    return Err(invalid_data(format!(
+                        "Line {line_number}: expected {expected} fields, found {}",
+                        fields.len()
+                    )));
+                }
+                _ => (),
+            }
+
+            for (col, field) in fields.iter().enumerate() {
+                let value = field.trim().parse::<T>().map_err(|_| {
+                    invalid_data(format!(
+                        "Line {line_number}, column {col}: \"{field}\" is not a number"
+                    ))
+                })?;
+
+                if Some(col) == self.label_column {
+                    data.labels.push(value);
+                } else {
+                    data.features.push(value);
+                }
+            }
+            data.rows += 1;
+        }
+
+        Ok(data)
+    }
+}
+
+/// Splits a line into fields. Fields may be quoted with `"`; `""` inside quotes is an escaped quote.
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ch if ch == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            ch => field.push(ch),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+impl<T: Copy> CsvData<T> {
+    /// Copies the features to `device`.
+    #[inline]
+    pub fn features_matrix<'a, D>(&self, device: &'a D) -> Matrix<'a, T, D>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        Matrix::from((device, self.rows, self.cols, self.features.clone()))
+    }
+
+    /// Copies the labels to `device`.
+    #[inline]
+    pub fn labels_buffer<'a, D>(&self, device: &'a D) -> Buffer<'a, T, D>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        Buffer::from((device, self.labels.clone()))
+    }
+
+    /// Copies the labels to `device` as a matrix with one column.
+    #[inline]
+    pub fn labels_matrix<'a, D>(&self, device: &'a D) -> Matrix<'a, T, D>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        Matrix::from((device, self.labels.len(), 1, self.labels.clone()))
+    }
+}
+
+/// Writes `data` with `cols` values per line. If provided, `header` is written as the first line.
+pub fn write_csv<T: Display>(
+    writer: &mut impl Write,
+    cols: usize,
+    data: &[T],
+    delimiter: char,
+    header: Option<&[&str]>,
+) -> io::Result<()> {
+    let mut line = String::new();
+
+    if let Some(header) = header {
+        for (idx, name) in header.iter().enumerate() {
+            if idx > 0 {
+                line.push(delimiter);
+            }
+            if name.contains(['"', delimiter, '\n']) {
+                line.push('"');
+                line.push_str(&name.replace('"', "\"\""));
+                line.push('"');
+            } else {
+                line.push_str(name);
+            }
+        }
+        writeln!(writer, "{line}")?;
+    }
+
+    for row in data.chunks(cols.max(1)) {
+        line.clear();
+        for (idx, value) in row.iter().enumerate() {
+            if idx > 0 {
+                line.push(delimiter);
+            }
+            line.push_str(&value.to_string());
+        }
+        writeln!(writer, "{line}")?;
+    }
+    Ok(())
+}
+
+impl<'a, T: Display + Default + Clone, D: Device> Matrix<'a, T, D> {
+    /// Saves the matrix as a comma separated file without a header, one row per line.
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> io::Result<()>
+    where
+        D: Read<T>,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_csv(&mut writer, self.cols(), &self.read_to_vec(), ',', None)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_line, write_csv, CsvLoader};
+
+    #[test]
+    fn test_split_line() {
+        assert_eq!(split_line("1,2,,3", ','), ["1", "2", "", "3"]);
+        assert_eq!(
+            split_line(r#""a;b";"say ""hi""";c"#, ';'),
+            ["a;b", r#"say "hi""#, "c"]
+        );
+    }
+
+    #[test]
+    fn test_read_csv_options() {
+        let csv = "1;2;3\r\n\n4;5;6\n";
+        let data = CsvLoader::new()
+            .delimiter(';')
+            .has_header(false)
+            .label_column(Some(2))
+            .read::<i32>(csv.as_bytes())
+            .unwrap();
+
+        assert_eq!((data.rows, data.cols), (2, 2));
+        assert_eq!(data.features, [1, 2, 4, 5]);
+        assert_eq!(data.labels, [3, 6]);
+        assert_eq!(data.header, None);
+
+        let data = CsvLoader::new()
+            .has_header(false)
+            .label_column(None)
+            .read::<f64>(" 1.5, -2\n3e2,4\n".as_bytes())
+            .unwrap();
+        assert_eq!(data.features, [1.5, -2., 300., 4.]);
+        assert!(data.labels.is_empty());
+    }
+
+    #[test]
+    fn test_read_csv_errors() {
+        let loader = CsvLoader::new().has_header(false);
+
+        let err = loader.read::<f32>("1,2\n3\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("Line 2"), "{err}");
+
+        assert!(loader.read::<f32>("1,x\n".as_bytes()).is_err());
+        assert!(loader
+            .label_column(Some(3))
+            .read::<f32>("1,2\n".as_bytes())
+            .is_err());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut out = vec![];
+        write_csv(&mut out, 2, &[1., 2.5, -3., 4.], ',', Some(&["x", "y,z"])).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "x,\"y,z\"\n1,2.5\n-3,4\n");
+    }
+}
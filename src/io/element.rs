@@ -0,0 +1,304 @@
+use core::fmt::Display;
+
+/// The element types that can be stored in files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dtype {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+}
+
+impl Dtype {
+    /// Returns the size of one element in bytes.
+    #[inline]
+    pub fn size(self) -> usize {
+        match self {
+            Dtype::U8 | Dtype::I8 => 1,
+            Dtype::U16 | Dtype::I16 => 2,
+            Dtype::U32 | Dtype::I32 | Dtype::F32 => 4,
+            Dtype::U64 | Dtype::I64 | Dtype::F64 => 8,
+        }
+    }
+
+    #[inline]
+    pub fn is_float(self) -> bool {
+        matches!(self, Dtype::F32 | Dtype::F64)
+    }
+
+    /// Returns the name used in safetensors headers, e.g. `"F32"`.
+    pub fn safetensors_name(self) -> &'static str {
+        match self {
+            Dtype::U8 => "U8",
+            Dtype::I8 => "I8",
+            Dtype::U16 => "U16",
+            Dtype::I16 => "I16",
+            Dtype::U32 => "U32",
+            Dtype::I32 => "I32",
+            Dtype::U64 => "U64",
+            Dtype::I64 => "I64",
+            Dtype::F32 => "F32",
+            Dtype::F64 => "F64",
+        }
+    }
+
+    pub fn from_safetensors_name(name: &str) -> Option<Dtype> {
+        Some(match name {
+            "U8" => Dtype::U8,
+            "I8" => Dtype::I8,
+            "U16" => Dtype::U16,
+            "I16" => Dtype::I16,
+            "U32" => Dtype::U32,
+            "I32" => Dtype::I32,
+            "U64" => Dtype::U64,
+            "I64" => Dtype::I64,
+            "F32" => Dtype::F32,
+            "F64" => Dtype::F64,
+            _ => return None,
+        })
+    }
+}
+
+impl Dtype {
+    /// Returns the little endian NumPy type string, e.g. `"<f4"`.
+    pub fn npy_descr(self) -> &'static str {
+        match self {
+            Dtype::U8 => "|u1",
+            Dtype::I8 => "|i1",
+            Dtype::U16 => "<u2",
+            Dtype::I16 => "<i2",
+            Dtype::U32 => "<u4",
+            Dtype::I32 => "<i4",
+            Dtype::U64 => "<u8",
+            Dtype::I64 => "<i8",
+            Dtype::F32 => "<f4",
+            Dtype::F64 => "<f8",
+        }
+    }
+
+    /// Parses a NumPy type string. Returns the dtype and whether the data is big endian.
+    pub fn from_npy_descr(descr: &str) -> Option<(Dtype, bool)> {
+        let mut chars = descr.chars();
+        let big_endian = match chars.next()? {
+            '<' | '|' => false,
+            '>' => true,
+            '=' => cfg!(target_endian = "big"),
+            _ => return None,
+        };
+
+        let dtype = match chars.as_str() {
+            "u1" => Dtype::U8,
+            "i1" => Dtype::I8,
+            "u2" => Dtype::U16,
+            "i2" => Dtype::I16,
+            "u4" => Dtype::U32,
+            "i4" => Dtype::I32,
+            "u8" => Dtype::U64,
+            "i8" => Dtype::I64,
+            "f4" => Dtype::F32,
+            "f8" => Dtype::F64,
+            _ => return None,
+        };
+        Some((dtype, big_endian))
+    }
+}
+
+impl Display for Dtype {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.safetensors_name())
+    }
+}
+
+/// A number that can be converted from and to its byte representation.
+pub trait Element: Copy + Default + 'static {
+    const DTYPE: Dtype;
+
+    /// Appends the little endian bytes of `self` to `bytes`.
+    fn extend_le_bytes(self, bytes: &mut Vec<u8>);
+
+    /// # Panics
+    /// If the length of `bytes` is not the size of `Self`.
+    fn from_le_slice(bytes: &[u8]) -> Self;
+
+    /// # Panics
+    /// If the length of `bytes` is not the size of `Self`.
+    fn from_be_slice(bytes: &[u8]) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    /// Converts with the semantics of `as`, i.e. integers saturate.
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_element {
+    ($($ty:ty => $dtype:ident),*) => {
+        $(
+            impl Element for $ty {
+                const DTYPE: Dtype = Dtype::$dtype;
+
+                #[inline]
+                fn extend_le_bytes(self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes())
+                }
+
+                #[inline]
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    <$ty>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline]
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    <$ty>::from_be_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn from_f64(value: f64) -> Self {
+                    value as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_element! {
+    u8 => U8, i8 => I8, u16 => U16, i16 => I16, u32 => U32, i32 => I32,
+    u64 => U64, i64 => I64, f32 => F32, f64 => F64
+}
+
+/// Appends the little endian bytes of every value to `bytes`.
+pub fn encode_le<T: Element>(values: &[T], bytes: &mut Vec<u8>) {
+    bytes.reserve(values.len() * T::DTYPE.size());
+    for value in values {
+        value.extend_le_bytes(bytes);
+    }
+}
+
+/// Converts little endian `bytes` into values. Trailing bytes that do not form a full value are ignored.
+pub fn decode_le<T: Element>(bytes: &[u8]) -> Vec<T> {
+    bytes
+        .chunks_exact(T::DTYPE.size())
+        .map(T::from_le_slice)
+        .collect()
+}
+
+/// Converts big endian `bytes` into values. Trailing bytes that do not form a full value are ignored.
+pub fn decode_be<T: Element>(bytes: &[u8]) -> Vec<T> {
+    bytes
+        .chunks_exact(T::DTYPE.size())
+        .map(T::from_be_slice)
+        .collect()
+}
+
+fn decode_converted<S: Element, T: Element>(bytes: &[u8], big_endian: bool) -> Vec<T> {
+    bytes
+        .chunks_exact(S::DTYPE.size())
+        .map(|bytes| {
+            let value = if big_endian {
+                S::from_be_slice(bytes)
+            } else {
+                S::from_le_slice(bytes)
+            };
+            T::from_f64(value.to_f64())
+        })
+        .collect()
+}
+
+/// Converts `bytes` holding values of `dtype` into values of `T`.
+/// Integers are converted to floats and floats to floats of another precision (through `f64`).
+/// Returns `None` for float to integer conversions, as they would truncate silently.
+/// Integer to integer conversions saturate, integers beyond `2^53` lose precision.
+pub fn decode_as<T: Element>(dtype: Dtype, bytes: &[u8], big_endian: bool) -> Option<Vec<T>> {
+    if dtype == T::DTYPE {
+        return Some(if big_endian {
+            decode_be(bytes)
+        } else {
+            decode_le(bytes)
+        });
+    }
+
+    if dtype.is_float() && !T::DTYPE.is_float() {
+        return None;
+    }
+
+    Some(match dtype {
+        Dtype::U8 => decode_converted::<u8, T>(bytes, big_endian),
+        Dtype::I8 => decode_converted::<i8, T>(bytes, big_endian),
+        Dtype::U16 => decode_converted::<u16, T>(bytes, big_endian),
+        Dtype::I16 => decode_converted::<i16, T>(bytes, big_endian),
+        Dtype::U32 => decode_converted::<u32, T>(bytes, big_endian),
+        Dtype::I32 => decode_converted::<i32, T>(bytes, big_endian),
+        Dtype::U64 => decode_converted::<u64, T>(bytes, big_endian),
+        Dtype::I64 => decode_converted::<i64, T>(bytes, big_endian),
+        Dtype::F32 => decode_converted::<f32, T>(bytes, big_endian),
+        Dtype::F64 => decode_converted::<f64, T>(bytes, big_endian),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_as, decode_be, decode_le, encode_le, Dtype, Element};
+
+    #[test]
+    fn test_encode_decode_le() {
+        let mut bytes = vec![];
+        encode_le(&[1f32, -2.5], &mut bytes);
+        assert_eq!(bytes, [0, 0, 128, 63, 0, 0, 32, 192]);
+        assert_eq!(decode_le::<f32>(&bytes), [1., -2.5]);
+    }
+
+    #[test]
+    fn test_decode_be() {
+        assert_eq!(decode_be::<u32>(&[0, 0, 8, 3, 0, 0, 0, 1]), [2051, 1]);
+    }
+
+    #[test]
+    fn test_decode_as() {
+        let mut bytes = vec![];
+        encode_le(&[1.5f64, -2.], &mut bytes);
+        assert_eq!(
+            decode_as::<f32>(Dtype::F64, &bytes, false),
+            Some(vec![1.5, -2.])
+        );
+        assert_eq!(decode_as::<i32>(Dtype::F64, &bytes, false), None);
+
+        assert_eq!(
+            decode_as::<f32>(Dtype::I16, &[255, 255, 0, 3], true),
+            Some(vec![-1., 3.])
+        );
+        assert_eq!(
+            decode_as::<u8>(Dtype::I32, &[44, 1, 0, 0], false),
+            Some(vec![255])
+        );
+    }
+
+    #[test]
+    fn test_dtype_names() {
+        for dtype in [Dtype::U8, Dtype::I32, Dtype::I64, Dtype::F32, Dtype::F64] {
+            assert_eq!(
+                Dtype::from_safetensors_name(dtype.safetensors_name()),
+                Some(dtype)
+            );
+        }
+        assert_eq!(f64::DTYPE.size(), 8);
+        assert_eq!(Dtype::from_npy_descr("<f4"), Some((Dtype::F32, false)));
+        assert_eq!(Dtype::from_npy_descr(">i8"), Some((Dtype::I64, true)));
+        assert_eq!(
+            Dtype::from_npy_descr(Dtype::U8.npy_descr()),
+            Some((Dtype::U8, false))
+        );
+        assert_eq!(Dtype::from_npy_descr("<c8"), None);
+        assert_eq!(Dtype::from_safetensors_name("BF16"), None);
+    }
+}
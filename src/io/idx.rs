@@ -0,0 +1,239 @@
+//! The IDX format of the MNIST files: a magic number with the dtype and the number of dimensions,
+//! big endian `u32` dimensions and big endian data.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use custos::{
+    prelude::{Float, Number},
+    Alloc, Buffer, Device, OnNewBuffer,
+};
+
+use crate::{nn::StateEntry, Matrix};
+
+use super::{decode_be, invalid_data, Dtype, Element};
+
+fn dtype_from_code(code: u8) -> Option<Dtype> {
+    Some(match code {
+        0x08 => Dtype::U8,
+        0x09 => Dtype::I8,
+        0x0B => Dtype::I16,
+        0x0C => Dtype::I32,
+        0x0D => Dtype::F32,
+        0x0E => Dtype::F64,
+        _ => return None,
+    })
+}
+
+/// Reads an uncompressed IDX file whose dtype is the one of `T`.
+pub fn read_idx<T: Element>(reader: &mut impl Read) -> io::Result<StateEntry<T>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+
+    if magic[..2] == [0x1f, 0x8b] {
+        return Err(invalid_data(
+            "The IDX file is gzip compressed, decompress it first (e.g. with gunzip)",
+        ));
+    }
+    if magic[..2] != [0, 0] {
+        return Err(invalid_data("Not an IDX file"));
+    }
+
+    let dtype = dtype_from_code(magic[2])
+        .ok_or_else(|| invalid_data(format!("Unknown IDX dtype {:#04x}", magic[2])))?;
+    if dtype != T::DTYPE {
+        return Err(invalid_data(format!(
+            "The IDX file contains {dtype} values, expected {}",
+            T::DTYPE
+        )));
+    }
+
+    let mut dims = vec![0; magic[3] as usize * 4];
+    reader.read_exact(&mut dims)?;
+    let shape = decode_be::<u32>(&dims)
+        .into_iter()
+        .map(|dim| dim as usize)
+        .collect::<Vec<_>>();
+
+    let size = shape
+        .iter()
+        .try_fold(dtype.size(), |size, &dim| size.checked_mul(dim))
+        .ok_or_else(|| invalid_data(format!("The IDX shape {shape:?} is too large")))?;
+
+    // grows with the read data instead of trusting the header with the allocation
+    let mut bytes = Vec::new();
+    reader.by_ref().take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "The IDX data is shorter than its shape",
+        ));
+    }
+
+    Ok(StateEntry {
+        shape,
+        data: decode_be(&bytes),
+    })
+}
+
+/// Loads the uncompressed IDX file at `path`, see [`read_idx`].
+pub fn load_idx<T: Element>(path: impl AsRef<Path>) -> io::Result<StateEntry<T>> {
+    read_idx(&mut BufReader::new(File::open(path)?))
+}
+
+/// Loads IDX images (e.g. `train-images-idx3-ubyte`) onto `device`.
+/// Every image becomes a row with values normalised to `[0, 1]`.
+pub fn load_idx_images<'a, T, D>(
+    device: &'a D,
+    path: impl AsRef<Path>,
+) -> io::Result<Matrix<'a, T, D>>
+where
+    T: Float,
+    D: Alloc<T> + OnNewBuffer<T, D>,
+{
+    let images = load_idx::<u8>(path)?;
+    let Some((&samples, pixels)) = images.shape.split_first() else {
+        return Err(invalid_data("The IDX images have no dimensions"));
+    };
+
+    let max = T::from_u64(255);
+    let data = images
+        .data
+        .into_iter()
+        .map(|pixel| T::from_u64(pixel as u64) / max)
+        .collect::<Vec<_>>();
+
+    Ok(Matrix::from((
+        device,
+        samples,
+        pixels.iter().product(),
+        data,
+    )))
+}
+
+/// Loads IDX labels (e.g. `train-labels-idx1-ubyte`) as class indices onto `device`.
+pub fn load_idx_labels<'a, T, D>(
+    device: &'a D,
+    path: impl AsRef<Path>,
+) -> io::Result<Buffer<'a, T, D>>
+where
+    T: Number,
+    D: Alloc<T> + OnNewBuffer<T, D>,
+{
+    let labels = load_idx::<u8>(path)?;
+    if labels.shape.len() != 1 {
+        return Err(invalid_data(format!(
+            "Expected one-dimensional IDX labels, found the shape {:?}",
+            labels.shape
+        )));
+    }
+
+    let data = labels
+        .data
+        .into_iter()
+        .map(|label| T::from_u64(label as u64))
+        .collect::<Vec<_>>();
+    Ok(Buffer::from((device, data)))
+}
+
+/// Loads the MNIST training (or test) set from the standard files in `dir`:
+/// `train-images-idx3-ubyte` and `train-labels-idx1-ubyte` (or `t10k-…`).
+/// The names with a dot before `idx` (e.g. `train-images.idx3-ubyte`) are found as well.
+///
+/// Returns the normalised images, one per row, and the digits.
+pub fn load_mnist<'a, T, D>(
+    device: &'a D,
+    dir: impl AsRef<Path>,
+    train: bool,
+) -> io::Result<(Matrix<'a, T, D>, Buffer<'a, T, D>)>
+where
+    T: Float,
+    D: Alloc<T> + OnNewBuffer<T, D>,
+{
+    let dir = dir.as_ref();
+    let prefix = if train { "train" } else { "t10k" };
+
+    let find = |kind: &str, dims: u8| {
+        [
+            dir.join(format!("{prefix}-{kind}-idx{dims}-ubyte")),
+            dir.join(format!("{prefix}-{kind}.idx{dims}-ubyte")),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No MNIST {prefix} {kind} in {}", dir.display()),
+            )
+        })
+    };
+
+    let images = load_idx_images(device, find("images", 3)?)?;
+    let labels = load_idx_labels(device, find("labels", 1)?)?;
+
+    if images.rows() != labels.len() {
+        return Err(invalid_data(format!(
+            "{} images, but {} labels",
+            images.rows(),
+            labels.len()
+        )));
+    }
+    Ok((images, labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_idx;
+
+    /// Two 2x3 "images" in the IDX format.
+    fn idx_images() -> Vec<u8> {
+        let mut bytes = vec![0, 0, 0x08, 3];
+        for dim in [2u32, 2, 3] {
+            bytes.extend_from_slice(&dim.to_be_bytes());
+        }
+        bytes.extend_from_slice(&[0, 51, 102, 153, 204, 255, 255, 0, 0, 0, 0, 255]);
+        bytes
+    }
+
+    #[test]
+    fn test_read_idx_u8() {
+        let images = read_idx::<u8>(&mut &idx_images()[..]).unwrap();
+        assert_eq!(images.shape, [2, 2, 3]);
+        assert_eq!(images.data[..6], [0, 51, 102, 153, 204, 255]);
+    }
+
+    #[test]
+    fn test_read_idx_i32_big_endian() {
+        let mut bytes = vec![0, 0, 0x0C, 1, 0, 0, 0, 2];
+        bytes.extend_from_slice(&(-2i32).to_be_bytes());
+        bytes.extend_from_slice(&70000i32.to_be_bytes());
+
+        let entry = read_idx::<i32>(&mut &bytes[..]).unwrap();
+        assert_eq!(entry.shape, [2]);
+        assert_eq!(entry.data, [-2, 70000]);
+    }
+
+    #[test]
+    fn test_read_idx_errors() {
+        // wrong dtype
+        assert!(read_idx::<f32>(&mut &idx_images()[..]).is_err());
+
+        // gzip
+        let err = read_idx::<u8>(&mut &[0x1f, 0x8b, 8, 0][..]).unwrap_err();
+        assert!(err.to_string().contains("gzip"));
+
+        // truncated
+        let images = idx_images();
+        assert!(read_idx::<u8>(&mut &images[..images.len() - 1]).is_err());
+
+        // the size of the shape overflows usize on 32 bit targets and is missing on 64 bit ones
+        let mut bytes = vec![0, 0, 0x0E, 3];
+        for _ in 0..3 {
+            bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        }
+        assert!(read_idx::<f64>(&mut &bytes[..]).is_err());
+    }
+}
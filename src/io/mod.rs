@@ -0,0 +1,21 @@
+//! Reading and writing of parameters and datasets.
+
+mod csv;
+mod element;
+mod idx;
+pub(crate) mod json;
+mod npy;
+mod safetensors;
+pub(crate) mod zip;
+
+pub use csv::*;
+pub use element::*;
+pub use idx::*;
+pub use npy::*;
+pub use safetensors::*;
+
+/// Creates an [`std::io::Error`] of kind [`InvalidData`](std::io::ErrorKind::InvalidData).
+#[inline]
+pub(crate) fn invalid_data(msg: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg.into())
+}
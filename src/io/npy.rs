@@ -0,0 +1,423 @@
+//! NumPy `.npy` files and `.npz` archives of `.npy` files.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use custos::{Alloc, Device, OnNewBuffer};
+
+use crate::{
+    nn::{StateDict, StateEntry},
+    Matrix,
+};
+
+use super::{decode_as, encode_le, invalid_data, zip, Dtype, Element};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Writes a C ordered array with the given shape in the `.npy` format (version 1.0).
+///
+/// # Panics
+/// If the product of `shape` does not equal the length of `data`.
+pub fn write_npy<T: Element>(
+    writer: &mut impl Write,
+    shape: &[usize],
+    data: &[T],
+) -> io::Result<()> {
+    assert_eq!(
+        shape.iter().product::<usize>(),
+        data.len(),
+        "The shape {shape:?} does not match the length of the data"
+    );
+
+    let shape = match shape {
+        [len] => format!("({len},)"),
+        shape => format!(
+            "({})",
+            shape
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {shape}, }}",
+        T::DTYPE.npy_descr()
+    );
+
+    // magic, version and header length take 10 bytes. The data is aligned to 64 bytes
+    while (10 + header.len() + 1) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let header_len = u16::try_from(header.len())
+        .map_err(|_| invalid_data("The npy header is too large for version 1.0"))?;
+
+    let mut bytes = Vec::with_capacity(10 + header.len() + data.len() * T::DTYPE.size());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&header_len.to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    encode_le(data, &mut bytes);
+
+    writer.write_all(&bytes)
+}
+
+/// Returns the text after `'key':` in a header dictionary.
+fn header_value<'h>(header: &'h str, key: &str) -> Option<&'h str> {
+    let start = header
+        .find(&format!("'{key}'"))
+        .or_else(|| header.find(&format!("\"{key}\"")))?;
+    let rest = header[start + key.len() + 2..].trim_start();
+    Some(rest.strip_prefix(':')?.trim_start())
+}
+
+/// Parses the header dictionary: `{'descr': '<f4', 'fortran_order': False, 'shape': (3, 4), }`.
+fn parse_header(header: &str) -> io::Result<(Dtype, bool, bool, Vec<usize>)> {
+    let invalid = |what: &str| invalid_data(format!("Invalid npy header, {what}: {header}"));
+
+    let descr = header_value(header, "descr").ok_or_else(|| invalid("missing descr"))?;
+    let quote = descr
+        .chars()
+        .next()
+        .ok_or_else(|| invalid("missing descr"))?;
+    let descr = descr
+        .get(quote.len_utf8()..)
+        .ok_or_else(|| invalid("invalid descr"))?
+        .split(quote)
+        .next()
+        .ok_or_else(|| invalid("invalid descr"))?;
+    let (dtype, big_endian) =
+        Dtype::from_npy_descr(descr).ok_or_else(|| invalid("unsupported descr"))?;
+
+    let fortran_order = header_value(header, "fortran_order")
+        .ok_or_else(|| invalid("missing fortran_order"))?
+        .starts_with("True");
+
+    let shape = header_value(header, "shape").ok_or_else(|| invalid("missing shape"))?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|shape| shape.split(')').next())
+        .ok_or_else(|| invalid("invalid shape"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| dim.trim_end_matches('L').parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid("invalid shape"))?;
+
+    Ok((dtype, big_endian, fortran_order, shape))
+}
+
+/// Reorders Fortran (column major) ordered `data` to C (row major) order.
+fn fortran_to_c<T: Copy>(shape: &[usize], data: &[T]) -> Vec<T> {
+    (0..data.len())
+        .map(|mut idx| {
+            // the index of the last dimension changes the fastest in C order
+            let mut offset = 0;
+            let mut stride = data.len();
+            for &dim in shape.iter().rev() {
+                stride /= dim;
+                offset += (idx % dim) * stride;
+                idx /= dim;
+            }
+            data[offset]
+        })
+        .collect()
+}
+
+/// Reads a `.npy` array and converts its values to `T`, see [`decode_as`] for the supported conversions.
+/// The returned data is in C order.
+pub fn read_npy<T: Element>(reader: &mut impl Read) -> io::Result<StateEntry<T>> {
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid_data("Not an npy file"));
+    }
+
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => {
+            return Err(invalid_data(format!(
+                "Unsupported npy version {version}.{}",
+                preamble[7]
+            )))
+        }
+    };
+
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header =
+        std::str::from_utf8(&header).map_err(|_| invalid_data("The npy header is not utf-8"))?;
+    let (dtype, big_endian, fortran_order, shape) = parse_header(header)?;
+
+    let size = shape
+        .iter()
+        .try_fold(dtype.size(), |size, &dim| size.checked_mul(dim))
+        .ok_or_else(|| invalid_data(format!("The npy shape {shape:?} is too large")))?;
+
+    // grows with the read data instead of trusting the header with the allocation
+    let mut bytes = Vec::new();
+    reader.by_ref().take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "The npy data is shorter than its shape",
+        ));
+    }
+
+    let data = decode_as(dtype, &bytes, big_endian).ok_or_else(|| {
+        invalid_data(format!(
+            "The npy file contains {} values, which can not be converted to {}",
+            dtype.npy_descr(),
+            T::DTYPE.npy_descr()
+        ))
+    })?;
+    let data = if fortran_order && shape.len() > 1 {
+        fortran_to_c(&shape, &data)
+    } else {
+        data
+    };
+
+    Ok(StateEntry { shape, data })
+}
+
+/// Saves a C ordered array with the given shape to a `.npy` file at `path`.
+pub fn save_npy<T: Element>(path: impl AsRef<Path>, shape: &[usize], data: &[T]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy(&mut writer, shape, data)?;
+    writer.flush()
+}
+
+/// Loads the `.npy` file at `path`, see [`read_npy`].
+pub fn load_npy<T: Element>(path: impl AsRef<Path>) -> io::Result<StateEntry<T>> {
+    read_npy(&mut BufReader::new(File::open(path)?))
+}
+
+/// Writes every entry as `<name>.npy` to an uncompressed `.npz` archive, like `numpy.savez`.
+pub fn write_npz<T: Element>(writer: &mut impl Write, arrays: &StateDict<T>) -> io::Result<()> {
+    let files = arrays
+        .iter()
+        .map(|(name, entry)| {
+            let mut npy = Vec::new();
+            write_npy(&mut npy, &entry.shape, &entry.data)?;
+            Ok((format!("{name}.npy"), npy))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    zip::write_stored(
+        writer,
+        files
+            .iter()
+            .map(|(name, npy)| (name.as_str(), npy.as_slice())),
+    )
+}
+
+/// Reads an uncompressed `.npz` archive. The names of the arrays do not contain the `.npy` extension.
+/// The arrays may have different dtypes, every array is converted to `T` like in [`read_npy`].
+pub fn read_npz<T: Element>(reader: &mut impl Read) -> io::Result<StateDict<T>> {
+    let mut archive = Vec::new();
+    reader.read_to_end(&mut archive)?;
+
+    let mut arrays = StateDict::new();
+    for (name, mut npy) in zip::read_stored(&archive)? {
+        let entry = read_npy(&mut npy)?;
+        let name = name.strip_suffix(".npy").unwrap_or(&name);
+        arrays.insert(name, entry.shape, entry.data);
+    }
+    Ok(arrays)
+}
+
+/// Saves `arrays` to an uncompressed `.npz` archive at `path`.
+pub fn save_npz<T: Element>(path: impl AsRef<Path>, arrays: &StateDict<T>) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npz(&mut writer, arrays)?;
+    writer.flush()
+}
+
+/// Loads the uncompressed `.npz` archive at `path`, see [`read_npz`].
+pub fn load_npz<T: Element>(path: impl AsRef<Path>) -> io::Result<StateDict<T>> {
+    read_npz(&mut BufReader::new(File::open(path)?))
+}
+
+impl<'a, T: Element, D: Device> Matrix<'a, T, D> {
+    /// Loads a two-dimensional `.npy` array onto `device`.
+    /// Use [`load_npy`] and [`StateEntry::to_buffer`] for arrays with other dimensions.
+    pub fn read_npy(device: &'a D, path: impl AsRef<Path>) -> io::Result<Matrix<'a, T, D>>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        let entry = load_npy::<T>(path)?;
+        let [rows, cols] = entry.shape[..] else {
+            return Err(invalid_data(format!(
+                "Expected a two-dimensional array, found the shape {:?}",
+                entry.shape
+            )));
+        };
+        Ok(Matrix::from((device, rows, cols, entry.data)))
+    }
+
+    /// Saves the matrix as a two-dimensional `.npy` array.
+    pub fn write_npy(&self, path: impl AsRef<Path>) -> io::Result<()>
+    where
+        D: custos::Read<T>,
+    {
+        save_npy(path, &[self.rows(), self.cols()], &self.read_to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nn::StateDict;
+
+    use super::{fortran_to_c, parse_header, read_npy, read_npz, write_npy, write_npz};
+    use crate::io::Dtype;
+
+    #[test]
+    fn test_npy_roundtrip() {
+        let mut bytes = vec![];
+        write_npy(&mut bytes, &[2, 3], &[1f32, 2., 3., 4., 5., 6.]).unwrap();
+
+        // the data is aligned to 64 bytes
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        assert_eq!(bytes.len(), 10 + header_len + 6 * 4);
+
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (2, 3), }"));
+        assert!(header.ends_with('\n'));
+
+        let entry = read_npy::<f32>(&mut &bytes[..]).unwrap();
+        assert_eq!(entry.shape, [2, 3]);
+        assert_eq!(entry.data, [1., 2., 3., 4., 5., 6.]);
+
+        // floats are converted to other floats, but not truncated to integers
+        assert_eq!(
+            read_npy::<f64>(&mut &bytes[..]).unwrap().data,
+            [1., 2., 3., 4., 5., 6.]
+        );
+        assert!(read_npy::<i32>(&mut &bytes[..]).is_err());
+
+        // truncated data
+        assert!(read_npy::<f32>(&mut &bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_read_npy_huge_shape() {
+        let header = format!(
+            "{{'descr': '<f8', 'fortran_order': False, 'shape': ({}, 4), }}\n",
+            usize::MAX / 8
+        );
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        assert!(read_npy::<f64>(&mut &bytes[..]).is_err());
+
+        // fits into usize, but the data is missing
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (1000000000000,), }\n";
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(&[0; 16]);
+        assert!(read_npy::<f64>(&mut &bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_npy_one_and_zero_dims() {
+        let mut bytes = vec![];
+        write_npy(&mut bytes, &[3], &[1i64, 2, 3]).unwrap();
+        assert!(std::str::from_utf8(&bytes[10..])
+            .unwrap()
+            .contains("'shape': (3,)"));
+        assert_eq!(read_npy::<i64>(&mut &bytes[..]).unwrap().shape, [3]);
+
+        let mut bytes = vec![];
+        write_npy(&mut bytes, &[], &[7u8]).unwrap();
+        let entry = read_npy::<u8>(&mut &bytes[..]).unwrap();
+        assert!(entry.shape.is_empty());
+        assert_eq!(entry.data, [7]);
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let (dtype, big_endian, fortran_order, shape) =
+            parse_header("{'descr': '>i4', 'fortran_order': True, 'shape': (2, 3), }").unwrap();
+        assert_eq!(dtype, Dtype::I32);
+        assert!(big_endian && fortran_order);
+        assert_eq!(shape, [2, 3]);
+
+        assert!(parse_header("{'descr': '<c16', 'fortran_order': False, 'shape': (), }").is_err());
+        // a multi-byte quote must not panic
+        assert!(parse_header("{'descr': é, 'fortran_order': False, 'shape': (), }").is_err());
+    }
+
+    #[test]
+    fn test_read_fortran_big_endian_npy() {
+        // np.asfortranarray(np.array([[1, 2, 3], [4, 5, 6]], dtype='>i2'))
+        let header = "{'descr': '>i2', 'fortran_order': True, 'shape': (2, 3), }\n";
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for value in [1i16, 4, 2, 5, 3, 6] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+
+        let entry = read_npy::<i16>(&mut &bytes[..]).unwrap();
+        assert_eq!(entry.shape, [2, 3]);
+        assert_eq!(entry.data, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_fortran_to_c_3d() {
+        // shape (2, 2, 2), value = 100 * i + 10 * j + k
+        let fortran = [0, 100, 10, 110, 1, 101, 11, 111];
+        assert_eq!(
+            fortran_to_c(&[2, 2, 2], &fortran),
+            [0, 1, 10, 11, 100, 101, 110, 111]
+        );
+    }
+
+    #[test]
+    fn test_npz_roundtrip() {
+        let mut arrays = StateDict::new();
+        arrays.insert("x", vec![2, 2], vec![1., 2., 3., 4.]);
+        arrays.insert("y", vec![2], vec![0., 1.]);
+
+        let mut bytes = vec![];
+        write_npz(&mut bytes, &arrays).unwrap();
+
+        assert_eq!(read_npz::<f64>(&mut &bytes[..]).unwrap(), arrays);
+    }
+
+    #[test]
+    fn test_read_npz_mixed_dtypes() {
+        let mut x = vec![];
+        write_npy(&mut x, &[2], &[0.5f64, -1.]).unwrap();
+        let mut steps = vec![];
+        write_npy(&mut steps, &[1], &[3i64]).unwrap();
+
+        let mut bytes = vec![];
+        crate::io::zip::write_stored(
+            &mut bytes,
+            [("x.npy", x.as_slice()), ("steps.npy", steps.as_slice())],
+        )
+        .unwrap();
+
+        let arrays = read_npz::<f32>(&mut &bytes[..]).unwrap();
+        assert_eq!(arrays.get("x").unwrap().data, [0.5, -1.]);
+        assert_eq!(arrays.get("steps").unwrap().data, [3.]);
+    }
+}
@@ -0,0 +1,264 @@
+//! The [safetensors](https://github.com/huggingface/safetensors) format:
+//! an 8 byte little endian header size, a JSON header and the raw little endian data.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::nn::StateDict;
+
+use super::{decode_as, encode_le, invalid_data, json, Dtype, Element};
+
+/// The largest accepted header (100 MB), which guards against allocating huge buffers for corrupt files.
+const MAX_HEADER_SIZE: u64 = 100_000_000;
+
+/// Writes `state_dict` in the safetensors format.
+pub fn write_safetensors<T: Element>(
+    writer: &mut impl Write,
+    state_dict: &StateDict<T>,
+) -> io::Result<()> {
+    let mut header = String::from("{");
+    let mut data = Vec::new();
+
+    for (idx, (name, entry)) in state_dict.iter().enumerate() {
+        if idx > 0 {
+            header.push(',');
+        }
+
+        let begin = data.len();
+        encode_le(&entry.data, &mut data);
+
+        json::write_string(&mut header, name);
+        let shape = entry
+            .shape
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        header.push_str(&format!(
+            ":{{\"dtype\":\"{}\",\"shape\":[{shape}],\"data_offsets\":[{begin},{}]}}",
+            T::DTYPE.safetensors_name(),
+            data.len()
+        ));
+    }
+    header.push('}');
+
+    // the data is aligned to 8 bytes
+    while header.len() % 8 != 0 {
+        header.push(' ');
+    }
+
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(&data)?;
+    Ok(())
+}
+
+/// Reads a safetensors file and converts its tensors to `T`, see [`decode_as`] for the supported conversions.
+/// The tensors may have different dtypes. The metadata is ignored.
+pub fn read_safetensors<T: Element>(reader: &mut impl Read) -> io::Result<StateDict<T>> {
+    let mut header_size = [0; 8];
+    reader.read_exact(&mut header_size)?;
+    let header_size = u64::from_le_bytes(header_size);
+
+    if header_size > MAX_HEADER_SIZE {
+        return Err(invalid_data(format!(
+            "The safetensors header size {header_size} exceeds the maximum size"
+        )));
+    }
+
+    let mut header = vec![0; header_size as usize];
+    reader.read_exact(&mut header)?;
+    let header = std::str::from_utf8(&header)
+        .map_err(|_| invalid_data("The safetensors header is not valid utf-8"))?;
+    let header = json::parse(header).map_err(invalid_data)?;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    let members = header
+        .as_object()
+        .ok_or_else(|| invalid_data("The safetensors header is not a JSON object"))?;
+
+    let mut state_dict = StateDict::new();
+    for (name, info) in members {
+        if name == "__metadata__" {
+            continue;
+        }
+        let (shape, values) = read_tensor::<T>(name, info, &data)?;
+        state_dict.insert(name.clone(), shape, values);
+    }
+    Ok(state_dict)
+}
+
+fn read_tensor<T: Element>(
+    name: &str,
+    info: &json::Json,
+    data: &[u8],
+) -> io::Result<(Vec<usize>, Vec<T>)> {
+    let invalid = |what: &str| invalid_data(format!("Tensor \"{name}\": {what}"));
+
+    let dtype = info
+        .get("dtype")
+        .and_then(json::Json::as_str)
+        .ok_or_else(|| invalid("missing dtype"))?;
+    let dtype = Dtype::from_safetensors_name(dtype)
+        .ok_or_else(|| invalid(&format!("unsupported dtype {dtype}")))?;
+
+    let usizes = |key: &str| {
+        info.get(key)
+            .and_then(json::Json::as_array)
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(json::Json::as_usize)
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or_else(|| invalid(&format!("invalid {key}")))
+    };
+
+    let shape = usizes("shape")?;
+    let offsets = usizes("data_offsets")?;
+    let [begin, end] = offsets[..] else {
+        return Err(invalid("invalid data_offsets"));
+    };
+
+    if begin > end || end > data.len() {
+        return Err(invalid("data_offsets out of bounds"));
+    }
+    let size = shape
+        .iter()
+        .try_fold(dtype.size(), |size, &dim| size.checked_mul(dim))
+        .ok_or_else(|| invalid("the size of the shape overflows"))?;
+    if size != end - begin {
+        return Err(invalid("the shape does not match the data_offsets"));
+    }
+
+    let values = decode_as(dtype, &data[begin..end], false).ok_or_else(|| {
+        invalid(&format!(
+            "dtype {dtype} can not be converted to {}",
+            T::DTYPE
+        ))
+    })?;
+    Ok((shape, values))
+}
+
+/// Saves `state_dict` to a safetensors file at `path`.
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{
+///     io::{load_safetensors, save_safetensors},
+///     nn::{Linear, Module},
+///     CPU,
+/// };
+///
+/// let device = CPU::<custos::Autograd<custos::Base>>::new();
+/// let mut lin = Linear::<f32, _, 2, 3>::new(&device);
+///
+/// let path = std::env::temp_dir().join("sliced_doc_linear.safetensors");
+/// save_safetensors(&path, &lin.state_dict()).unwrap();
+///
+/// let mut restored = Linear::<f32, _, 2, 3>::new(&device);
+/// restored.load_state_dict(&load_safetensors(&path).unwrap()).unwrap();
+/// assert_eq!(restored.weights.read(), lin.weights.read());
+/// ```
+pub fn save_safetensors<T: Element>(
+    path: impl AsRef<Path>,
+    state_dict: &StateDict<T>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_safetensors(&mut writer, state_dict)?;
+    writer.flush()
+}
+
+/// Loads a safetensors file at `path` and converts its tensors to `T`.
+pub fn load_safetensors<T: Element>(path: impl AsRef<Path>) -> io::Result<StateDict<T>> {
+    read_safetensors(&mut BufReader::new(File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nn::StateDict;
+
+    use super::{read_safetensors, write_safetensors};
+
+    #[test]
+    fn test_safetensors_roundtrip() {
+        let mut state_dict = StateDict::new();
+        state_dict.insert("b", vec![2, 2], vec![1f64, 2., 3., 4.]);
+        state_dict.insert("a", vec![3], vec![-1., 0.5, 7.]);
+
+        let mut bytes = vec![];
+        write_safetensors(&mut bytes, &state_dict).unwrap();
+
+        let header_size = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+        assert_eq!(header_size % 8, 0);
+        assert_eq!(bytes.len(), 8 + header_size + 7 * 8);
+
+        let header = std::str::from_utf8(&bytes[8..8 + header_size]).unwrap();
+        assert_eq!(
+            header.trim_end(),
+            r#"{"a":{"dtype":"F64","shape":[3],"data_offsets":[0,24]},"b":{"dtype":"F64","shape":[2,2],"data_offsets":[24,56]}}"#
+        );
+
+        let read = read_safetensors::<f64>(&mut &bytes[..]).unwrap();
+        assert_eq!(read, state_dict);
+    }
+
+    #[test]
+    fn test_read_safetensors_with_metadata() {
+        let header = r#"{"__metadata__":{"format":"pt"},"x":{"dtype":"I32","shape":[2],"data_offsets":[0,8]}}"#;
+
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(&[1, 0, 0, 0, 255, 255, 255, 255]);
+
+        let state_dict = read_safetensors::<i32>(&mut &bytes[..]).unwrap();
+        assert_eq!(state_dict.len(), 1);
+        assert_eq!(state_dict.get("x").unwrap().data, [1, -1]);
+    }
+
+    #[test]
+    fn test_read_safetensors_errors() {
+        let mut state_dict = StateDict::new();
+        state_dict.insert("x", vec![2], vec![1f32, 2.]);
+
+        let mut bytes = vec![];
+        write_safetensors(&mut bytes, &state_dict).unwrap();
+
+        // floats are not truncated to integers
+        assert!(read_safetensors::<i32>(&mut &bytes[..]).is_err());
+
+        // truncated data
+        assert!(read_safetensors::<f32>(&mut &bytes[..bytes.len() - 1]).is_err());
+
+        // the size of the shape overflows
+        let header = format!(
+            r#"{{"x":{{"dtype":"F32","shape":[{},4],"data_offsets":[0,0]}}}}"#,
+            usize::MAX / 2
+        );
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        assert!(read_safetensors::<f32>(&mut &bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_read_safetensors_mixed_dtypes() {
+        let header = r#"{"w":{"dtype":"F64","shape":[2],"data_offsets":[0,16]},"steps":{"dtype":"I64","shape":[1],"data_offsets":[16,24]}}"#;
+
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        for value in [0.5f64, -1.] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&7i64.to_le_bytes());
+
+        let state_dict = read_safetensors::<f32>(&mut &bytes[..]).unwrap();
+        assert_eq!(state_dict.get("w").unwrap().data, [0.5, -1.]);
+        assert_eq!(state_dict.get("steps").unwrap().data, [7.]);
+    }
+}
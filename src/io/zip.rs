@@ -0,0 +1,218 @@
+//! Uncompressed (stored) zip archives, as written by `numpy.savez`.
+
+use std::io::{self, Write};
+
+use super::invalid_data;
+
+const LOCAL_HEADER: u32 = 0x04034b50;
+const CENTRAL_HEADER: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIR: u32 = 0x06054b50;
+
+/// 1980-01-01, the earliest date of the DOS format.
+const DOS_DATE: u16 = 0x21;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut idx = 0;
+    while idx < 256 {
+        let mut crc = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xEDB88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+    table
+}
+
+/// The CRC-32 checksum used by zip archives.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, byte| {
+        CRC_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn to_u32(value: usize) -> io::Result<u32> {
+    u32::try_from(value)
+        .map_err(|_| invalid_data("Zip archives larger than 4 GB are not supported"))
+}
+
+/// Writes the `(name, content)` files as a zip archive without compression.
+pub fn write_stored<'f>(
+    writer: &mut impl Write,
+    files: impl IntoIterator<Item = (&'f str, &'f [u8])>,
+) -> io::Result<()> {
+    let mut central_dir = Vec::new();
+    let mut offset = 0;
+    let mut count = 0u16;
+
+    for (name, content) in files {
+        let crc = crc32(content);
+        let size = to_u32(content.len())?;
+
+        // shared by the local and the central header: version, flags, method, time, date, crc, sizes
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&DOS_DATE.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        writer.write_all(&LOCAL_HEADER.to_le_bytes())?;
+        writer.write_all(&common)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(content)?;
+
+        central_dir.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        // version made by
+        central_dir.extend_from_slice(&20u16.to_le_bytes());
+        central_dir.extend_from_slice(&common);
+        // comment length, disk number, internal and external attributes
+        central_dir.extend_from_slice(&[0; 10]);
+        central_dir.extend_from_slice(&to_u32(offset)?.to_le_bytes());
+        central_dir.extend_from_slice(name.as_bytes());
+
+        offset += 30 + name.len() + content.len();
+        count = count
+            .checked_add(1)
+            .ok_or_else(|| invalid_data("Too many files for a zip archive"))?;
+    }
+
+    writer.write_all(&central_dir)?;
+
+    writer.write_all(&END_OF_CENTRAL_DIR.to_le_bytes())?;
+    // disk numbers
+    writer.write_all(&[0; 4])?;
+    writer.write_all(&count.to_le_bytes())?;
+    writer.write_all(&count.to_le_bytes())?;
+    writer.write_all(&to_u32(central_dir.len())?.to_le_bytes())?;
+    writer.write_all(&to_u32(offset)?.to_le_bytes())?;
+    // comment length
+    writer.write_all(&0u16.to_le_bytes())
+}
+
+#[inline]
+fn u16_at(bytes: &[u8], pos: usize) -> io::Result<u16> {
+    bytes
+        .get(pos..pos + 2)
+        .map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| invalid_data("Truncated zip archive"))
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], pos: usize) -> io::Result<u32> {
+    bytes
+        .get(pos..pos + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| invalid_data("Truncated zip archive"))
+}
+
+/// Returns the `(name, content)` files of an archive. Only stored (uncompressed) files are supported.
+pub fn read_stored(archive: &[u8]) -> io::Result<Vec<(String, &[u8])>> {
+    // the end of central directory record is followed by a comment of at most u16::MAX bytes
+    let search_start = archive.len().saturating_sub(22 + u16::MAX as usize);
+    let eocd = (search_start..archive.len().saturating_sub(21))
+        .rev()
+        .find(|&pos| u32_at(archive, pos).ok() == Some(END_OF_CENTRAL_DIR))
+        .ok_or_else(|| invalid_data("Not a zip archive"))?;
+
+    let count = u16_at(archive, eocd + 10)?;
+    let mut pos = u32_at(archive, eocd + 16)? as usize;
+
+    let mut files = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        if u32_at(archive, pos)? != CENTRAL_HEADER {
+            return Err(invalid_data("Invalid zip central directory"));
+        }
+
+        let method = u16_at(archive, pos + 10)?;
+        let crc = u32_at(archive, pos + 16)?;
+        let size = u32_at(archive, pos + 20)?;
+        let name_len = u16_at(archive, pos + 28)? as usize;
+        let extra_len = u16_at(archive, pos + 30)? as usize;
+        let comment_len = u16_at(archive, pos + 32)? as usize;
+        let local_offset = u32_at(archive, pos + 42)? as usize;
+
+        let name = archive
+            .get(pos + 46..pos + 46 + name_len)
+            .ok_or_else(|| invalid_data("Truncated zip archive"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+
+        if method != 0 {
+            return Err(invalid_data(format!(
+                "\"{name}\" is compressed, only uncompressed archives are supported"
+            )));
+        }
+        if size == u32::MAX {
+            return Err(invalid_data("Zip64 archives are not supported"));
+        }
+
+        if u32_at(archive, local_offset)? != LOCAL_HEADER {
+            return Err(invalid_data("Invalid zip local header"));
+        }
+        let data_start = local_offset
+            + 30
+            + u16_at(archive, local_offset + 26)? as usize
+            + u16_at(archive, local_offset + 28)? as usize;
+
+        let content = archive
+            .get(data_start..data_start + size as usize)
+            .ok_or_else(|| invalid_data("Truncated zip archive"))?;
+
+        if crc32(content) != crc {
+            return Err(invalid_data(format!("CRC mismatch of \"{name}\"")));
+        }
+
+        files.push((name, content));
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, read_stored, write_stored};
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let mut archive = vec![];
+        write_stored(
+            &mut archive,
+            [("a.npy", &b"first"[..]), ("b/c.npy", &b""[..])],
+        )
+        .unwrap();
+
+        let files = read_stored(&archive).unwrap();
+        assert_eq!(
+            files,
+            [
+                ("a.npy".to_string(), &b"first"[..]),
+                ("b/c.npy".to_string(), &b""[..])
+            ]
+        );
+
+        // corrupt the content of the first file
+        archive[30 + 5] = b'F';
+        assert!(read_stored(&archive).is_err());
+    }
+}
@@ -1,13 +1,27 @@
 pub mod assign_or_set;
 #[cfg(feature = "matrix")]
+pub mod data;
+#[cfg(feature = "matrix")]
+pub mod init;
+#[cfg(feature = "matrix")]
+pub mod io;
+#[cfg(feature = "matrix")]
 mod matrix;
+#[cfg(feature = "matrix")]
+pub mod metrics;
+#[cfg(feature = "matrix")]
+pub mod nn;
 mod ops;
 mod ops2;
+#[cfg(all(feature = "matrix", feature = "autograd"))]
+pub mod optim;
 mod rawops;
+mod rng;
 
 pub use ops::*;
 pub use ops2::*;
 pub use rawops::*;
+pub use rng::*;
 
 #[cfg(feature = "matrix")]
 pub use matrix::*;
@@ -1,9 +1,15 @@
+mod access;
+mod constructors;
+mod fmt;
 mod impl_from;
 mod impl_from_const;
 
 #[cfg(feature = "static-api")]
 mod to_static_device;
 
+mod view;
+pub use view::*;
+
 use std::{fmt::Display, ops::Mul};
 
 use custos::{
@@ -13,9 +19,11 @@ use custos::{
 };
 
 use crate::{
-    AddElementWiseGrad, BinaryElementWise, BinaryOpsMayGrad, DiagflatMayGrad, GemmMayGrad,
-    MaxColsMayGrad, MaxRowsMayGrad, PowMayGrad, RandOp, RowOpMayGrad, SoftmaxMayGrad,
-    SquareMayGrad, SumColsMayGrad, TransposeMayGrad,
+    AddElementWiseGrad, BinaryElementWise, BinaryOpsMayGrad, ClipMayGrad, ConcatMayGrad,
+    DiagflatMayGrad, Distributions, DropoutMayGrad, EmbeddingMayGrad, GemmMayGrad,
+    IndexSelectRowsMayGrad, MaxColsMayGrad, MaxRowsMayGrad, PowMayGrad, RandInt, RandOp, Rng,
+    RowOpMayGrad, ScatterAddRowsMayGrad, SoftmaxMayGrad, SplitMayGrad, SquareMayGrad,
+    SumColsMayGrad, TransposeMayGrad,
 };
 
 pub struct Matrix<'a, T = f32, D: Device = CPU, S: Shape = ()> {
@@ -261,6 +269,21 @@ impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
         (out, self.rows, self.cols).into()
     }
 
+    /// Zeroes elements with probability `p` and scales the remaining ones by `1 / (1 - p)`.
+    /// In evaluation mode (`training == false`), this is the identity.
+    #[inline]
+    pub fn dropout(&self, p: T, training: bool) -> Matrix<'a, T, D, S>
+    where
+        D: DropoutMayGrad<T, S>,
+    {
+        (
+            self.device().dropout(self, p, training),
+            self.rows,
+            self.cols,
+        )
+            .into()
+    }
+
     #[inline]
 
     pub fn rand(&mut self, lo: T, hi: T)
@@ -270,6 +293,51 @@ impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
         self.device().rand(self, lo, hi);
     }
 
+    /// Fills this `Matrix` with uniform values in `[lo, hi)` drawn from `rng`.
+    #[inline]
+    pub fn rand_with(&mut self, rng: &mut Rng, lo: T, hi: T)
+    where
+        D: RandOp<T, S>,
+    {
+        self.device().rand_with(rng, self, lo, hi);
+    }
+
+    /// Fills this `Matrix` with normally distributed values.
+    #[inline]
+    pub fn randn(&mut self, mean: T, std: T)
+    where
+        D: Distributions<T, S>,
+    {
+        self.device().randn(self, mean, std);
+    }
+
+    /// Fills this `Matrix` with normally distributed values that lie in `[lo, hi]`.
+    #[inline]
+    pub fn trunc_normal(&mut self, mean: T, std: T, lo: T, hi: T)
+    where
+        D: Distributions<T, S>,
+    {
+        self.device().trunc_normal(self, mean, std, lo, hi);
+    }
+
+    /// Fills this `Matrix` with ones (with probability `p`) and zeros.
+    #[inline]
+    pub fn bernoulli(&mut self, p: T)
+    where
+        D: Distributions<T, S>,
+    {
+        self.device().bernoulli(self, p);
+    }
+
+    /// Fills this `Matrix` with integers in `[lo, hi)`.
+    #[inline]
+    pub fn rand_int(&mut self, lo: T, hi: T)
+    where
+        D: RandInt<T, S>,
+    {
+        self.device().rand_int(self, lo, hi);
+    }
+
     #[inline]
 
     pub fn squared(&self) -> Matrix<'a, T, D, S>
@@ -296,6 +364,29 @@ impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
         (self.device().pow(self, rhs), self.rows, self.cols).into()
     }
 
+    /// Clamps every element to `[min, max]`. The gradient is zero outside of the range.
+    #[inline]
+    pub fn clip(&self, min: T, max: T) -> Matrix<'a, T, D, S>
+    where
+        D: ClipMayGrad<T, S>,
+    {
+        (self.device().clip(self, min, max), self.rows, self.cols).into()
+    }
+
+    /// Clamps every element to `[min, max]` and passes the gradient through unchanged.
+    #[inline]
+    pub fn clip_straight_through(&self, min: T, max: T) -> Matrix<'a, T, D, S>
+    where
+        D: ClipMayGrad<T, S>,
+    {
+        (
+            self.device().clip_with(self, min, max, true),
+            self.rows,
+            self.cols,
+        )
+            .into()
+    }
+
     #[inline]
 
     pub fn max_rows<OS: Shape>(&self) -> Matrix<'a, T, D, OS>
@@ -376,6 +467,130 @@ impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
         )
             .into()
     }
+
+    /// Appends the rows of `rhs` below the rows of this `Matrix`.
+    #[inline]
+    pub fn concat_rows<RS: Shape, OS: Shape>(
+        &self,
+        rhs: &Matrix<'a, T, D, RS>,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: ConcatMayGrad<T, S, RS, OS>,
+    {
+        assert_eq!(self.cols, rhs.cols, "Column count of matrices differs");
+        (
+            self.device().concat_rows(self, rhs),
+            self.rows + rhs.rows,
+            self.cols,
+        )
+            .into()
+    }
+
+    /// Appends the columns of `rhs` to the right of the columns of this `Matrix`.
+    #[inline]
+    pub fn concat_cols<RS: Shape, OS: Shape>(
+        &self,
+        rhs: &Matrix<'a, T, D, RS>,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: ConcatMayGrad<T, S, RS, OS>,
+    {
+        assert_eq!(self.rows, rhs.rows, "Row count of matrices differs");
+        (
+            self.device().concat_cols(self.cols, rhs.cols, self, rhs),
+            self.rows,
+            self.cols + rhs.cols,
+        )
+            .into()
+    }
+
+    /// Splits this `Matrix` before the row `at`.
+    /// `at` may be `0` or the row count, one of the parts is empty then.
+    #[inline]
+    pub fn split_rows<LS: Shape, RS: Shape>(
+        &self,
+        at: usize,
+    ) -> (Matrix<'a, T, D, LS>, Matrix<'a, T, D, RS>)
+    where
+        D: SplitMayGrad<T, S, LS, RS>,
+    {
+        assert!(at <= self.rows, "Split index is out of bounds");
+        let (top, bottom) = self.device().split_rows(self.cols, at, self);
+        (
+            (top, at, self.cols).into(),
+            (bottom, self.rows - at, self.cols).into(),
+        )
+    }
+
+    /// Splits this `Matrix` before the column `at`.
+    /// `at` may be `0` or the column count, one of the parts is empty then.
+    #[inline]
+    pub fn split_cols<LS: Shape, RS: Shape>(
+        &self,
+        at: usize,
+    ) -> (Matrix<'a, T, D, LS>, Matrix<'a, T, D, RS>)
+    where
+        D: SplitMayGrad<T, S, LS, RS>,
+    {
+        assert!(at <= self.cols, "Split index is out of bounds");
+        let (left, right) = self.device().split_cols(self.cols, at, self);
+        (
+            (left, self.rows, at).into(),
+            (right, self.rows, self.cols - at).into(),
+        )
+    }
+
+    /// Selects the rows given by `indices`. A row may be selected more than once.
+    #[inline]
+    pub fn index_select_rows<I, OS: Shape>(
+        &self,
+        indices: &Buffer<'a, I, D>,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: IndexSelectRowsMayGrad<T, I, S, OS>,
+    {
+        (
+            self.device().index_select_rows(self.cols, self, indices),
+            indices.len(),
+            self.cols,
+        )
+            .into()
+    }
+
+    /// Treats this `Matrix` as an embedding table and looks up the rows given by `indices`.
+    /// The gradient is only accumulated into the looked up rows.
+    #[inline]
+    pub fn embedding<I, OS: Shape>(&self, indices: &Buffer<'a, I, D>) -> Matrix<'a, T, D, OS>
+    where
+        D: EmbeddingMayGrad<T, I, S, OS>,
+    {
+        (
+            self.device().embedding(self.cols, self, indices),
+            indices.len(),
+            self.cols,
+        )
+            .into()
+    }
+
+    /// Adds the row `i` of this `Matrix` to the row `indices[i]` of a zeroed `Matrix` with `rows` rows.
+    #[inline]
+    pub fn scatter_add_rows<I, OS: Shape>(
+        &self,
+        rows: usize,
+        indices: &Buffer<'a, I, D>,
+    ) -> Matrix<'a, T, D, OS>
+    where
+        D: ScatterAddRowsMayGrad<T, I, S, OS>,
+    {
+        assert_eq!(self.rows, indices.len(), "Each row requires an index");
+        (
+            self.device()
+                .scatter_add_rows(rows, self.cols, self, indices),
+            rows,
+            self.cols,
+        )
+            .into()
+    }
 }
 
 impl<T, D: IsShapeIndep, S: Shape> Matrix<'_, T, D, S> {
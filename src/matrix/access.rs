@@ -0,0 +1,100 @@
+use custos::{Device, Read, Shape};
+
+use crate::{Matrix, ReadStrided, WriteElement};
+
+impl<'a, T, D: Device, S: Shape> Matrix<'a, T, D, S> {
+    #[inline]
+    fn assert_in_bounds(&self, row: usize, col: usize) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) is out of bounds for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+    }
+}
+
+/// Element, row and column access. Host-backed devices index their memory directly,
+/// other devices only read back the requested values.
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{Matrix, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let mut x = Matrix::from((&device, 2, 3, [1, 2, 3, 4, 5, 6]));
+/// x.set(0, 1, -2);
+///
+/// assert_eq!(x.get(0, 1), -2);
+/// assert_eq!(x.row(1), [4, 5, 6]);
+/// assert_eq!(x.col(2), [3, 6]);
+/// assert_eq!(x.to_vec2d(), [vec![1, -2, 3], vec![4, 5, 6]]);
+/// ```
+impl<'a, T, D: ReadStrided<T, S>, S: Shape> Matrix<'a, T, D, S> {
+    /// Returns the value at `row` and `col`.
+    ///
+    /// # Panics
+    /// If `row` or `col` is out of bounds.
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.assert_in_bounds(row, col);
+        self.device().read_element(self, row * self.cols + col)
+    }
+
+    /// Returns the values of row `row`.
+    #[inline]
+    pub fn row(&self, row: usize) -> Vec<T> {
+        assert!(
+            row < self.rows,
+            "row {row} is out of bounds ({})",
+            self.rows
+        );
+        self.device()
+            .read_strided(self, row * self.cols, 1, self.cols)
+    }
+
+    /// Returns the values of column `col`.
+    #[inline]
+    pub fn col(&self, col: usize) -> Vec<T> {
+        assert!(
+            col < self.cols,
+            "col {col} is out of bounds ({})",
+            self.cols
+        );
+        self.device().read_strided(self, col, self.cols, self.rows)
+    }
+
+    /// Returns an iterator over the rows. Every row is read when it is reached.
+    #[inline]
+    pub fn rows_iter(&self) -> impl Iterator<Item = Vec<T>> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    /// Returns the values as one `Vec` per row.
+    pub fn to_vec2d(&self) -> Vec<Vec<T>>
+    where
+        T: Default + Clone,
+        D: Read<T, S>,
+    {
+        let values = self.read_to_vec();
+        if self.cols == 0 {
+            return vec![Vec::new(); self.rows];
+        }
+        values.chunks(self.cols).map(|row| row.to_vec()).collect()
+    }
+}
+
+impl<'a, T, D: WriteElement<T, S>, S: Shape> Matrix<'a, T, D, S> {
+    /// Sets the value at `row` and `col` to `value`.
+    ///
+    /// # Panics
+    /// If `row` or `col` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        self.assert_in_bounds(row, col);
+        let cols = self.cols;
+        self.device().write_element(self, row * cols + col, value);
+    }
+}
@@ -0,0 +1,149 @@
+use custos::{
+    prelude::{Float, Number},
+    Alloc, OnNewBuffer, Shape, WriteBuf,
+};
+
+use crate::Matrix;
+
+/// Constructors that fill a new matrix in row-major order.
+/// The values are created on the host and written to the device.
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{Matrix, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let eye = Matrix::<f32, _>::eye(&device, 2);
+/// assert_eq!(eye.read(), [1., 0., 0., 1.]);
+///
+/// let x = Matrix::<f32, _>::from_fn(&device, 2, 3, |row, col| (row * 3 + col) as f32);
+/// assert_eq!(x.read(), [0., 1., 2., 3., 4., 5.]);
+/// ```
+impl<'a, T, D, S> Matrix<'a, T, D, S>
+where
+    D: Alloc<T> + OnNewBuffer<T, D, S> + WriteBuf<T, S>,
+    S: Shape,
+{
+    /// Creates a `rows` x `cols` matrix whose elements are `f(row, col)`.
+    ///
+    /// # Panics
+    /// If the shape `S` has a size and it is not `rows * cols`.
+    pub fn from_fn(
+        device: &'a D,
+        rows: usize,
+        cols: usize,
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> Self {
+        assert!(
+            S::LEN == 0 || S::LEN == rows * cols,
+            "The shape of the matrix does not have {rows} * {cols} elements"
+        );
+
+        let data = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect::<Vec<_>>();
+
+        let mut matrix = Matrix::new(device, rows, cols);
+        device.write(&mut matrix, &data);
+        matrix
+    }
+
+    /// Creates a matrix filled with `value`.
+    #[inline]
+    pub fn full(device: &'a D, rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(device, rows, cols, |_, _| value.clone())
+    }
+
+    /// Creates a matrix filled with zeros.
+    #[inline]
+    pub fn zeros(device: &'a D, rows: usize, cols: usize) -> Self
+    where
+        T: Number,
+    {
+        Matrix::full(device, rows, cols, T::zero())
+    }
+
+    /// Creates a matrix filled with ones.
+    #[inline]
+    pub fn ones(device: &'a D, rows: usize, cols: usize) -> Self
+    where
+        T: Number,
+    {
+        Matrix::full(device, rows, cols, T::one())
+    }
+
+    /// Creates a `size` x `size` identity matrix.
+    #[inline]
+    pub fn eye(device: &'a D, size: usize) -> Self
+    where
+        T: Number,
+    {
+        Matrix::from_fn(device, size, size, |row, col| {
+            if row == col {
+                T::one()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Creates a matrix of the values `start`, `start + step`, `start + 2 * step`, ...
+    #[inline]
+    pub fn arange(device: &'a D, rows: usize, cols: usize, start: T, step: T) -> Self
+    where
+        T: Number,
+    {
+        Matrix::from_fn(device, rows, cols, |row, col| {
+            start + step * T::from_usize(row * cols + col)
+        })
+    }
+
+    /// Creates a matrix of `rows * cols` evenly spaced values from `start` to `end` (inclusive).
+    #[inline]
+    pub fn linspace(device: &'a D, rows: usize, cols: usize, start: T, end: T) -> Self
+    where
+        T: Float,
+    {
+        let intervals = (rows * cols).saturating_sub(1).max(1);
+        let step = (end - start) / T::from_usize(intervals);
+
+        Matrix::from_fn(device, rows, cols, |row, col| {
+            let idx = row * cols + col;
+            // hit `end` exactly
+            if idx == intervals {
+                end
+            } else {
+                start + step * T::from_usize(idx)
+            }
+        })
+    }
+}
+
+impl<'a, T, D, const R: usize, const C: usize> From<(&'a D, [[T; C]; R])> for Matrix<'a, T, D>
+where
+    T: Copy,
+    D: Alloc<T> + OnNewBuffer<T, D> + WriteBuf<T>,
+{
+    #[inline]
+    fn from((device, array): (&'a D, [[T; C]; R])) -> Self {
+        Matrix::from_fn(device, R, C, |row, col| array[row][col])
+    }
+}
+
+#[cfg(feature = "static-api")]
+impl<'a, T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<'a, T>
+where
+    T: Copy,
+{
+    #[inline]
+    fn from(array: [[T; C]; R]) -> Self {
+        let data = array.iter().flatten().copied().collect::<Vec<_>>();
+        Matrix::from((custos::Buffer::from(&data[..]), R, C))
+    }
+}
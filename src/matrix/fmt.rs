@@ -0,0 +1,176 @@
+use core::fmt::{self, Debug, Display, Formatter};
+
+use custos::Shape;
+
+use crate::ReadStrided;
+
+use super::Matrix;
+
+/// Matrices with more rows or columns than this are elided.
+const ELIDE_THRESHOLD: usize = 10;
+/// The number of leading and trailing rows or columns that are shown when eliding.
+const EDGE_ITEMS: usize = 3;
+
+/// Removes the module paths of a type name, e.g. `custos::devices::cpu::CPU<custos::Base>` -> `CPU<Base>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment = String::new();
+
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+            continue;
+        }
+        short.push_str(segment.rsplit("::").next().unwrap_or_default());
+        segment.clear();
+        short.push(c);
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+    short
+}
+
+/// Returns the indices to show, `None` marks the elided part.
+fn shown_indices(len: usize, elide: bool) -> Vec<Option<usize>> {
+    if !elide || len <= ELIDE_THRESHOLD {
+        return (0..len).map(Some).collect();
+    }
+    (0..EDGE_ITEMS)
+        .map(Some)
+        .chain([None])
+        .chain((len - EDGE_ITEMS..len).map(Some))
+        .collect()
+}
+
+impl<'a, T, D: ReadStrided<T, S>, S: Shape> Matrix<'a, T, D, S> {
+    /// Reads the values of `row` at the shown `cols`, one contiguous read per run of shown columns.
+    fn read_shown_values(&self, row: usize, cols: &[Option<usize>]) -> Vec<T> {
+        cols.split(Option::is_none)
+            .filter_map(|run| Some((run.first()?.as_ref()?, run.len())))
+            .flat_map(|(&col, len)| {
+                self.device()
+                    .read_strided(self, row * self.cols + col, 1, len)
+            })
+            .collect()
+    }
+
+    /// Writes the shape, device and grad status followed by the values, one row per line.
+    /// The precision of the formatter is applied to every value (`{:.2}`).
+    /// Large matrices are elided, the alternate flag (`{:#}`) prints every value.
+    /// Only the shown values are read from the device.
+    fn fmt_grid(
+        &self,
+        f: &mut Formatter<'_>,
+        fmt_value: impl Fn(&T, Option<usize>) -> String,
+    ) -> fmt::Result {
+        writeln!(
+            f,
+            "Matrix({}x{}, device: {}, requires_grad: {})",
+            self.rows,
+            self.cols,
+            short_type_name(core::any::type_name::<D>()),
+            self.requires_grad()
+        )?;
+
+        let elide = !f.alternate();
+        let precision = f.precision();
+        let rows = shown_indices(self.rows, elide);
+        let cols = shown_indices(self.cols, elide);
+
+        let cells = rows
+            .iter()
+            .map(|row| {
+                let Some(row) = row else {
+                    return vec!["...".to_string(); cols.len()];
+                };
+                let values = self.read_shown_values(*row, &cols);
+                let mut values = values.iter();
+
+                cols.iter()
+                    .map(|col| match col {
+                        Some(_) => fmt_value(values.next().unwrap(), precision),
+                        None => "...".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let width = cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "[")?;
+        for (idx, (row, cells)) in rows.iter().zip(&cells).enumerate() {
+            if idx > 0 {
+                write!(f, ",\n ")?;
+            }
+            if row.is_none() {
+                write!(f, "...")?;
+                continue;
+            }
+            write!(f, "[")?;
+            for (col_idx, cell) in cells.iter().enumerate() {
+                if col_idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{cell:>width$}")?;
+            }
+            write!(f, "]")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<'a, T, D, S> Display for Matrix<'a, T, D, S>
+where
+    T: Display,
+    D: ReadStrided<T, S>,
+    S: Shape,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_grid(f, |value, precision| match precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => format!("{value}"),
+        })
+    }
+}
+
+impl<'a, T, D, S> Debug for Matrix<'a, T, D, S>
+where
+    T: Debug,
+    D: ReadStrided<T, S>,
+    S: Shape,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_grid(f, |value, precision| match precision {
+            Some(precision) => format!("{value:.precision$?}"),
+            None => format!("{value:?}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{short_type_name, shown_indices};
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(
+            short_type_name("custos::devices::cpu::CPU<custos::modules::Autograd<custos::Base>>"),
+            "CPU<Autograd<Base>>"
+        );
+        assert_eq!(short_type_name("f32"), "f32");
+    }
+
+    #[test]
+    fn test_shown_indices() {
+        assert_eq!(shown_indices(3, true), [Some(0), Some(1), Some(2)]);
+        assert_eq!(
+            shown_indices(12, true),
+            [Some(0), Some(1), Some(2), None, Some(9), Some(10), Some(11)]
+        );
+        assert_eq!(shown_indices(12, false).len(), 12);
+    }
+}
@@ -0,0 +1,251 @@
+use core::ops::{Bound, RangeBounds};
+
+use custos::{Device, CPU};
+
+use crate::{Layout, Matrix, ViewOpsMayGrad};
+
+/// A strided window into a [`Matrix`] that borrows the matrix instead of copying it.
+///
+/// Views select row or column ranges or transpose the matrix by swapping the strides.
+/// Ops on views read the matrix directly, their gradients are accumulated into the gradient of the viewed matrix.
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{Matrix, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let x = Matrix::from((&device, 3, 2, [1., 2., 3., 4., 5., 6.]));
+///
+/// // rows 1..3 times x^T
+/// let out = x.view_rows(1..).gemm(&x.view_t());
+/// assert_eq!(out.read(), [11., 25., 39., 17., 39., 61.]);
+///
+/// let col = x.view_cols(1..2).to_matrix();
+/// assert_eq!(col.read(), [2., 4., 6.]);
+/// ```
+pub struct MatrixView<'v, 'a, T = f32, D: Device = CPU> {
+    parent: &'v Matrix<'a, T, D>,
+    layout: Layout,
+}
+
+impl<'v, 'a, T, D: Device> Clone for MatrixView<'v, 'a, T, D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'v, 'a, T, D: Device> Copy for MatrixView<'v, 'a, T, D> {}
+
+fn range_to_bounds(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(start) => *start,
+        Bound::Excluded(start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end + 1,
+        Bound::Excluded(end) => *end,
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
+impl<'a, T, D: Device> Matrix<'a, T, D> {
+    /// Returns a view of the whole matrix.
+    #[inline]
+    pub fn view(&self) -> MatrixView<'_, 'a, T, D> {
+        MatrixView {
+            parent: self,
+            layout: Layout::contiguous(self.rows, self.cols),
+        }
+    }
+
+    /// Returns a view of the rows in `range`, e.g. a minibatch.
+    #[inline]
+    pub fn view_rows(&self, range: impl RangeBounds<usize>) -> MatrixView<'_, 'a, T, D> {
+        self.view().view_rows(range)
+    }
+
+    /// Returns a view of the columns in `range`, e.g. an attention head.
+    #[inline]
+    pub fn view_cols(&self, range: impl RangeBounds<usize>) -> MatrixView<'_, 'a, T, D> {
+        self.view().view_cols(range)
+    }
+
+    /// Returns a transposed view.
+    #[inline]
+    pub fn view_t(&self) -> MatrixView<'_, 'a, T, D> {
+        self.view().t()
+    }
+}
+
+impl<'v, 'a, T, D: Device> MatrixView<'v, 'a, T, D> {
+    /// Creates a view with an arbitrary `layout`.
+    ///
+    /// # Panics
+    /// If the last element of `layout` is outside of `parent` or if elements of `layout` overlap,
+    /// since the gradient is accumulated into every element of the window.
+    pub fn new(parent: &'v Matrix<'a, T, D>, layout: Layout) -> Self {
+        assert!(
+            !layout.overlaps(),
+            "The elements of the layout {layout:?} overlap"
+        );
+        if !layout.is_empty() {
+            let last = layout.index(layout.rows - 1, layout.cols - 1);
+            assert!(
+                last < parent.len(),
+                "The layout exceeds the viewed matrix ({last} >= {})",
+                parent.len()
+            );
+        }
+        MatrixView { parent, layout }
+    }
+
+    /// Returns the viewed matrix.
+    #[inline]
+    pub fn parent(&self) -> &'v Matrix<'a, T, D> {
+        self.parent
+    }
+
+    #[inline]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.layout.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.layout.cols
+    }
+
+    /// Returns `true` if the viewed elements are a contiguous row-major block of the matrix.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        self.layout.is_contiguous()
+    }
+
+    /// Returns a view of the rows in `range` of this view.
+    #[inline]
+    pub fn view_rows(&self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = range_to_bounds(range, self.rows());
+        MatrixView {
+            parent: self.parent,
+            layout: self.layout.slice_rows(start, end),
+        }
+    }
+
+    /// Returns a view of the columns in `range` of this view.
+    #[inline]
+    pub fn view_cols(&self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = range_to_bounds(range, self.cols());
+        MatrixView {
+            parent: self.parent,
+            layout: self.layout.slice_cols(start, end),
+        }
+    }
+
+    /// Returns the transposed view.
+    #[inline]
+    pub fn t(&self) -> Self {
+        MatrixView {
+            parent: self.parent,
+            layout: self.layout.t(),
+        }
+    }
+
+    /// Copies the viewed elements to a new matrix.
+    #[inline]
+    pub fn to_matrix(&self) -> Matrix<'a, T, D>
+    where
+        D: ViewOpsMayGrad<T>,
+    {
+        let device = self.parent.device();
+        (
+            device.copy_view(self.parent, self.layout),
+            self.rows(),
+            self.cols(),
+        )
+            .into()
+    }
+
+    /// Matrix multiplication of two views, without copying them beforehand.
+    #[inline]
+    pub fn gemm(&self, rhs: &MatrixView<'_, 'a, T, D>) -> Matrix<'a, T, D>
+    where
+        D: ViewOpsMayGrad<T>,
+    {
+        assert_eq!(
+            self.cols(),
+            rhs.rows(),
+            "The columns of lhs must equal the rows of rhs"
+        );
+        let device = self.parent.device();
+        (
+            device.gemm_views(self.parent, self.layout, rhs.parent, rhs.layout),
+            self.rows(),
+            rhs.cols(),
+        )
+            .into()
+    }
+
+    #[inline]
+    pub fn add(&self, rhs: &MatrixView<'_, 'a, T, D>) -> Matrix<'a, T, D>
+    where
+        D: ViewOpsMayGrad<T>,
+    {
+        self.assert_same_dims(rhs);
+        let device = self.parent.device();
+        (
+            device.add_views(self.parent, self.layout, rhs.parent, rhs.layout),
+            self.rows(),
+            self.cols(),
+        )
+            .into()
+    }
+
+    #[inline]
+    pub fn sub(&self, rhs: &MatrixView<'_, 'a, T, D>) -> Matrix<'a, T, D>
+    where
+        D: ViewOpsMayGrad<T>,
+    {
+        self.assert_same_dims(rhs);
+        let device = self.parent.device();
+        (
+            device.sub_views(self.parent, self.layout, rhs.parent, rhs.layout),
+            self.rows(),
+            self.cols(),
+        )
+            .into()
+    }
+
+    #[inline]
+    pub fn mul(&self, rhs: &MatrixView<'_, 'a, T, D>) -> Matrix<'a, T, D>
+    where
+        D: ViewOpsMayGrad<T>,
+    {
+        self.assert_same_dims(rhs);
+        let device = self.parent.device();
+        (
+            device.mul_views(self.parent, self.layout, rhs.parent, rhs.layout),
+            self.rows(),
+            self.cols(),
+        )
+            .into()
+    }
+
+    #[inline]
+    fn assert_same_dims(&self, rhs: &MatrixView<'_, 'a, T, D>) {
+        assert_eq!(
+            (self.rows(), self.cols()),
+            (rhs.rows(), rhs.cols()),
+            "The views must have the same dimensions"
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! Evaluation metrics. The predictions and labels are read to the host.
+//!
+//! Classification metrics take a score matrix with one row per sample and one column per class,
+//! the predicted class is the column with the highest score.
+//! A single column is treated as the probability of class 1 (predicted if the score is at least 0.5).
+//! Labels hold the class index of every sample.
+
+use custos::{prelude::Number, Buffer, Read};
+
+use crate::Matrix;
+
+/// How per-class precision, recall and F1 scores are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Average {
+    /// The unweighted mean of the per-class scores.
+    Macro,
+    /// The score of the summed true positives, false positives and false negatives of all classes.
+    Micro,
+}
+
+fn read_classes<T: Number, D: Read<T>>(labels: &Buffer<T, D>) -> Vec<usize> {
+    labels
+        .read_to_vec()
+        .into_iter()
+        .map(|label| label.as_f64().round() as usize)
+        .collect()
+}
+
+fn read_scores<T: Number, D: Read<T>>(scores: &Buffer<T, D>) -> Vec<f64> {
+    scores
+        .read_to_vec()
+        .into_iter()
+        .map(|score| score.as_f64())
+        .collect()
+}
+
+fn argmax(row: &[f64]) -> usize {
+    let mut max_idx = 0;
+    for (idx, value) in row.iter().enumerate().skip(1) {
+        if *value > row[max_idx] {
+            max_idx = idx;
+        }
+    }
+    max_idx
+}
+
+/// Returns the predicted class of every row of `scores`.
+pub fn predicted_classes<T: Number, D: Read<T>>(scores: &Matrix<T, D>) -> Vec<usize> {
+    let values = read_scores(scores);
+    if scores.cols() == 1 {
+        return values
+            .iter()
+            .map(|score| (*score >= 0.5) as usize)
+            .collect();
+    }
+    values.chunks(scores.cols()).map(argmax).collect()
+}
+
+/// Computes the fraction of rows whose predicted class is the label. Zero if there are no rows.
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{metrics::accuracy, Buffer, Matrix, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let scores = Matrix::from((&device, 3, 2, [0.9, 0.1, 0.2, 0.8, 0.6, 0.4]));
+/// let labels = Buffer::from((&device, [0., 1., 1.]));
+///
+/// assert!((accuracy(&scores, &labels) - 2. / 3.).abs() < 1e-9);
+/// ```
+pub fn accuracy<T: Number, D: Read<T>>(scores: &Matrix<T, D>, labels: &Buffer<T, D>) -> f64 {
+    assert_eq!(scores.rows(), labels.len(), "Every row needs a label");
+
+    let correct = predicted_classes(scores)
+        .into_iter()
+        .zip(read_classes(labels))
+        .filter(|(predicted, label)| predicted == label)
+        .count();
+    ratio(correct, labels.len())
+}
+
+/// Computes the fraction of rows whose label is among the `k` highest scores. Zero if there are no rows.
+///
+/// A single column scores class 1 with `p` and class 0 with `1 - p`.
+pub fn top_k_accuracy<T: Number, D: Read<T>>(
+    scores: &Matrix<T, D>,
+    labels: &Buffer<T, D>,
+    k: usize,
+) -> f64 {
+    assert_eq!(scores.rows(), labels.len(), "Every row needs a label");
+
+    let mut values = read_scores(scores);
+    let mut cols = scores.cols();
+    if cols == 1 {
+        values = values.into_iter().flat_map(|p| [1. - p, p]).collect();
+        cols = 2;
+    }
+
+    let correct = values
+        .chunks(cols)
+        .zip(read_classes(labels))
+        .filter(|(row, label)| {
+            // the label is in the top k if less than k classes score higher
+            let score = row.get(*label).copied().unwrap_or(f64::NEG_INFINITY);
+            row.iter().filter(|other| **other > score).count() < k
+        })
+        .count();
+    ratio(correct, labels.len())
+}
+
+/// Counts how often each class is predicted for each actual class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    classes: usize,
+    counts: Vec<usize>,
+}
+
+impl ConfusionMatrix {
+    /// Creates a confusion matrix of the predicted classes of `scores`.
+    /// The number of classes is the larger one of the number of columns (at least two) and the highest label + 1.
+    pub fn new<T: Number, D: Read<T>>(scores: &Matrix<T, D>, labels: &Buffer<T, D>) -> Self {
+        assert_eq!(scores.rows(), labels.len(), "Every row needs a label");
+
+        let labels = read_classes(labels);
+        let classes = labels
+            .iter()
+            .map(|label| label + 1)
+            .max()
+            .unwrap_or(0)
+            .max(scores.cols().max(2));
+        Self::from_classes(&predicted_classes(scores), &labels, classes)
+    }
+
+    /// Creates a confusion matrix from predicted and actual class indices.
+    ///
+    /// # Panics
+    /// If the lengths differ or a class index is not below `classes`.
+    pub fn from_classes(predicted: &[usize], actual: &[usize], classes: usize) -> Self {
+        assert_eq!(
+            predicted.len(),
+            actual.len(),
+            "Every prediction needs a label"
+        );
+
+        let mut counts = vec![0; classes * classes];
+        for (predicted, actual) in predicted.iter().zip(actual) {
+            assert!(
+                *predicted < classes && *actual < classes,
+                "Class index out of range"
+            );
+            counts[actual * classes + predicted] += 1;
+        }
+        ConfusionMatrix { classes, counts }
+    }
+
+    #[inline]
+    pub fn classes(&self) -> usize {
+        self.classes
+    }
+
+    /// Returns the number of samples of class `actual` that were predicted as `predicted`.
+    #[inline]
+    pub fn get(&self, actual: usize, predicted: usize) -> usize {
+        self.counts[actual * self.classes + predicted]
+    }
+
+    /// Returns the counts in row-major order, rows are the actual classes.
+    #[inline]
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    #[inline]
+    pub fn true_positives(&self, class: usize) -> usize {
+        self.get(class, class)
+    }
+
+    /// Returns the number of samples wrongly predicted as `class`.
+    pub fn false_positives(&self, class: usize) -> usize {
+        (0..self.classes)
+            .filter(|actual| *actual != class)
+            .map(|actual| self.get(actual, class))
+            .sum()
+    }
+
+    /// Returns the number of samples of `class` that were predicted as another class.
+    pub fn false_negatives(&self, class: usize) -> usize {
+        (0..self.classes)
+            .filter(|predicted| *predicted != class)
+            .map(|predicted| self.get(class, predicted))
+            .sum()
+    }
+
+    /// Returns the fraction of correct predictions.
+    pub fn accuracy(&self) -> f64 {
+        let correct = (0..self.classes)
+            .map(|class| self.get(class, class))
+            .sum::<usize>();
+        ratio(correct, self.counts.iter().sum())
+    }
+
+    /// Returns the precision of `class`: tp / (tp + fp). Zero if `class` is never predicted.
+    pub fn class_precision(&self, class: usize) -> f64 {
+        let tp = self.true_positives(class);
+        ratio(tp, tp + self.false_positives(class))
+    }
+
+    /// Returns the recall of `class`: tp / (tp + fn). Zero if `class` never occurs.
+    pub fn class_recall(&self, class: usize) -> f64 {
+        let tp = self.true_positives(class);
+        ratio(tp, tp + self.false_negatives(class))
+    }
+
+    /// Returns the harmonic mean of the precision and recall of `class`.
+    pub fn class_f1(&self, class: usize) -> f64 {
+        f1(self.class_precision(class), self.class_recall(class))
+    }
+
+    pub fn precision(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::class_precision),
+            Average::Micro => {
+                let (tp, fp, _) = self.summed_counts();
+                ratio(tp, tp + fp)
+            }
+        }
+    }
+
+    pub fn recall(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::class_recall),
+            Average::Micro => {
+                let (tp, _, fn_) = self.summed_counts();
+                ratio(tp, tp + fn_)
+            }
+        }
+    }
+
+    /// Returns the F1 score. The macro average is the mean of the per-class F1 scores.
+    pub fn f1(&self, average: Average) -> f64 {
+        match average {
+            Average::Macro => self.macro_average(Self::class_f1),
+            Average::Micro => f1(self.precision(average), self.recall(average)),
+        }
+    }
+
+    fn macro_average(&self, metric: fn(&Self, usize) -> f64) -> f64 {
+        if self.classes == 0 {
+            return 0.;
+        }
+        (0..self.classes)
+            .map(|class| metric(self, class))
+            .sum::<f64>()
+            / self.classes as f64
+    }
+
+    fn summed_counts(&self) -> (usize, usize, usize) {
+        (0..self.classes).fold((0, 0, 0), |(tp, fp, fn_), class| {
+            (
+                tp + self.true_positives(class),
+                fp + self.false_positives(class),
+                fn_ + self.false_negatives(class),
+            )
+        })
+    }
+}
+
+#[inline]
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        return 0.;
+    }
+    numerator as f64 / denominator as f64
+}
+
+#[inline]
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0. {
+        return 0.;
+    }
+    2. * precision * recall / (precision + recall)
+}
+
+/// Computes the precision of the predicted classes of `scores`, see [`ConfusionMatrix::precision`].
+#[inline]
+pub fn precision<T: Number, D: Read<T>>(
+    scores: &Matrix<T, D>,
+    labels: &Buffer<T, D>,
+    average: Average,
+) -> f64 {
+    ConfusionMatrix::new(scores, labels).precision(average)
+}
+
+/// Computes the recall of the predicted classes of `scores`, see [`ConfusionMatrix::recall`].
+#[inline]
+pub fn recall<T: Number, D: Read<T>>(
+    scores: &Matrix<T, D>,
+    labels: &Buffer<T, D>,
+    average: Average,
+) -> f64 {
+    ConfusionMatrix::new(scores, labels).recall(average)
+}
+
+/// Computes the F1 score of the predicted classes of `scores`, see [`ConfusionMatrix::f1`].
+#[inline]
+pub fn f1_score<T: Number, D: Read<T>>(
+    scores: &Matrix<T, D>,
+    labels: &Buffer<T, D>,
+    average: Average,
+) -> f64 {
+    ConfusionMatrix::new(scores, labels).f1(average)
+}
+
+/// Computes the area under the ROC curve of binary `labels` (1 is the positive class) and the `scores` of the positive class.
+/// This is the probability that a random positive sample scores higher than a random negative one (ties count half).
+///
+/// # Panics
+/// If the lengths differ or only one class is present.
+pub fn roc_auc<T: Number, D: Read<T>>(scores: &Buffer<T, D>, labels: &Buffer<T, D>) -> f64 {
+    assert_eq!(scores.len(), labels.len(), "Every score needs a label");
+
+    let mut samples = read_scores(scores)
+        .into_iter()
+        .zip(read_classes(labels))
+        .collect::<Vec<_>>();
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let positives = samples.iter().filter(|(_, label)| *label == 1).count();
+    let negatives = samples.len() - positives;
+    assert!(
+        positives > 0 && negatives > 0,
+        "The ROC-AUC requires positive and negative samples"
+    );
+
+    // Mann-Whitney U: sum of the (tie averaged) ranks of the positive samples
+    let mut positive_rank_sum = 0.;
+    let mut start = 0;
+    while start < samples.len() {
+        let mut end = start + 1;
+        while end < samples.len() && samples[end].0 == samples[start].0 {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.;
+        let tied_positives = samples[start..end]
+            .iter()
+            .filter(|(_, label)| *label == 1)
+            .count();
+        positive_rank_sum += rank * tied_positives as f64;
+        start = end;
+    }
+
+    let positives = positives as f64;
+    (positive_rank_sum - positives * (positives + 1.) / 2.) / (positives * negatives as f64)
+}
+
+/// Computes the coefficient of determination: 1 - (residual sum of squares / total sum of squares).
+pub fn r2_score<T: Number, D: Read<T>>(preds: &Buffer<T, D>, targets: &Buffer<T, D>) -> f64 {
+    assert_eq!(
+        preds.len(),
+        targets.len(),
+        "Every prediction needs a target"
+    );
+
+    let preds = read_scores(preds);
+    let targets = read_scores(targets);
+
+    let mean = targets.iter().sum::<f64>() / targets.len() as f64;
+    let residual = preds
+        .iter()
+        .zip(&targets)
+        .map(|(pred, target)| (target - pred).powi(2))
+        .sum::<f64>();
+    let total = targets
+        .iter()
+        .map(|target| (target - mean).powi(2))
+        .sum::<f64>();
+
+    if total == 0. {
+        return if residual == 0. { 1. } else { 0. };
+    }
+    1. - residual / total
+}
+
+/// Computes the root of the mean squared error.
+pub fn rmse<T: Number, D: Read<T>>(preds: &Buffer<T, D>, targets: &Buffer<T, D>) -> f64 {
+    assert_eq!(
+        preds.len(),
+        targets.len(),
+        "Every prediction needs a target"
+    );
+
+    let squared = read_scores(preds)
+        .into_iter()
+        .zip(read_scores(targets))
+        .map(|(pred, target)| (pred - target).powi(2))
+        .sum::<f64>();
+    (squared / preds.len() as f64).sqrt()
+}
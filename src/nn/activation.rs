@@ -0,0 +1,86 @@
+use custos::{
+    prelude::{Float, Number},
+    AddGradFn, Alloc, ApplyFunction, Device, MayTapeActions, TwoWay, UnaryElementWiseMayGrad,
+    UnaryGrad, ZeroGrad,
+};
+
+use crate::{Matrix, SoftmaxMayGrad};
+
+use super::Module;
+
+/// Applies [`Matrix::relu`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReLU;
+
+/// Applies [`Matrix::tanh`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tanh;
+
+/// Applies [`Matrix::sigmoid`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sigmoid;
+
+/// Applies [`Matrix::softmax`] to every row.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Softmax;
+
+impl<'a, T, D> Module<'a, T, D> for ReLU
+where
+    T: TwoWay<T> + Number + 'static,
+    D: UnaryElementWiseMayGrad<T, D, ()>
+        + ApplyFunction<T>
+        + MayTapeActions
+        + UnaryGrad<T>
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        inputs.relu()
+    }
+}
+
+impl<'a, T, D> Module<'a, T, D> for Tanh
+where
+    T: Float + 'static,
+    D: UnaryElementWiseMayGrad<T, D, ()>
+        + ApplyFunction<T>
+        + MayTapeActions
+        + UnaryGrad<T>
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        inputs.tanh()
+    }
+}
+
+impl<'a, T, D> Module<'a, T, D> for Sigmoid
+where
+    T: TwoWay<T> + Float + 'static,
+    D: UnaryElementWiseMayGrad<T, D, ()>
+        + ApplyFunction<T>
+        + MayTapeActions
+        + UnaryGrad<T>
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        inputs.sigmoid()
+    }
+}
+
+impl<'a, T, D: Device + SoftmaxMayGrad<T, ()>> Module<'a, T, D> for Softmax {
+    #[inline]
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        inputs.softmax()
+    }
+}
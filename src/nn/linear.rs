@@ -0,0 +1,79 @@
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, OnNewBuffer,
+};
+
+use crate::{GemmMayGrad, Matrix, RandOp, RowOpMayGrad};
+
+use super::{Module, NamedParam};
+
+/// A fully connected layer mapping `I` input to `O` output features: `inputs * weights + bias`.
+pub struct Linear<'a, T, D: Device, const I: usize, const O: usize> {
+    pub weights: Matrix<'a, T, D>,
+    pub bias: Matrix<'a, T, D>,
+}
+
+impl<'a, T: Float, D: Device + OnNewBuffer<T, D>, const I: usize, const O: usize>
+    Linear<'a, T, D, I, O>
+{
+    /// Creates a `Linear` layer with weights drawn from `U(-0.1, 0.1)` and a zero bias.
+    /// Use [`with_init`](Linear::with_init) to initialise the weights differently.
+    pub fn new(device: &'a D) -> Self
+    where
+        D: RandOp<T> + Alloc<T> + ClearBuf<T>,
+    {
+        Self::with_init(device, |weights| {
+            device.rand(weights, T::from_f64(-0.1), T::from_f64(0.1))
+        })
+    }
+
+    /// Creates a `Linear` layer whose `I x O` weights are initialised by `init_weights` and a zero bias.
+    ///
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{init, nn::Linear, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let lin = Linear::<f32, _, 784, 128>::with_init(&device, |weights| {
+    ///     init::xavier_uniform(weights, 784, 128)
+    /// });
+    /// assert_eq!((lin.weights.rows(), lin.weights.cols()), (784, 128));
+    /// ```
+    pub fn with_init(device: &'a D, init_weights: impl FnOnce(&mut Matrix<'a, T, D>)) -> Self
+    where
+        D: Alloc<T> + ClearBuf<T>,
+    {
+        let mut weights = Matrix::new(device, I, O).require_grad();
+        init_weights(&mut weights);
+
+        let mut bias = Matrix::new(device, 1, O);
+        bias.clear();
+
+        Linear {
+            weights,
+            bias: bias.require_grad(),
+        }
+    }
+}
+
+impl<'a, T, D, const I: usize, const O: usize> Module<'a, T, D> for Linear<'a, T, D, I, O>
+where
+    D: GemmMayGrad<T> + RowOpMayGrad<T>,
+{
+    #[inline]
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        let mut out = inputs.gemm(&self.weights);
+        out.add_row_mut(&self.bias);
+        out
+    }
+
+    #[inline]
+    fn named_params<'b>(&'b mut self) -> Vec<NamedParam<'b, 'a, T, D>> {
+        vec![
+            NamedParam::new("weights", &mut self.weights),
+            NamedParam::new("bias", &mut self.bias),
+        ]
+    }
+}
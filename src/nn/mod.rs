@@ -0,0 +1,159 @@
+//! Reusable neural network building blocks.
+//!
+//! # Example
+#![cfg_attr(feature = "cpu", doc = "```")]
+#![cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+//! use sliced::{
+//!     nn::{Linear, Module, ReLU, Sequential},
+//!     Matrix, CPU,
+//! };
+//!
+//! let device = CPU::<custos::Autograd<custos::Base>>::new();
+//!
+//! let mut net = Sequential::new()
+//!     .add(Linear::<f32, _, 2, 8>::new(&device))
+//!     .add(ReLU)
+//!     .add(Linear::<f32, _, 8, 1>::new(&device));
+//!
+//! let x = Matrix::from((&device, 3, 2, [1., 2., 3., 4., 5., 6.]));
+//! let out = net.forward(&x);
+//!
+//! assert_eq!((out.rows(), out.cols()), (3, 1));
+//! assert_eq!(net.params().len(), 4);
+//!
+//! let names = net.named_params().into_iter().map(|param| param.name).collect::<Vec<_>>();
+//! assert_eq!(names, ["0.weights", "0.bias", "2.weights", "2.bias"]);
+//! ```
+
+mod activation;
+mod linear;
+mod sequential;
+mod state_dict;
+
+pub use activation::*;
+pub use linear::*;
+pub use sequential::*;
+pub use state_dict::*;
+
+use custos::{Buffer, Device, Read, WriteBuf};
+
+use crate::Matrix;
+
+/// A layer (or a composition of layers) of a neural network.
+pub trait Module<'a, T, D: Device> {
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D>;
+
+    /// Returns the trainable parameters with their names. Defaults to none.
+    #[inline]
+    fn named_params<'b>(&'b mut self) -> Vec<NamedParam<'b, 'a, T, D>> {
+        Vec::new()
+    }
+
+    /// Returns the trainable parameters. Defaults to the [`Module::named_params`].
+    #[inline]
+    fn params<'b>(&'b mut self) -> Vec<Param<'b, 'a, T, D>> {
+        self.named_params().into_iter().map(Param::from).collect()
+    }
+
+    /// Copies the named parameters to the host.
+    fn state_dict(&mut self) -> StateDict<T>
+    where
+        T: Default + Clone,
+        D: Read<T>,
+    {
+        let mut state_dict = StateDict::new();
+        for param in self.named_params() {
+            state_dict.insert_matrix(param.name, param.param);
+        }
+        state_dict
+    }
+
+    /// Overwrites the named parameters with the values of `state_dict`.
+    /// Nothing is written if a parameter is missing, a shape differs or `state_dict` contains an unknown name.
+    fn load_state_dict(&mut self, state_dict: &StateDict<T>) -> Result<(), StateDictError>
+    where
+        D: WriteBuf<T>,
+    {
+        let params = self.named_params();
+
+        for param in &params {
+            let entry = state_dict
+                .get(&param.name)
+                .ok_or_else(|| StateDictError::MissingParam(param.name.clone()))?;
+
+            let expected = [param.param.rows(), param.param.cols()];
+            if entry.shape != expected {
+                return Err(StateDictError::ShapeMismatch {
+                    name: param.name.clone(),
+                    expected: expected.to_vec(),
+                    found: entry.shape.clone(),
+                });
+            }
+        }
+
+        if let Some(name) = state_dict
+            .names()
+            .find(|name| params.iter().all(|param| param.name != *name))
+        {
+            return Err(StateDictError::UnexpectedParam(name.to_string()));
+        }
+
+        for param in params {
+            // checked above
+            let entry = state_dict.get(&param.name).unwrap();
+            param.param.write(&entry.data);
+        }
+        Ok(())
+    }
+}
+
+/// A mutable handle to a trainable parameter, which is updated by an optimizer.
+pub struct Param<'a, 'b, T, D: Device> {
+    pub param: &'a mut Buffer<'b, T, D>,
+}
+
+impl<'a, 'b, T, D: Device> Param<'a, 'b, T, D> {
+    #[inline]
+    pub fn new(param: &'a mut Buffer<'b, T, D>) -> Self {
+        Param { param }
+    }
+}
+
+impl<'a, 'b, T, D: Device> From<NamedParam<'a, 'b, T, D>> for Param<'a, 'b, T, D> {
+    #[inline]
+    fn from(named: NamedParam<'a, 'b, T, D>) -> Self {
+        Param::new(named.param.as_buf_mut())
+    }
+}
+
+/// A trainable parameter together with its name (e.g. `"0.weights"`) and shape.
+pub struct NamedParam<'a, 'b, T, D: Device> {
+    pub name: String,
+    pub param: &'a mut Matrix<'b, T, D>,
+}
+
+impl<'a, 'b, T, D: Device> NamedParam<'a, 'b, T, D> {
+    #[inline]
+    pub fn new(name: impl Into<String>, param: &'a mut Matrix<'b, T, D>) -> Self {
+        NamedParam {
+            name: name.into(),
+            param,
+        }
+    }
+
+    /// Prepends `prefix` and a dot to the name.
+    /// Modules containing other modules use this to qualify the names of their children:
+    ///
+    /// ```ignore
+    /// fn named_params<'b>(&'b mut self) -> Vec<NamedParam<'b, 'a, T, D>> {
+    ///     let lin1 = self.lin1.named_params().into_iter().map(|param| param.prefixed("lin1"));
+    ///     let lin2 = self.lin2.named_params().into_iter().map(|param| param.prefixed("lin2"));
+    ///     lin1.chain(lin2).collect()
+    /// }
+    /// ```
+    #[inline]
+    pub fn prefixed(mut self, prefix: &str) -> Self {
+        self.name = format!("{prefix}.{}", self.name);
+        self
+    }
+}
@@ -0,0 +1,82 @@
+use custos::Device;
+
+use crate::Matrix;
+
+use super::{Module, NamedParam, Param};
+
+/// Chains modules: the output of a module is the input of the next one.
+/// The names of the parameters are prefixed with the index of their module, e.g. `"0.weights"`.
+pub struct Sequential<'a, 'm, T, D: Device> {
+    modules: Vec<Box<dyn Module<'a, T, D> + 'm>>,
+}
+
+impl<'a, 'm, T, D: Device> Sequential<'a, 'm, T, D> {
+    #[inline]
+    pub fn new() -> Self {
+        Sequential {
+            modules: Vec::new(),
+        }
+    }
+
+    /// Appends `module` to the chain.
+    #[inline]
+    pub fn add(mut self, module: impl Module<'a, T, D> + 'm) -> Self {
+        self.push(module);
+        self
+    }
+
+    #[inline]
+    pub fn push(&mut self, module: impl Module<'a, T, D> + 'm) {
+        self.modules.push(Box::new(module));
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+}
+
+impl<'a, 'm, T, D: Device> Default for Sequential<'a, 'm, T, D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, 'm, T, D: Device> Module<'a, T, D> for Sequential<'a, 'm, T, D> {
+    /// # Panics
+    /// If the `Sequential` does not contain any module.
+    fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D> {
+        let mut modules = self.modules.iter();
+        let first = modules
+            .next()
+            .expect("A Sequential requires at least one module");
+
+        modules.fold(first.forward(inputs), |out, module| module.forward(&out))
+    }
+
+    fn params<'b>(&'b mut self) -> Vec<Param<'b, 'a, T, D>> {
+        self.modules
+            .iter_mut()
+            .flat_map(|module| module.params())
+            .collect()
+    }
+
+    fn named_params<'b>(&'b mut self) -> Vec<NamedParam<'b, 'a, T, D>> {
+        self.modules
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(idx, module)| {
+                module
+                    .named_params()
+                    .into_iter()
+                    .map(move |param| param.prefixed(&idx.to_string()))
+            })
+            .collect()
+    }
+}
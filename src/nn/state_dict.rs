@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use custos::{Alloc, Buffer, Device, OnNewBuffer, Read};
+
+use crate::Matrix;
+
+/// The shape and the values of a parameter, stored on the host.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateEntry<T> {
+    pub shape: Vec<usize>,
+    pub data: Vec<T>,
+}
+
+impl<T: Copy> StateEntry<T> {
+    /// Copies the values to `device`.
+    #[inline]
+    pub fn to_buffer<'a, D>(&self, device: &'a D) -> Buffer<'a, T, D>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        Buffer::from((device, self.data.clone()))
+    }
+
+    /// Copies the values to `device`.
+    /// An entry with one dimension becomes a row vector, more than two dimensions are flattened into the columns.
+    pub fn to_matrix<'a, D>(&self, device: &'a D) -> Matrix<'a, T, D>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D>,
+    {
+        let (rows, cols) = match self.shape[..] {
+            [] => (1, 1),
+            [cols] => (1, cols),
+            [rows, ref cols @ ..] => (rows, cols.iter().product()),
+        };
+        Matrix::from((device, rows, cols, self.data.clone()))
+    }
+}
+
+/// Host copies of named parameters, sorted by name.
+/// Created by [`Module::state_dict`](super::Module::state_dict) and loaded with [`Module::load_state_dict`](super::Module::load_state_dict).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDict<T> {
+    entries: BTreeMap<String, StateEntry<T>>,
+}
+
+impl<T> StateDict<T> {
+    #[inline]
+    pub fn new() -> Self {
+        StateDict {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Inserts a parameter and returns the previous entry with the same name.
+    ///
+    /// # Panics
+    /// If the product of `shape` does not equal the length of `data`.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        shape: Vec<usize>,
+        data: Vec<T>,
+    ) -> Option<StateEntry<T>> {
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            data.len(),
+            "The shape {shape:?} does not match the length of the data"
+        );
+        self.entries.insert(name.into(), StateEntry { shape, data })
+    }
+
+    /// Reads `matrix` and inserts it with the shape `[rows, cols]`.
+    pub fn insert_matrix<D>(&mut self, name: impl Into<String>, matrix: &Matrix<T, D>)
+    where
+        T: Default + Clone,
+        D: Read<T>,
+    {
+        let shape = vec![matrix.rows(), matrix.cols()];
+        self.insert(name, shape, matrix.read_to_vec());
+    }
+
+    /// Reads `buffer` and inserts it with the shape `[len]`.
+    pub fn insert_buffer<D>(&mut self, name: impl Into<String>, buffer: &Buffer<T, D>)
+    where
+        T: Default + Clone,
+        D: Read<T>,
+    {
+        self.insert(name, vec![buffer.len()], buffer.read_to_vec());
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&StateEntry<T>> {
+        self.entries.get(name)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<StateEntry<T>> {
+        self.entries.remove(name)
+    }
+
+    /// Returns the names in ascending order.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StateEntry<T>)> {
+        self.entries
+            .iter()
+            .map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<T> Default for StateDict<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IntoIterator for StateDict<T> {
+    type Item = (String, StateEntry<T>);
+    type IntoIter = std::collections::btree_map::IntoIter<String, StateEntry<T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Returned by [`Module::load_state_dict`](super::Module::load_state_dict).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateDictError {
+    /// A parameter of the module is not contained in the state dict.
+    MissingParam(String),
+    /// The state dict contains a name that is not a parameter of the module.
+    UnexpectedParam(String),
+    ShapeMismatch {
+        name: String,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+}
+
+impl Display for StateDictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateDictError::MissingParam(name) => write!(f, "Missing parameter \"{name}\""),
+            StateDictError::UnexpectedParam(name) => write!(f, "Unexpected parameter \"{name}\""),
+            StateDictError::ShapeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Parameter \"{name}\" has shape {expected:?}, but the state dict contains {found:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StateDictError {}
@@ -5,16 +5,19 @@ use std::{
 
 use custos::{
     number::Numeric,
-    prelude::{Float, One, Two},
+    prelude::{Float, Number, One, Two},
     AddGradFn, AddOperation, Alloc, ApplyFunction, AsNoId, Buffer, Combiner, Device, Eval, HasId,
-    MayTapeActions, MayToCLSource, SetOpHint, Shape, TwoWay, UnaryGrad, WriteBuf, ZeroGrad,
+    MayTapeActions, MayToCLSource, Retriever, SetOpHint, Shape, TwoWay, UnaryGrad, WriteBuf,
+    ZeroGrad,
 };
 
 use crate::{
-    AddElementWiseGrad, BinaryElementWise, BinaryElementWiseGrad, Diagflat, DiagflatGrad, Gemm,
-    GemmGrad, MaxCols, MaxColsGrad, MaxRows, MaxRowsGrad, MeanCols, MeanColsGrad, MeanRows,
-    MeanRowsGrad, RowOp, RowOpGrad, Softmax, SoftmaxGrad, SumCols, SumColsGrad, SumRows,
-    SumRowsGrad, TranposeGrad, Transpose,
+    AddElementWiseGrad, AddStridedAssign, BinaryElementWise, BinaryElementWiseGrad, BinaryStrided,
+    Concat, ConcatGrad, CopyStrided, Diagflat, DiagflatGrad, Gemm, GemmGrad, GemmStrided,
+    GemmStridedGrad, IndexSelectRows, IndexSelectRowsGrad, Layout, MaxCols, MaxColsGrad, MaxRows,
+    MaxRowsGrad, MeanCols, MeanColsGrad, MeanRows, MeanRowsGrad, MulStridedGrad, RandOp, RowOp,
+    RowOpGrad, ScatterAddRows, ScatterAddRowsGrad, Softmax, SoftmaxGrad, Split, SplitGrad, SumCols,
+    SumColsGrad, SumRows, SumRowsGrad, TranposeGrad, Transpose,
 };
 
 pub trait SquareMayGrad<T, S = ()>: Device
@@ -79,6 +82,78 @@ where
 
 impl<T: 'static, S: Shape, D: Device> SquareMayGrad<T, S> for D {}
 
+/// Clamps the elements of a buffer, recording the gradient if autograd is enabled.
+///
+/// This trait replaces the former gradient-less `Clip` trait.
+/// `Clip` still names this trait, hence existing imports and `D: Clip<T, S>` bounds keep compiling for the devices of this crate.
+/// However, such bounds now require the bounds of [`ClipMayGrad`], e.g. `UnaryGrad` and `AddGradFn`, instead of only `ApplyFunction`.
+pub trait ClipMayGrad<T, S: Shape = (), D: Device = Self>: Device {
+    /// Clamps every element of `x` to `[min, max]`.
+    /// The gradient passes through where `min <= x <= max` and is zero elsewhere.
+    #[inline]
+    fn clip(&self, x: &Buffer<T, D, S>, min: T, max: T) -> Buffer<T, D, S> {
+        self.clip_with(x, min, max, false)
+    }
+
+    /// Like [`ClipMayGrad::clip`], but `straight_through` passes the gradient through everywhere.
+    fn clip_with(
+        &self,
+        x: &Buffer<T, D, S>,
+        min: T,
+        max: T,
+        straight_through: bool,
+    ) -> Buffer<T, D, S>;
+}
+
+/// An alias of [`ClipMayGrad`], kept for code written against the former `Clip` trait.
+/// A separate trait would make `clip` calls ambiguous if both are imported.
+#[doc(hidden)]
+pub use ClipMayGrad as Clip;
+
+impl<T, S, D> ClipMayGrad<T, S, D> for D
+where
+    T: TwoWay<T> + Float + 'static,
+    S: Shape,
+    D: ApplyFunction<T, S, Self>
+        + UnaryGrad<T, S, Self>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    fn clip_with(
+        &self,
+        x: &Buffer<T, D, S>,
+        min: T,
+        max: T,
+        straight_through: bool,
+    ) -> Buffer<T, D, S> {
+        let out = self.apply_fn(x, move |x| x.max(min).min(max));
+
+        if straight_through {
+            self.add_grad_fn((x, &out), |(x, out)| {
+                x.device()
+                    .add_unary_grad(x, x.grad_mut(), out.grad(), |_| T::one().identity());
+                Ok(())
+            });
+            return out;
+        }
+
+        self.add_grad_fn((x, min.no_id(), max.no_id(), &out), |(x, min, max, out)| {
+            let (min, neg_max) = (**min, T::zero() - **max);
+            // x <= max is expressed as -x >= -max
+            x.device()
+                .add_unary_grad(x, x.grad_mut(), out.grad(), move |x| {
+                    x.geq(min).mul(x.neg().geq(neg_max))
+                });
+            Ok(())
+        });
+
+        out
+    }
+}
+
 pub trait BinaryOpsMayGrad<T, S: Shape = (), D: Device = Self>: Device {
     fn add(&self, lhs: &Buffer<T, D, S>, rhs: &Buffer<T, D, S>) -> Buffer<T, D, S>;
 
@@ -289,6 +364,200 @@ where
     }
 }
 
+/// Ops on strided windows of buffers, see [`MatrixView`](crate::MatrixView).
+/// The gradients are accumulated into the window of the viewed buffer's gradient.
+pub trait ViewOpsMayGrad<T, D: Device = Self>: Device {
+    /// Copies the window `layout` of `x` to a new contiguous buffer.
+    fn copy_view(&self, x: &Buffer<T, D>, layout: Layout) -> Buffer<T, D>;
+
+    /// Multiplies the windows `lhs_layout` (m x k) and `rhs_layout` (k x n).
+    fn gemm_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D>;
+
+    fn add_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D>;
+
+    fn sub_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D>;
+
+    fn mul_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D>;
+}
+
+impl<T, D> ViewOpsMayGrad<T, D> for D
+where
+    T: Number + 'static,
+    D: CopyStrided<T>
+        + AddStridedAssign<T>
+        + GemmStrided<T>
+        + GemmStridedGrad<T>
+        + BinaryStrided<T>
+        + MulStridedGrad<T>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    fn copy_view(&self, x: &Buffer<T, D>, layout: Layout) -> Buffer<T, D> {
+        let out = self.copy_strided(x, layout);
+
+        self.add_grad_fn((x, &out, layout.no_id()), |(x, out, layout)| {
+            x.device()
+                .add_strided_assign(x.grad_mut(), **layout, out.grad(), T::one());
+            Ok(())
+        });
+
+        out
+    }
+
+    fn gemm_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D> {
+        let out = self.gemm_strided(lhs, lhs_layout, rhs, rhs_layout);
+
+        self.add_grad_fn(
+            (lhs, rhs, &out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                let device = lhs.device();
+                let out_layout = Layout::contiguous(lhs_layout.rows, rhs_layout.cols);
+
+                // lhs_grad += out_grad * rhs^T, rhs_grad += lhs^T * out_grad
+                device.add_gemm_strided(
+                    lhs.grad_mut(),
+                    **lhs_layout,
+                    out.grad(),
+                    out_layout,
+                    rhs,
+                    rhs_layout.t(),
+                );
+                device.add_gemm_strided(
+                    rhs.grad_mut(),
+                    **rhs_layout,
+                    lhs,
+                    lhs_layout.t(),
+                    out.grad(),
+                    out_layout,
+                );
+                Ok(())
+            },
+        );
+
+        out
+    }
+
+    fn add_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D> {
+        let out = self.binary_strided(lhs, lhs_layout, rhs, rhs_layout, |lhs, rhs| lhs.add(rhs));
+
+        self.add_grad_fn(
+            (lhs, rhs, &out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                let device = lhs.device();
+                device.add_strided_assign(lhs.grad_mut(), **lhs_layout, out.grad(), T::one());
+                device.add_strided_assign(rhs.grad_mut(), **rhs_layout, out.grad(), T::one());
+                Ok(())
+            },
+        );
+
+        out
+    }
+
+    fn sub_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D> {
+        let out = self.binary_strided(lhs, lhs_layout, rhs, rhs_layout, |lhs, rhs| lhs.sub(rhs));
+
+        self.add_grad_fn(
+            (lhs, rhs, &out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                let device = lhs.device();
+                device.add_strided_assign(lhs.grad_mut(), **lhs_layout, out.grad(), T::one());
+                device.add_strided_assign(
+                    rhs.grad_mut(),
+                    **rhs_layout,
+                    out.grad(),
+                    T::zero() - T::one(),
+                );
+                Ok(())
+            },
+        );
+
+        out
+    }
+
+    fn mul_views(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, D> {
+        let out = self.binary_strided(lhs, lhs_layout, rhs, rhs_layout, |lhs, rhs| lhs.mul(rhs));
+
+        self.add_grad_fn(
+            (lhs, rhs, &out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                let device = lhs.device();
+                let out_layout = Layout::contiguous(lhs_layout.rows, lhs_layout.cols);
+
+                device.add_mul_strided(
+                    lhs.grad_mut(),
+                    **lhs_layout,
+                    out.grad(),
+                    out_layout,
+                    rhs,
+                    **rhs_layout,
+                );
+                device.add_mul_strided(
+                    rhs.grad_mut(),
+                    **rhs_layout,
+                    out.grad(),
+                    out_layout,
+                    lhs,
+                    **lhs_layout,
+                );
+                Ok(())
+            },
+        );
+
+        out
+    }
+}
+
 pub trait RowOpMayGrad<T, LS: Shape = (), RS: Shape = (), D: Device = Self>: Device {
     fn add_row(
         &self,
@@ -416,22 +685,6 @@ where
 {
 }
 
-pub trait Clip<T: TwoWay<T> + Float, S: Shape>: ApplyFunction<T, S> {
-    #[inline]
-
-    fn clip(&self, x: &Buffer<T, Self, S>, min: T, max: T) -> Buffer<T, Self, S> {
-        self.apply_fn(x, move |x| x.max(min).min(max))
-    }
-}
-
-impl<T, S, D> Clip<T, S> for D
-where
-    D: ApplyFunction<T, S>,
-    T: TwoWay<T> + Float,
-    S: Shape,
-{
-}
-
 pub trait Exp<T: Float, S: Shape>: ApplyFunction<T, S> {
     #[inline]
     fn exp(&self, x: &Buffer<T, Self, S>) -> Buffer<T, Self, S> {
@@ -778,6 +1031,336 @@ where
     }
 }
 
+pub trait ConcatMayGrad<T, LS: Shape = (), RS: Shape = (), OS: Shape = ()>: Device {
+    fn concat_rows(
+        &self,
+        lhs: &Buffer<T, Self, LS>,
+        rhs: &Buffer<T, Self, RS>,
+    ) -> Buffer<T, Self, OS>;
+
+    fn concat_cols(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs: &Buffer<T, Self, LS>,
+        rhs: &Buffer<T, Self, RS>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+impl<T, LS, RS, OS, D> ConcatMayGrad<T, LS, RS, OS> for D
+where
+    T: 'static,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+    D: Concat<T, LS, RS, OS>
+        + ConcatGrad<T, LS, RS, OS>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn concat_rows(
+        &self,
+        lhs: &Buffer<T, Self, LS>,
+        rhs: &Buffer<T, Self, RS>,
+    ) -> Buffer<T, Self, OS> {
+        let out = self.concat_rows(lhs, rhs);
+
+        self.add_grad_fn((lhs, rhs, &out), |(lhs, rhs, out)| {
+            lhs.device()
+                .concat_rows_grad(lhs.grad_mut(), rhs.grad_mut(), out.grad());
+            Ok(())
+        });
+
+        out
+    }
+
+    #[inline]
+    fn concat_cols(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs: &Buffer<T, Self, LS>,
+        rhs: &Buffer<T, Self, RS>,
+    ) -> Buffer<T, Self, OS> {
+        let out = self.concat_cols(lhs_cols, rhs_cols, lhs, rhs);
+
+        self.add_grad_fn(
+            (lhs_cols.no_id(), rhs_cols.no_id(), lhs, rhs, &out),
+            |(lhs_cols, rhs_cols, lhs, rhs, out)| {
+                lhs.device().concat_cols_grad(
+                    **lhs_cols,
+                    **rhs_cols,
+                    lhs.grad_mut(),
+                    rhs.grad_mut(),
+                    out.grad(),
+                );
+                Ok(())
+            },
+        );
+
+        out
+    }
+}
+
+pub trait SplitMayGrad<T, IS: Shape = (), LS: Shape = (), RS: Shape = ()>: Device {
+    fn split_rows(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>);
+
+    fn split_cols(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>);
+}
+
+impl<T, IS, LS, RS, D> SplitMayGrad<T, IS, LS, RS> for D
+where
+    T: 'static,
+    IS: Shape,
+    LS: Shape,
+    RS: Shape,
+    D: Split<T, IS, LS, RS>
+        + SplitGrad<T, IS, LS, RS>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn split_rows(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>) {
+        let (lhs, rhs) = self.split_rows(cols, at, x);
+
+        self.add_grad_fn((x, &lhs, &rhs), |(x, lhs, rhs)| {
+            x.device()
+                .split_rows_grad(x.grad_mut(), lhs.grad(), rhs.grad());
+            Ok(())
+        });
+
+        (lhs, rhs)
+    }
+
+    #[inline]
+    fn split_cols(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>) {
+        let (lhs, rhs) = self.split_cols(cols, at, x);
+
+        self.add_grad_fn(
+            (at.no_id(), cols.no_id(), x, &lhs, &rhs),
+            |(at, cols, x, lhs, rhs)| {
+                x.device().split_cols_grad(
+                    **at,
+                    **cols - **at,
+                    x.grad_mut(),
+                    lhs.grad(),
+                    rhs.grad(),
+                );
+                Ok(())
+            },
+        );
+
+        (lhs, rhs)
+    }
+}
+
+pub trait IndexSelectRowsMayGrad<T, I, IS: Shape = (), OS: Shape = ()>: Device {
+    fn index_select_rows(
+        &self,
+        cols: usize,
+        x: &Buffer<T, Self, IS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+impl<T, I, IS, OS, D> IndexSelectRowsMayGrad<T, I, IS, OS> for D
+where
+    T: 'static,
+    I: 'static,
+    IS: Shape,
+    OS: Shape,
+    D: IndexSelectRows<T, I, IS, OS>
+        + IndexSelectRowsGrad<T, I, IS, OS>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn index_select_rows(
+        &self,
+        cols: usize,
+        x: &Buffer<T, Self, IS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS> {
+        let out = self.index_select_rows(cols, x, indices);
+
+        self.add_grad_fn(
+            (cols.no_id(), x, indices, &out),
+            |(cols, x, indices, out)| {
+                x.device()
+                    .index_select_rows_grad(**cols, indices, x.grad_mut(), out.grad());
+                Ok(())
+            },
+        );
+
+        out
+    }
+}
+
+pub trait ScatterAddRowsMayGrad<T, I, IS: Shape = (), OS: Shape = ()>: Device {
+    fn scatter_add_rows(
+        &self,
+        rows: usize,
+        cols: usize,
+        x: &Buffer<T, Self, IS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+impl<T, I, IS, OS, D> ScatterAddRowsMayGrad<T, I, IS, OS> for D
+where
+    T: 'static,
+    I: 'static,
+    IS: Shape,
+    OS: Shape,
+    D: ScatterAddRows<T, I, IS, OS>
+        + ScatterAddRowsGrad<T, I, IS, OS>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    #[inline]
+    fn scatter_add_rows(
+        &self,
+        rows: usize,
+        cols: usize,
+        x: &Buffer<T, Self, IS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS> {
+        let out = self.scatter_add_rows(rows, cols, x, indices);
+
+        self.add_grad_fn(
+            (cols.no_id(), x, indices, &out),
+            |(cols, x, indices, out)| {
+                x.device()
+                    .scatter_add_rows_grad(**cols, indices, x.grad_mut(), out.grad());
+                Ok(())
+            },
+        );
+
+        out
+    }
+}
+
+pub trait EmbeddingMayGrad<T, I, WS: Shape = (), OS: Shape = ()>: Device {
+    /// Looks up the rows of `weights` (with `dim` columns) given by `indices`.
+    /// Unlike a `onehot` + `gemm` formulation, the backward pass only touches referenced rows.
+    fn embedding(
+        &self,
+        dim: usize,
+        weights: &Buffer<T, Self, WS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+impl<T, I, WS, OS, D> EmbeddingMayGrad<T, I, WS, OS> for D
+where
+    T: 'static,
+    I: 'static,
+    WS: Shape,
+    OS: Shape,
+    D: IndexSelectRowsMayGrad<T, I, WS, OS>,
+{
+    #[inline]
+    fn embedding(
+        &self,
+        dim: usize,
+        weights: &Buffer<T, Self, WS>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self, OS> {
+        // the backward pass of the row selection is already sparse
+        IndexSelectRowsMayGrad::index_select_rows(self, dim, weights, indices)
+    }
+}
+
+pub trait DropoutMayGrad<T, S: Shape = ()>: Device {
+    /// Zeroes every element of `x` with probability `p` and scales the kept elements by `1 / (1 - p)`.
+    /// If `training` is false, this is the identity.
+    fn dropout(&self, x: &Buffer<T, Self, S>, p: T, training: bool) -> Buffer<T, Self, S>;
+}
+
+impl<T, S, D> DropoutMayGrad<T, S> for D
+where
+    T: TwoWay<T> + Float + 'static,
+    S: Shape,
+    D: RandOp<T, S>
+        + Retriever<T, S>
+        + ApplyFunction<T, S>
+        + BinaryElementWise<T, S>
+        + UnaryGrad<T, S>
+        + MayTapeActions
+        + Alloc<T>
+        + ZeroGrad<T>
+        + AddGradFn
+        + 'static,
+{
+    fn dropout(&self, x: &Buffer<T, Self, S>, p: T, training: bool) -> Buffer<T, Self, S> {
+        if !training || p == T::zero() {
+            let out = self.apply_fn(x, |x| x);
+
+            self.add_grad_fn((x, &out), |(x, out)| {
+                x.device()
+                    .add_unary_grad(x, x.grad_mut(), out.grad(), |_| T::one().identity());
+                Ok(())
+            });
+
+            return out;
+        }
+
+        assert!(
+            p > T::zero() && p < T::one(),
+            "Dropout probability must be in [0, 1)"
+        );
+
+        let mut noise = self.retrieve(x.len(), x).unwrap();
+        self.rand(&mut noise, T::zero(), T::one());
+
+        let scale = T::one() / (T::one() - p);
+        let mask = self.apply_fn(&noise, move |noise| noise.geq(p).mul(scale));
+        let out = self.binary_ew(x, &mask, |x, mask| x.mul(mask));
+
+        // the mask already contains the scale, hence it is the derivative
+        self.add_grad_fn((x, &mask, &out), |(x, mask, out)| {
+            x.device()
+                .add_unary_grad(mask, x.grad_mut(), out.grad(), |mask| mask);
+            Ok(())
+        });
+
+        out
+    }
+}
+
 macro_rules! _impl_may_autograd_op {
     ($trait_name:ident, $forward_trait:ident, $backward_trait:ident) => {};
 }
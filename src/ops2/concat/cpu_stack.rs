@@ -0,0 +1,279 @@
+use std::ops::Deref;
+
+use custos::{impl_stack, AddOperation, AsNoId, Buffer, Device, Retrieve, Retriever, Shape, CPU};
+
+use crate::{
+    assign_or_set::{AssignOrSet, Set},
+    Concat, Split,
+};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[impl_stack]
+impl<T, LS, RS, OS, D, Mods> Concat<T, LS, RS, OS, D> for CPU<Mods>
+where
+    T: Copy + Default + 'static,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+    D: Device + 'static,
+    D::Base<T, LS>: Deref<Target = [T]>,
+    D::Base<T, RS>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T, OS> + AddOperation + 'static,
+{
+    fn concat_rows(&self, lhs: &Buffer<T, D, LS>, rhs: &Buffer<T, D, RS>) -> Buffer<T, Self, OS> {
+        let mut out = self.retrieve(lhs.len() + rhs.len(), (lhs, rhs)).unwrap();
+        self.add_op((lhs, rhs, &mut out), |(lhs, rhs, out)| {
+            slice_concat_rows::<_, Set>(lhs, rhs, out);
+            Ok(())
+        })
+        .unwrap();
+        out
+    }
+
+    fn concat_cols(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs: &Buffer<T, D, LS>,
+        rhs: &Buffer<T, D, RS>,
+    ) -> Buffer<T, Self, OS> {
+        // rows * lhs_cols * rhs_cols on both sides, holds for zero-width operands as well
+        debug_assert_eq!(lhs.len() * rhs_cols, rhs.len() * lhs_cols);
+
+        let mut out = self.retrieve(lhs.len() + rhs.len(), (lhs, rhs)).unwrap();
+        self.add_op(
+            (lhs_cols.no_id(), rhs_cols.no_id(), lhs, rhs, &mut out),
+            |(lhs_cols, rhs_cols, lhs, rhs, out)| {
+                slice_concat_cols::<_, Set>(**lhs_cols, **rhs_cols, lhs, rhs, out);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+#[impl_stack]
+impl<T, IS, LS, RS, D, Mods> Split<T, IS, LS, RS, D> for CPU<Mods>
+where
+    T: Copy + Default + 'static,
+    IS: Shape,
+    LS: Shape,
+    RS: Shape,
+    D: Device + 'static,
+    D::Base<T, IS>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T, LS> + Retrieve<Self, T, RS> + AddOperation + 'static,
+{
+    fn split_rows(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, D, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>) {
+        let lhs_len = at * cols;
+        debug_assert!(lhs_len <= x.len());
+
+        let mut lhs = self.retrieve(lhs_len, x).unwrap();
+        let mut rhs = self.retrieve(x.len() - lhs_len, x).unwrap();
+        self.add_op((x, &mut lhs, &mut rhs), |(x, lhs, rhs)| {
+            slice_split_rows::<_, Set>(x, lhs, rhs);
+            Ok(())
+        })
+        .unwrap();
+        (lhs, rhs)
+    }
+
+    fn split_cols(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, D, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>) {
+        debug_assert!(at <= cols);
+        let rows = x.len().checked_div(cols).unwrap_or(0);
+
+        let mut lhs = self.retrieve(rows * at, x).unwrap();
+        let mut rhs = self.retrieve(rows * (cols - at), x).unwrap();
+        self.add_op(
+            (at.no_id(), cols.no_id(), x, &mut lhs, &mut rhs),
+            |(at, cols, x, lhs, rhs)| {
+                slice_split_cols::<_, Set>(**at, **cols - **at, x, lhs, rhs);
+                Ok(())
+            },
+        )
+        .unwrap();
+        (lhs, rhs)
+    }
+}
+
+/// Writes the values of `lhs` followed by the values of `rhs` to `out`.
+/// For row-major data, this stacks the rows of `rhs` below the rows of `lhs`.
+pub fn slice_concat_rows<T: Clone, AOS: AssignOrSet<T>>(lhs: &[T], rhs: &[T], out: &mut [T]) {
+    for (out, val) in out.iter_mut().zip(lhs.iter().chain(rhs)) {
+        AOS::assign_or_set(out, val.clone());
+    }
+}
+
+/// Writes every row of `lhs` followed by the matching row of `rhs` to `out`.
+///
+/// # Example
+/// ```
+/// use sliced::{slice_concat_cols, assign_or_set::Set};
+///
+/// let lhs = [1, 4];
+/// let rhs = [2, 3, 5, 6];
+/// let mut out = [0; 6];
+///
+/// slice_concat_cols::<_, Set>(1, 2, &lhs, &rhs, &mut out);
+/// assert_eq!(out, [1, 2, 3, 4, 5, 6]);
+/// ```
+pub fn slice_concat_cols<T: Clone, AOS: AssignOrSet<T>>(
+    lhs_cols: usize,
+    rhs_cols: usize,
+    lhs: &[T],
+    rhs: &[T],
+    out: &mut [T],
+) {
+    let cols = lhs_cols + rhs_cols;
+    let rows = out.len().checked_div(cols).unwrap_or(0);
+
+    for row in 0..rows {
+        let lhs_row = &lhs[row * lhs_cols..(row + 1) * lhs_cols];
+        let rhs_row = &rhs[row * rhs_cols..(row + 1) * rhs_cols];
+
+        for (out, val) in out[row * cols..(row + 1) * cols]
+            .iter_mut()
+            .zip(lhs_row.iter().chain(rhs_row))
+        {
+            AOS::assign_or_set(out, val.clone());
+        }
+    }
+}
+
+/// Writes the first `lhs.len()` values of `x` to `lhs` and the remaining ones to `rhs`.
+pub fn slice_split_rows<T: Clone, AOS: AssignOrSet<T>>(x: &[T], lhs: &mut [T], rhs: &mut [T]) {
+    for (out, val) in lhs.iter_mut().chain(rhs.iter_mut()).zip(x) {
+        AOS::assign_or_set(out, val.clone());
+    }
+}
+
+/// Writes the first `lhs_cols` columns of every row of `x` to `lhs` and the remaining columns to `rhs`.
+pub fn slice_split_cols<T: Clone, AOS: AssignOrSet<T>>(
+    lhs_cols: usize,
+    rhs_cols: usize,
+    x: &[T],
+    lhs: &mut [T],
+    rhs: &mut [T],
+) {
+    let cols = lhs_cols + rhs_cols;
+    let rows = x.len().checked_div(cols).unwrap_or(0);
+
+    for row in 0..rows {
+        let lhs_row = &mut lhs[row * lhs_cols..(row + 1) * lhs_cols];
+        let rhs_row = &mut rhs[row * rhs_cols..(row + 1) * rhs_cols];
+
+        for (out, val) in lhs_row
+            .iter_mut()
+            .chain(rhs_row.iter_mut())
+            .zip(&x[row * cols..(row + 1) * cols])
+        {
+            AOS::assign_or_set(out, val.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assign_or_set::{Assign, Set},
+        slice_concat_cols, slice_concat_rows, slice_split_cols, slice_split_rows,
+    };
+
+    #[test]
+    fn test_slice_concat_rows() {
+        let lhs = [1, 2, 3, 4];
+        let rhs = [5, 6];
+
+        let mut out = [0; 6];
+        slice_concat_rows::<_, Set>(&lhs, &rhs, &mut out);
+
+        assert_eq!(out, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_slice_concat_cols() {
+        #[rustfmt::skip]
+        let lhs = [
+            1, 2,
+            5, 6,
+            9, 10,
+        ];
+        let rhs = [3, 7, 11];
+
+        let mut out = [0; 9];
+        slice_concat_cols::<_, Set>(2, 1, &lhs, &rhs, &mut out);
+
+        #[rustfmt::skip]
+        let expected = [
+            1, 2, 3,
+            5, 6, 7,
+            9, 10, 11,
+        ];
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_slice_split_rows_assign() {
+        let x = [1, 2, 3, 4, 5, 6];
+
+        let mut lhs = [1; 2];
+        let mut rhs = [1; 4];
+        slice_split_rows::<_, Assign>(&x, &mut lhs, &mut rhs);
+
+        assert_eq!(lhs, [2, 3]);
+        assert_eq!(rhs, [4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_slice_split_cols() {
+        #[rustfmt::skip]
+        let x = [
+            1, 2, 3,
+            5, 6, 7,
+            9, 10, 11,
+        ];
+
+        let mut lhs = [0; 3];
+        let mut rhs = [0; 6];
+        slice_split_cols::<_, Set>(1, 2, &x, &mut lhs, &mut rhs);
+
+        assert_eq!(lhs, [1, 5, 9]);
+        assert_eq!(rhs, [2, 3, 6, 7, 10, 11]);
+    }
+
+    #[test]
+    fn test_slice_concat_split_cols_empty_side() {
+        let x = [1, 2, 3, 4];
+
+        let mut out = [0; 4];
+        slice_concat_cols::<_, Set>(0, 2, &[], &x, &mut out);
+        assert_eq!(out, x);
+
+        slice_concat_cols::<_, Set>(2, 0, &x, &[], &mut out);
+        assert_eq!(out, x);
+
+        let mut rhs = [0; 4];
+        slice_split_cols::<_, Set>(0, 2, &x, &mut [], &mut rhs);
+        assert_eq!(rhs, x);
+
+        let mut lhs = [0; 4];
+        slice_split_cols::<_, Set>(2, 0, &x, &mut lhs, &mut []);
+        assert_eq!(lhs, x);
+
+        // no columns at all
+        slice_concat_cols::<i32, Set>(0, 0, &[], &[], &mut []);
+        slice_split_cols::<i32, Set>(0, 0, &[], &mut [], &mut []);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(any(feature = "cpu", feature = "stack"))]
+mod cpu_stack;
+#[cfg(any(feature = "cpu", feature = "stack"))]
+pub use cpu_stack::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Shape};
+
+/// Slices the gradient of a concatenation back to both inputs.
+pub trait ConcatGrad<T, LS: Shape = (), RS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn concat_rows_grad(
+        &self,
+        lhs_grad: &mut Buffer<T, D, LS>,
+        rhs_grad: &mut Buffer<T, D, RS>,
+        out_grad: &Buffer<T, D, OS>,
+    );
+
+    fn concat_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs_grad: &mut Buffer<T, D, LS>,
+        rhs_grad: &mut Buffer<T, D, RS>,
+        out_grad: &Buffer<T, D, OS>,
+    );
+}
+
+/// Joins the gradients of both parts of a split back into the gradient of the input.
+pub trait SplitGrad<T, IS: Shape = (), LS: Shape = (), RS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn split_rows_grad(
+        &self,
+        x_grad: &mut Buffer<T, D, IS>,
+        lhs_grad: &Buffer<T, D, LS>,
+        rhs_grad: &Buffer<T, D, RS>,
+    );
+
+    fn split_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        x_grad: &mut Buffer<T, D, IS>,
+        lhs_grad: &Buffer<T, D, LS>,
+        rhs_grad: &Buffer<T, D, RS>,
+    );
+}
@@ -0,0 +1,81 @@
+use std::ops::{AddAssign, Deref, DerefMut};
+
+use custos::{impl_stack, Buffer, Device, OnDropBuffer, Shape, CPU};
+
+use crate::{
+    assign_or_set::Assign, slice_concat_cols, slice_concat_rows, slice_split_cols,
+    slice_split_rows, ConcatGrad, SplitGrad,
+};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[impl_stack]
+impl<T, LS, RS, OS, D, Mods: OnDropBuffer> ConcatGrad<T, LS, RS, OS, D> for CPU<Mods>
+where
+    T: Copy + AddAssign,
+    LS: Shape,
+    RS: Shape,
+    OS: Shape,
+    D: Device,
+    D::Base<T, LS>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, RS>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, OS>: Deref<Target = [T]>,
+{
+    #[inline]
+    fn concat_rows_grad(
+        &self,
+        lhs_grad: &mut Buffer<T, D, LS>,
+        rhs_grad: &mut Buffer<T, D, RS>,
+        out_grad: &Buffer<T, D, OS>,
+    ) {
+        slice_split_rows::<_, Assign>(out_grad, lhs_grad, rhs_grad);
+    }
+
+    #[inline]
+    fn concat_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs_grad: &mut Buffer<T, D, LS>,
+        rhs_grad: &mut Buffer<T, D, RS>,
+        out_grad: &Buffer<T, D, OS>,
+    ) {
+        slice_split_cols::<_, Assign>(lhs_cols, rhs_cols, out_grad, lhs_grad, rhs_grad);
+    }
+}
+
+#[impl_stack]
+impl<T, IS, LS, RS, D, Mods: OnDropBuffer> SplitGrad<T, IS, LS, RS, D> for CPU<Mods>
+where
+    T: Copy + AddAssign,
+    IS: Shape,
+    LS: Shape,
+    RS: Shape,
+    D: Device,
+    D::Base<T, IS>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, LS>: Deref<Target = [T]>,
+    D::Base<T, RS>: Deref<Target = [T]>,
+{
+    #[inline]
+    fn split_rows_grad(
+        &self,
+        x_grad: &mut Buffer<T, D, IS>,
+        lhs_grad: &Buffer<T, D, LS>,
+        rhs_grad: &Buffer<T, D, RS>,
+    ) {
+        slice_concat_rows::<_, Assign>(lhs_grad, rhs_grad, x_grad);
+    }
+
+    #[inline]
+    fn split_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        x_grad: &mut Buffer<T, D, IS>,
+        lhs_grad: &Buffer<T, D, LS>,
+        rhs_grad: &Buffer<T, D, RS>,
+    ) {
+        slice_concat_cols::<_, Assign>(lhs_cols, rhs_cols, lhs_grad, rhs_grad, x_grad);
+    }
+}
@@ -0,0 +1,62 @@
+use std::ops::AddAssign;
+
+use custos::{Buffer, CDatatype, OnDropBuffer, OpenCL};
+
+use crate::{assign_or_set::Assign, cl_concat_cols, cl_split_cols, ConcatGrad, SplitGrad};
+
+impl<Mods: OnDropBuffer, T: CDatatype + AddAssign> ConcatGrad<T> for OpenCL<Mods> {
+    #[inline]
+    fn concat_rows_grad(
+        &self,
+        lhs_grad: &mut Buffer<T, Self>,
+        rhs_grad: &mut Buffer<T, Self>,
+        out_grad: &Buffer<T, Self>,
+    ) {
+        let (lhs_len, rhs_len) = (lhs_grad.len(), rhs_grad.len());
+        cl_split_cols::<T, Assign>(self, lhs_len, rhs_len, out_grad, lhs_grad, rhs_grad).unwrap();
+    }
+
+    #[inline]
+    fn concat_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs_grad: &mut Buffer<T, Self>,
+        rhs_grad: &mut Buffer<T, Self>,
+        out_grad: &Buffer<T, Self>,
+    ) {
+        cl_split_cols::<T, Assign>(self, lhs_cols, rhs_cols, out_grad, lhs_grad, rhs_grad).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + AddAssign> SplitGrad<T> for OpenCL<Mods> {
+    #[inline]
+    fn split_rows_grad(
+        &self,
+        x_grad: &mut Buffer<T, Self>,
+        lhs_grad: &Buffer<T, Self>,
+        rhs_grad: &Buffer<T, Self>,
+    ) {
+        cl_concat_cols::<T, Assign>(
+            self,
+            lhs_grad.len(),
+            rhs_grad.len(),
+            lhs_grad,
+            rhs_grad,
+            x_grad,
+        )
+        .unwrap();
+    }
+
+    #[inline]
+    fn split_cols_grad(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        x_grad: &mut Buffer<T, Self>,
+        lhs_grad: &Buffer<T, Self>,
+        rhs_grad: &Buffer<T, Self>,
+    ) {
+        cl_concat_cols::<T, Assign>(self, lhs_cols, rhs_cols, lhs_grad, rhs_grad, x_grad).unwrap();
+    }
+}
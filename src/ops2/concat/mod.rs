@@ -0,0 +1,111 @@
+mod grad;
+use custos::{Buffer, Device, Shape};
+pub use grad::*;
+
+#[cfg(any(feature = "cpu", feature = "stack"))]
+mod cpu_stack;
+#[cfg(any(feature = "cpu", feature = "stack"))]
+pub use cpu_stack::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+/// Joins two row-major [`Buffer`]s along the rows or columns without gradients.
+pub trait Concat<T, LS: Shape = (), RS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    /// Appends the rows of `rhs` below the rows of `lhs`. Both need the same amount of columns.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, Concat, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let lhs = Buffer::from((&device, [1, 2, 3]));
+    /// let rhs = Buffer::from((&device, [4, 5, 6, 7, 8, 9]));
+    ///
+    /// let out: Buffer<i32> = device.concat_rows(&lhs, &rhs);
+    /// assert_eq!(&*out, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    /// ```
+    fn concat_rows(&self, lhs: &Buffer<T, D, LS>, rhs: &Buffer<T, D, RS>) -> Buffer<T, Self, OS>;
+
+    /// Appends the columns of `rhs` to the right of the columns of `lhs`. Both need the same amount of rows.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, Concat, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// // 2 x 1
+    /// let lhs = Buffer::from((&device, [1, 4]));
+    /// // 2 x 2
+    /// let rhs = Buffer::from((&device, [2, 3, 5, 6]));
+    ///
+    /// let out: Buffer<i32> = device.concat_cols(1, 2, &lhs, &rhs);
+    /// assert_eq!(&*out, [
+    ///     1, 2, 3,
+    ///     4, 5, 6
+    /// ]);
+    /// ```
+    fn concat_cols(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs: &Buffer<T, D, LS>,
+        rhs: &Buffer<T, D, RS>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+/// Splits a row-major [`Buffer`] into two parts along the rows or columns without gradients.
+pub trait Split<T, IS: Shape = (), LS: Shape = (), RS: Shape = (), D: Device = Self>:
+    Device
+{
+    /// Splits `x` before the row `at`. The first `Buffer` contains the rows `0..at`.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, Split, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+    ///
+    /// let (top, bottom): (Buffer<i32>, Buffer<i32>) = device.split_rows(2, 1, &x);
+    /// assert_eq!(&*top, [1, 2]);
+    /// assert_eq!(&*bottom, [3, 4, 5, 6]);
+    /// ```
+    fn split_rows(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, D, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>);
+
+    /// Splits `x` before the column `at`. The first `Buffer` contains the columns `0..at`.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, Split, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let x = Buffer::from((&device, [
+    ///     1, 2, 3,
+    ///     4, 5, 6
+    /// ]));
+    ///
+    /// let (left, right): (Buffer<i32>, Buffer<i32>) = device.split_cols(3, 1, &x);
+    /// assert_eq!(&*left, [1, 4]);
+    /// assert_eq!(&*right, [2, 3, 5, 6]);
+    /// ```
+    fn split_cols(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, D, IS>,
+    ) -> (Buffer<T, Self, LS>, Buffer<T, Self, RS>);
+}
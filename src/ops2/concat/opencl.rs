@@ -0,0 +1,205 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    Buffer, CDatatype, OpenCL, Retrieve, Retriever,
+};
+
+use crate::{
+    assign_or_set::{AssignOrSet, Set},
+    Concat, Split,
+};
+
+impl<Mods: Retrieve<Self, T>, T: CDatatype> Concat<T> for OpenCL<Mods> {
+    #[inline]
+    fn concat_rows(&self, lhs: &Buffer<T, Self>, rhs: &Buffer<T, Self>) -> Buffer<T, Self> {
+        let mut out = self.retrieve(lhs.len() + rhs.len(), (lhs, rhs)).unwrap();
+        // row-major: appending rows is the same as concatenating the columns of a single row
+        cl_concat_cols::<T, Set>(self, lhs.len(), rhs.len(), lhs, rhs, &mut out).unwrap();
+        out
+    }
+
+    #[inline]
+    fn concat_cols(
+        &self,
+        lhs_cols: usize,
+        rhs_cols: usize,
+        lhs: &Buffer<T, Self>,
+        rhs: &Buffer<T, Self>,
+    ) -> Buffer<T, Self> {
+        let mut out = self.retrieve(lhs.len() + rhs.len(), (lhs, rhs)).unwrap();
+        cl_concat_cols::<T, Set>(self, lhs_cols, rhs_cols, lhs, rhs, &mut out).unwrap();
+        out
+    }
+}
+
+impl<Mods: Retrieve<Self, T>, T: CDatatype> Split<T> for OpenCL<Mods> {
+    #[inline]
+    fn split_rows(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self>,
+    ) -> (Buffer<T, Self>, Buffer<T, Self>) {
+        let lhs_len = at * cols;
+        let mut lhs = self.retrieve(lhs_len, x).unwrap();
+        let mut rhs = self.retrieve(x.len() - lhs_len, x).unwrap();
+        cl_split_cols::<T, Set>(self, lhs_len, x.len() - lhs_len, x, &mut lhs, &mut rhs).unwrap();
+        (lhs, rhs)
+    }
+
+    #[inline]
+    fn split_cols(
+        &self,
+        cols: usize,
+        at: usize,
+        x: &Buffer<T, Self>,
+    ) -> (Buffer<T, Self>, Buffer<T, Self>) {
+        let rows = x.len() / cols;
+        let mut lhs = self.retrieve(rows * at, x).unwrap();
+        let mut rhs = self.retrieve(rows * (cols - at), x).unwrap();
+        cl_split_cols::<T, Set>(self, at, cols - at, x, &mut lhs, &mut rhs).unwrap();
+        (lhs, rhs)
+    }
+}
+
+pub fn cl_concat_cols<T: CDatatype, AOS: AssignOrSet<T>>(
+    device: &CLDevice,
+    lhs_cols: usize,
+    rhs_cols: usize,
+    lhs: &CLPtr<T>,
+    rhs: &CLPtr<T>,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    // a zero-width side does not know the row count
+    let rows = match (lhs_cols, rhs_cols) {
+        (0, 0) => 0,
+        (0, _) => rhs.len() / rhs_cols,
+        _ => lhs.len() / lhs_cols,
+    };
+    if rows == 0 || lhs_cols + rhs_cols == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void concat_cols(__global const {dtype}* lhs, __global const {dtype}* rhs, __global {dtype}* out, int lhs_cols, int rhs_cols) {{
+            size_t row = get_global_id(0);
+            size_t col = get_global_id(1);
+            size_t cols = lhs_cols + rhs_cols;
+
+            if (col < lhs_cols) {{
+                out[row * cols + col] {aos} lhs[row * lhs_cols + col];
+            }} else {{
+                out[row * cols + col] {aos} rhs[row * rhs_cols + col - lhs_cols];
+            }}
+        }}
+    ",
+        aos = AOS::STR_OP,
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [rows, lhs_cols + rhs_cols, 0],
+        None,
+        &[lhs, rhs, out, &(lhs_cols as i32), &(rhs_cols as i32)],
+    )
+}
+
+pub fn cl_split_cols<T: CDatatype, AOS: AssignOrSet<T>>(
+    device: &CLDevice,
+    lhs_cols: usize,
+    rhs_cols: usize,
+    x: &CLPtr<T>,
+    lhs: &mut CLPtr<T>,
+    rhs: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let cols = lhs_cols + rhs_cols;
+    if cols == 0 || x.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void split_cols(__global const {dtype}* x, __global {dtype}* lhs, __global {dtype}* rhs, int lhs_cols, int rhs_cols) {{
+            size_t row = get_global_id(0);
+            size_t col = get_global_id(1);
+            size_t cols = lhs_cols + rhs_cols;
+
+            if (col < lhs_cols) {{
+                lhs[row * lhs_cols + col] {aos} x[row * cols + col];
+            }} else {{
+                rhs[row * rhs_cols + col - lhs_cols] {aos} x[row * cols + col];
+            }}
+        }}
+    ",
+        aos = AOS::STR_OP,
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [x.len() / cols, cols, 0],
+        None,
+        &[x, lhs, rhs, &(lhs_cols as i32), &(rhs_cols as i32)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{assign_or_set::Set, cl_concat_cols, cl_split_cols};
+
+    #[test]
+    fn test_cl_concat_cols() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let lhs = Buffer::from((&device, [1, 2, 5, 6, 9, 10]));
+        let rhs = Buffer::from((&device, [3, 7, 11]));
+        let mut out = Buffer::<i32, _>::new(&device, 9);
+
+        cl_concat_cols::<_, Set>(&device, 2, 1, &lhs, &rhs, &mut out)?;
+
+        #[rustfmt::skip]
+        let expected = vec![
+            1, 2, 3,
+            5, 6, 7,
+            9, 10, 11,
+        ];
+        assert_eq!(out.read(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_split_cols() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let x = Buffer::from((&device, [1, 2, 3, 5, 6, 7, 9, 10, 11]));
+        let mut lhs = Buffer::<i32, _>::new(&device, 3);
+        let mut rhs = Buffer::<i32, _>::new(&device, 6);
+
+        cl_split_cols::<_, Set>(&device, 1, 2, &x, &mut lhs, &mut rhs)?;
+
+        assert_eq!(lhs.read(), vec![1, 5, 9]);
+        assert_eq!(rhs.read(), vec![2, 3, 6, 7, 10, 11]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_concat_split_cols_empty_side() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let x = Buffer::from((&device, [1, 2, 3, 4]));
+        let empty = Buffer::<i32, _>::new(&device, 0);
+
+        let mut out = Buffer::<i32, _>::new(&device, 4);
+        cl_concat_cols::<_, Set>(&device, 0, 2, &empty, &x, &mut out)?;
+        assert_eq!(out.read(), vec![1, 2, 3, 4]);
+
+        let mut lhs = Buffer::<i32, _>::new(&device, 0);
+        let mut rhs = Buffer::<i32, _>::new(&device, 4);
+        cl_split_cols::<_, Set>(&device, 0, 2, &x, &mut lhs, &mut rhs)?;
+        assert_eq!(rhs.read(), vec![1, 2, 3, 4]);
+        Ok(())
+    }
+}
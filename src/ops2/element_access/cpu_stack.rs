@@ -0,0 +1,74 @@
+use std::ops::{Deref, DerefMut};
+
+use custos::{impl_stack, Buffer, Device, OnDropBuffer, Shape, CPU};
+
+use crate::{assert_strided_in_bounds, ReadStrided, WriteElement};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+pub fn slice_read_strided<T: Clone>(x: &[T], start: usize, stride: usize, len: usize) -> Vec<T> {
+    (0..len)
+        .map(|idx| x[start + idx * stride].clone())
+        .collect()
+}
+
+#[impl_stack]
+impl<Mods: OnDropBuffer, T, D, S> ReadStrided<T, S, D> for CPU<Mods>
+where
+    T: Clone,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]>,
+{
+    #[inline]
+    fn read_strided(&self, x: &Buffer<T, D, S>, start: usize, stride: usize, len: usize) -> Vec<T> {
+        assert_strided_in_bounds(x.len(), start, stride, len);
+        slice_read_strided(x, start, stride, len)
+    }
+
+    #[inline]
+    fn read_element(&self, x: &Buffer<T, D, S>, idx: usize) -> T {
+        x[idx].clone()
+    }
+}
+
+#[impl_stack]
+impl<Mods: OnDropBuffer, T, D, S> WriteElement<T, S, D> for CPU<Mods>
+where
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn write_element(&self, x: &mut Buffer<T, D, S>, idx: usize, value: T) {
+        x[idx] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slice_read_strided;
+
+    #[cfg(feature = "cpu")]
+    #[test]
+    #[should_panic(expected = "with a stride of 3")]
+    fn test_read_strided_out_of_bounds() {
+        use custos::{Buffer, CPU};
+
+        use crate::ReadStrided;
+
+        let device = CPU::<custos::Base>::new();
+        let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+
+        device.read_strided(&x, 1, 3, 3);
+    }
+
+    #[test]
+    fn test_slice_read_strided() {
+        let x = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_read_strided(&x, 0, 1, 3), [1, 2, 3]);
+        assert_eq!(slice_read_strided(&x, 2, 3, 2), [3, 6]);
+        assert_eq!(slice_read_strided(&x, 4, 3, 0), []);
+    }
+}
@@ -0,0 +1,59 @@
+//! Reading and writing single values or rows and columns without reading the whole buffer.
+//! Used by [`Matrix::get`](crate::Matrix::get), [`Matrix::row`](crate::Matrix::row) etc.
+
+#[cfg(any(feature = "cpu", feature = "stack"))]
+mod cpu_stack;
+#[cfg(any(feature = "cpu", feature = "stack"))]
+pub use cpu_stack::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Shape};
+
+pub trait ReadStrided<T, S: Shape = (), D: Device = Self>: Device {
+    /// Reads `len` values of `x`, starting at `start`, that are `stride` values apart.
+    ///
+    /// # Panics
+    /// If the last value to read is out of bounds.
+    ///
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, ReadStrided, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    /// let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+    ///
+    /// assert_eq!(device.read_strided(&x, 1, 2, 3), [2, 4, 6]);
+    /// ```
+    fn read_strided(&self, x: &Buffer<T, D, S>, start: usize, stride: usize, len: usize) -> Vec<T>;
+
+    /// Reads the value at `idx`.
+    #[inline]
+    fn read_element(&self, x: &Buffer<T, D, S>, idx: usize) -> T {
+        self.read_strided(x, idx, 1, 1).remove(0)
+    }
+}
+
+/// Panics if `len` values of a buffer with `x_len` values, starting at `start` and `stride` values apart, are not in bounds.
+/// Every backend checks the bounds before reading, since a kernel would read out of bounds silently.
+pub fn assert_strided_in_bounds(x_len: usize, start: usize, stride: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let last = (len - 1)
+        .checked_mul(stride)
+        .and_then(|offset| offset.checked_add(start));
+    assert!(
+        last.is_some_and(|last| last < x_len),
+        "Reading {len} values, starting at {start} with a stride of {stride}, is out of bounds for a buffer with {x_len} values"
+    );
+}
+
+pub trait WriteElement<T, S: Shape = (), D: Device = Self>: Device {
+    /// Sets the value at `idx` to `value`.
+    fn write_element(&self, x: &mut Buffer<T, D, S>, idx: usize, value: T);
+}
@@ -0,0 +1,101 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    Buffer, CDatatype, CopySlice, OnDropBuffer, OpenCL,
+};
+
+use crate::{assert_strided_in_bounds, ReadStrided, WriteElement};
+
+impl<Mods: OnDropBuffer, T: CDatatype> ReadStrided<T> for OpenCL<Mods> {
+    fn read_strided(&self, x: &Buffer<T, Self>, start: usize, stride: usize, len: usize) -> Vec<T> {
+        assert_strided_in_bounds(x.len(), start, stride, len);
+
+        if len == 0 {
+            return Vec::new();
+        }
+
+        // contiguous values are copied out of `x` without launching a kernel
+        if stride == 1 || len == 1 {
+            return self.copy_slice(x, start..start + len).read();
+        }
+
+        let mut out = Buffer::<T, _>::new(self, len);
+        cl_read_strided(self, x, &mut out, start, stride).unwrap();
+        out.read()
+    }
+
+    #[inline]
+    fn read_element(&self, x: &Buffer<T, Self>, idx: usize) -> T {
+        assert_strided_in_bounds(x.len(), idx, 1, 1);
+        self.copy_slice(x, idx..idx + 1).read()[0]
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype> WriteElement<T> for OpenCL<Mods> {
+    fn write_element(&self, x: &mut Buffer<T, Self>, idx: usize, value: T) {
+        assert!(
+            idx < x.len(),
+            "Index {idx} is out of bounds for a buffer with {} values",
+            x.len()
+        );
+
+        let mut element = Buffer::<T, _>::new(self, 1);
+        element.write(&[value]);
+        self.copy_slice_to(&element, .., x, idx..idx + 1);
+    }
+}
+
+/// Copies `out.len()` values of `x`, starting at `start`, that are `stride` values apart, to `out`.
+pub fn cl_read_strided<T: CDatatype>(
+    device: &CLDevice,
+    x: &CLPtr<T>,
+    out: &mut CLPtr<T>,
+    start: usize,
+    stride: usize,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void read_strided(__global const {dtype}* x, __global {dtype}* out, int start, int stride) {{
+            size_t id = get_global_id(0);
+            out[id] = x[start + id * stride];
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [out.len(), 0, 0],
+        None,
+        &[x, out, &(start as i32), &(stride as i32)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{ReadStrided, WriteElement};
+
+    #[test]
+    fn test_cl_read_strided_write_element() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut x = Buffer::from((&device, [1f32, 2., 3., 4., 5., 6.]));
+        assert_eq!(device.read_strided(&x, 2, 3, 2), [3., 6.]);
+        assert_eq!(device.read_strided(&x, 3, 1, 3), [4., 5., 6.]);
+        assert_eq!(device.read_element(&x, 4), 5.);
+
+        device.write_element(&mut x, 1, -1.);
+        assert_eq!(x.read(), [1., -1., 3., 4., 5., 6.]);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "with a stride of 3")]
+    fn test_cl_read_strided_out_of_bounds() {
+        let device = OpenCL::<custos::Base>::new(0).unwrap();
+        let x = Buffer::from((&device, [1f32, 2., 3., 4., 5., 6.]));
+
+        device.read_strided(&x, 1, 3, 3);
+    }
+}
@@ -0,0 +1,89 @@
+use std::ops::{Deref, DerefMut};
+
+use custos::{prelude::Number, Buffer, Device, OnDropBuffer, Shape, CPU};
+
+use crate::{ClampAssign, ScaleAssign, SumSquares};
+
+pub fn slice_sum_squares<T: Number>(x: &[T]) -> T {
+    x.iter().fold(T::zero(), |acc, x| acc + *x * *x)
+}
+
+pub fn slice_scale_assign<T: Number>(x: &mut [T], factor: T) {
+    for x in x {
+        *x = *x * factor;
+    }
+}
+
+pub fn slice_clamp_assign<T: Number>(x: &mut [T], min: T, max: T) {
+    for x in x {
+        if *x < min {
+            *x = min;
+        } else if *x > max {
+            *x = max;
+        }
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> SumSquares<T, S, D> for CPU<Mods>
+where
+    T: Number,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]>,
+{
+    #[inline]
+    fn sum_squares(&self, x: &Buffer<T, D, S>) -> T {
+        slice_sum_squares(x)
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> ScaleAssign<T, S, D> for CPU<Mods>
+where
+    T: Number,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn scale_assign(&self, x: &mut Buffer<T, D, S>, factor: T) {
+        slice_scale_assign(x, factor)
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> ClampAssign<T, S, D> for CPU<Mods>
+where
+    T: Number,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn clamp_assign(&self, x: &mut Buffer<T, D, S>, min: T, max: T) {
+        slice_clamp_assign(x, min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{slice_clamp_assign, slice_scale_assign, slice_sum_squares};
+
+    #[test]
+    fn test_slice_sum_squares() {
+        assert_eq!(slice_sum_squares(&[1., -2., 3.]), 14.);
+        assert_eq!(slice_sum_squares::<f32>(&[]), 0.);
+    }
+
+    #[test]
+    fn test_slice_scale_assign() {
+        let mut x = [1., -2., 3.];
+        slice_scale_assign(&mut x, 0.5);
+        assert_eq!(x, [0.5, -1., 1.5]);
+    }
+
+    #[test]
+    fn test_slice_clamp_assign() {
+        let mut x = [1, -5, 3, 10];
+        slice_clamp_assign(&mut x, -2, 4);
+        assert_eq!(x, [1, -2, 3, 4]);
+    }
+}
@@ -0,0 +1,28 @@
+//! In-place device ops used to clip gradients, see [`clip_grad_norm`](crate::optim::clip_grad_norm).
+
+#[cfg(feature = "cpu")]
+mod cpu;
+#[cfg(feature = "cpu")]
+pub use cpu::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Shape};
+
+pub trait SumSquares<T, S: Shape = (), D: Device = Self>: Device {
+    /// Returns the sum of the squared values of `x`.
+    fn sum_squares(&self, x: &Buffer<T, D, S>) -> T;
+}
+
+pub trait ScaleAssign<T, S: Shape = (), D: Device = Self>: Device {
+    /// Multiplies every value of `x` by `factor`.
+    fn scale_assign(&self, x: &mut Buffer<T, D, S>, factor: T);
+}
+
+pub trait ClampAssign<T, S: Shape = (), D: Device = Self>: Device {
+    /// Clamps every value of `x` to `[min, max]`.
+    fn clamp_assign(&self, x: &mut Buffer<T, D, S>, min: T, max: T);
+}
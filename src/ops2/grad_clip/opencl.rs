@@ -0,0 +1,140 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::Number,
+    Buffer, CDatatype, OnDropBuffer, OpenCL,
+};
+
+use crate::{ClampAssign, ScaleAssign, SumSquares};
+
+/// The maximum number of partial sums computed by [`cl_sum_squares`].
+const PARTIAL_SUMS: usize = 256;
+
+impl<Mods: OnDropBuffer, T: CDatatype + Number> SumSquares<T> for OpenCL<Mods> {
+    fn sum_squares(&self, x: &Buffer<T, Self>) -> T {
+        if x.is_empty() {
+            return T::zero();
+        }
+
+        let mut partials = Buffer::<T, _>::new(self, x.len().min(PARTIAL_SUMS));
+        cl_sum_squares(self, x, &mut partials).unwrap();
+        partials.read().iter().fold(T::zero(), |acc, x| acc + *x)
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype> ScaleAssign<T> for OpenCL<Mods> {
+    #[inline]
+    fn scale_assign(&self, x: &mut Buffer<T, Self>, factor: T) {
+        cl_scale_assign(self, x, factor).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype> ClampAssign<T> for OpenCL<Mods> {
+    #[inline]
+    fn clamp_assign(&self, x: &mut Buffer<T, Self>, min: T, max: T) {
+        cl_clamp_assign(self, x, min, max).unwrap();
+    }
+}
+
+/// Every work item writes the sum of squares of a strided part of `x` to `partials`.
+pub fn cl_sum_squares<T: CDatatype>(
+    device: &CLDevice,
+    x: &CLPtr<T>,
+    partials: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void sum_squares(__global const {dtype}* x, __global {dtype}* partials, int len) {{
+            size_t id = get_global_id(0);
+            size_t work_items = get_global_size(0);
+
+            {dtype} acc = 0;
+            for (size_t i = id; i < len; i += work_items) {{
+                acc += x[i] * x[i];
+            }}
+            partials[id] = acc;
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [partials.len(), 0, 0],
+        None,
+        &[x, partials, &(x.len() as i32)],
+    )
+}
+
+pub fn cl_scale_assign<T: CDatatype>(
+    device: &CLDevice,
+    x: &mut CLPtr<T>,
+    factor: T,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void scale_assign(__global {dtype}* x, {dtype} factor) {{
+            size_t id = get_global_id(0);
+            x[id] *= factor;
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(&src, [x.len(), 0, 0], None, &[x, &factor])
+}
+
+pub fn cl_clamp_assign<T: CDatatype>(
+    device: &CLDevice,
+    x: &mut CLPtr<T>,
+    min: T,
+    max: T,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void clamp_assign(__global {dtype}* x, {dtype} min_value, {dtype} max_value) {{
+            size_t id = get_global_id(0);
+            if (x[id] < min_value) {{
+                x[id] = min_value;
+            }} else if (x[id] > max_value) {{
+                x[id] = max_value;
+            }}
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(&src, [x.len(), 0, 0], None, &[x, &min, &max])
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{ClampAssign, ScaleAssign, SumSquares};
+
+    #[test]
+    fn test_cl_sum_squares() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        // more values than partial sums
+        let data = (0..1000).map(|x| (x % 7) as f32 - 3.).collect::<Vec<_>>();
+        let x = Buffer::from((&device, &data));
+
+        let expected = data.iter().map(|x| x * x).sum::<f32>();
+        assert_eq!(device.sum_squares(&x), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_scale_and_clamp_assign() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut x = Buffer::from((&device, [1f32, -4., 3., 8.]));
+        device.scale_assign(&mut x, 0.5);
+        assert_eq!(x.read(), [0.5, -2., 1.5, 4.]);
+
+        device.clamp_assign(&mut x, -1., 2.);
+        assert_eq!(x.read(), [0.5, -1., 1.5, 2.]);
+        Ok(())
+    }
+}
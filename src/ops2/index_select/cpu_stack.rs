@@ -0,0 +1,178 @@
+use std::ops::{AddAssign, Deref};
+
+use custos::{
+    impl_stack, prelude::Number, AddOperation, AsNoId, Buffer, Device, Retrieve, Retriever, Shape,
+    CPU,
+};
+
+use crate::{
+    assign_or_set::{AssignOrSet, Set},
+    IndexSelectRows, ScatterAddRows,
+};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[impl_stack]
+impl<T, I, IS, OS, D, Mods> IndexSelectRows<T, I, IS, OS, D> for CPU<Mods>
+where
+    T: Copy + 'static,
+    I: Number,
+    IS: Shape,
+    OS: Shape,
+    D: Device + 'static,
+    D::Base<T, IS>: Deref<Target = [T]>,
+    D::Base<I, ()>: Deref<Target = [I]>,
+    Mods: Retrieve<Self, T, OS> + AddOperation + 'static,
+{
+    fn index_select_rows(
+        &self,
+        cols: usize,
+        x: &Buffer<T, D, IS>,
+        indices: &Buffer<I, D>,
+    ) -> Buffer<T, Self, OS> {
+        let mut out = self.retrieve(indices.len() * cols, x).unwrap();
+        self.add_op(
+            (cols.no_id(), x, indices, &mut out),
+            |(cols, x, indices, out)| {
+                slice_index_select_rows::<_, _, Set>(**cols, x, indices, out);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+#[impl_stack]
+impl<T, I, IS, OS, D, Mods> ScatterAddRows<T, I, IS, OS, D> for CPU<Mods>
+where
+    T: Copy + Default + AddAssign + 'static,
+    I: Number,
+    IS: Shape,
+    OS: Shape,
+    D: Device + 'static,
+    D::Base<T, IS>: Deref<Target = [T]>,
+    D::Base<I, ()>: Deref<Target = [I]>,
+    Mods: Retrieve<Self, T, OS> + AddOperation + 'static,
+{
+    fn scatter_add_rows(
+        &self,
+        rows: usize,
+        cols: usize,
+        x: &Buffer<T, D, IS>,
+        indices: &Buffer<I, D>,
+    ) -> Buffer<T, Self, OS> {
+        let mut out = self.retrieve(rows * cols, x).unwrap();
+        self.add_op(
+            (cols.no_id(), x, indices, &mut out),
+            |(cols, x, indices, out)| {
+                // the output may be a reused buffer
+                for val in out.iter_mut() {
+                    *val = T::default();
+                }
+                slice_scatter_add_rows(**cols, x, indices, out);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+/// Writes the rows `indices[0], indices[1], ..` of `x` to the rows `0, 1, ..` of `out`.
+///
+/// # Example
+/// ```
+/// use sliced::{slice_index_select_rows, assign_or_set::Set};
+///
+/// let x = [
+///     1, 2,
+///     3, 4,
+///     5, 6,
+/// ];
+///
+/// let mut out = [0; 4];
+/// slice_index_select_rows::<_, _, Set>(2, &x, &[1, 1], &mut out);
+///
+/// assert_eq!(out, [3, 4, 3, 4]);
+/// ```
+pub fn slice_index_select_rows<T, I, AOS>(cols: usize, x: &[T], indices: &[I], out: &mut [T])
+where
+    T: Clone,
+    I: Number,
+    AOS: AssignOrSet<T>,
+{
+    for (out_row, idx) in out.chunks_mut(cols).zip(indices) {
+        let start = idx.as_usize() * cols;
+
+        for (out, val) in out_row.iter_mut().zip(&x[start..start + cols]) {
+            AOS::assign_or_set(out, val.clone());
+        }
+    }
+}
+
+/// Adds the row `i` of `x` to the row `indices[i]` of `out`.
+/// Only the rows referenced by `indices` are touched.
+pub fn slice_scatter_add_rows<T, I>(cols: usize, x: &[T], indices: &[I], out: &mut [T])
+where
+    T: Copy + AddAssign,
+    I: Number,
+{
+    for (x_row, idx) in x.chunks(cols).zip(indices) {
+        let start = idx.as_usize() * cols;
+
+        for (out, val) in out[start..start + cols].iter_mut().zip(x_row) {
+            *out += *val;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assign_or_set::Set, slice_index_select_rows, slice_scatter_add_rows};
+
+    #[test]
+    fn test_slice_index_select_rows() {
+        #[rustfmt::skip]
+        let x = [
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+            10, 11, 12,
+        ];
+
+        let mut out = [0; 9];
+        slice_index_select_rows::<_, _, Set>(3, &x, &[3, 0, 3], &mut out);
+
+        #[rustfmt::skip]
+        let expected = [
+            10, 11, 12,
+            1, 2, 3,
+            10, 11, 12,
+        ];
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_slice_scatter_add_rows() {
+        #[rustfmt::skip]
+        let x = [
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+        ];
+
+        let mut out = [0; 12];
+        slice_scatter_add_rows(3, &x, &[3, 0, 3], &mut out);
+
+        #[rustfmt::skip]
+        let expected = [
+            4, 5, 6,
+            0, 0, 0,
+            0, 0, 0,
+            8, 10, 12,
+        ];
+        assert_eq!(out, expected);
+    }
+}
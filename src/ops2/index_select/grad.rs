@@ -0,0 +1,35 @@
+#[cfg(any(feature = "cpu", feature = "stack"))]
+mod cpu_stack;
+#[cfg(any(feature = "cpu", feature = "stack"))]
+pub use cpu_stack::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Shape};
+
+pub trait IndexSelectRowsGrad<T, I, IS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn index_select_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, D>,
+        x_grad: &mut Buffer<T, D, IS>,
+        out_grad: &Buffer<T, D, OS>,
+    );
+}
+
+pub trait ScatterAddRowsGrad<T, I, IS: Shape = (), OS: Shape = (), D: Device = Self>:
+    Device
+{
+    fn scatter_add_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, D>,
+        x_grad: &mut Buffer<T, D, IS>,
+        out_grad: &Buffer<T, D, OS>,
+    );
+}
@@ -0,0 +1,59 @@
+use std::ops::{AddAssign, Deref, DerefMut};
+
+use custos::{impl_stack, prelude::Number, Buffer, Device, OnDropBuffer, Shape, CPU};
+
+use crate::{
+    assign_or_set::Assign, slice_index_select_rows, slice_scatter_add_rows, IndexSelectRowsGrad,
+    ScatterAddRowsGrad,
+};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+#[impl_stack]
+impl<T, I, IS, OS, D, Mods: OnDropBuffer> IndexSelectRowsGrad<T, I, IS, OS, D> for CPU<Mods>
+where
+    T: Copy + AddAssign,
+    I: Number,
+    IS: Shape,
+    OS: Shape,
+    D: Device,
+    D::Base<T, IS>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, OS>: Deref<Target = [T]>,
+    D::Base<I, ()>: Deref<Target = [I]>,
+{
+    #[inline]
+    fn index_select_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, D>,
+        x_grad: &mut Buffer<T, D, IS>,
+        out_grad: &Buffer<T, D, OS>,
+    ) {
+        slice_scatter_add_rows(cols, out_grad, indices, x_grad);
+    }
+}
+
+#[impl_stack]
+impl<T, I, IS, OS, D, Mods: OnDropBuffer> ScatterAddRowsGrad<T, I, IS, OS, D> for CPU<Mods>
+where
+    T: Copy + AddAssign,
+    I: Number,
+    IS: Shape,
+    OS: Shape,
+    D: Device,
+    D::Base<T, IS>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, OS>: Deref<Target = [T]>,
+    D::Base<I, ()>: Deref<Target = [I]>,
+{
+    #[inline]
+    fn scatter_add_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, D>,
+        x_grad: &mut Buffer<T, D, IS>,
+        out_grad: &Buffer<T, D, OS>,
+    ) {
+        slice_index_select_rows::<_, _, Assign>(cols, out_grad, indices, x_grad);
+    }
+}
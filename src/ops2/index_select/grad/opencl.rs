@@ -0,0 +1,88 @@
+use std::ops::AddAssign;
+
+use custos::{
+    opencl::{CLDevice, CLPtr},
+    Buffer, CDatatype, OnDropBuffer, OpenCL,
+};
+
+use crate::{
+    assign_or_set::Assign, cl_index_select_rows, cl_scatter_add_rows, IndexSelectRowsGrad,
+    ScatterAddRowsGrad,
+};
+
+impl<Mods: OnDropBuffer, T, I> IndexSelectRowsGrad<T, I> for OpenCL<Mods>
+where
+    T: CDatatype + AddAssign,
+    I: CDatatype,
+{
+    #[inline]
+    fn index_select_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, Self>,
+        x_grad: &mut Buffer<T, Self>,
+        out_grad: &Buffer<T, Self>,
+    ) {
+        cl_index_select_rows_grad(self, cols, indices, x_grad, out_grad).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T, I> ScatterAddRowsGrad<T, I> for OpenCL<Mods>
+where
+    T: CDatatype + AddAssign,
+    I: CDatatype,
+{
+    #[inline]
+    fn scatter_add_rows_grad(
+        &self,
+        cols: usize,
+        indices: &Buffer<I, Self>,
+        x_grad: &mut Buffer<T, Self>,
+        out_grad: &Buffer<T, Self>,
+    ) {
+        cl_index_select_rows::<T, I, Assign>(self, cols, out_grad, indices, x_grad).unwrap();
+    }
+}
+
+/// Adds the row `i` of `out_grad` to the row `indices[i]` of `x_grad`.
+/// Launches one work item per (row, column) of `x_grad`, which sums up the rows of `out_grad` pointing to it.
+/// Hence, no two work items write to the same element, even for duplicated indices.
+#[inline]
+pub fn cl_index_select_rows_grad<T: CDatatype, I: CDatatype>(
+    device: &CLDevice,
+    cols: usize,
+    indices: &CLPtr<I>,
+    x_grad: &mut CLPtr<T>,
+    out_grad: &CLPtr<T>,
+) -> custos::Result<()> {
+    cl_scatter_add_rows::<T, I, Assign>(device, cols, out_grad, indices, x_grad)
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::cl_index_select_rows_grad;
+
+    #[test]
+    fn test_cl_index_select_rows_grad() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let indices = Buffer::from((&device, [3, 1, 3]));
+        let out_grad = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+        let mut x_grad = Buffer::from((&device, [1; 10]));
+
+        cl_index_select_rows_grad(&device, 2, &indices, &mut x_grad, &out_grad)?;
+
+        #[rustfmt::skip]
+        let expected = vec![
+            1, 1,
+            4, 5,
+            1, 1,
+            7, 9,
+            1, 1,
+        ];
+        assert_eq!(x_grad.read(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+mod grad;
+use custos::{Buffer, Device, Shape};
+pub use grad::*;
+
+#[cfg(any(feature = "cpu", feature = "stack"))]
+mod cpu_stack;
+#[cfg(any(feature = "cpu", feature = "stack"))]
+pub use cpu_stack::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+/// Gathers rows of a row-major [`Buffer`] by an index `Buffer` without gradients.
+pub trait IndexSelectRows<T, I, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    /// Writes the rows `indices[0], indices[1], ..` of `x` to the rows `0, 1, ..` of the output.
+    /// Rows may be selected more than once.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, IndexSelectRows, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let x = Buffer::from((&device, [
+    ///     1, 2,
+    ///     3, 4,
+    ///     5, 6,
+    /// ]));
+    /// let indices = Buffer::from((&device, [2, 0, 2]));
+    ///
+    /// let out: Buffer<i32> = device.index_select_rows(2, &x, &indices);
+    /// assert_eq!(&*out, [
+    ///     5, 6,
+    ///     1, 2,
+    ///     5, 6,
+    /// ]);
+    /// ```
+    fn index_select_rows(
+        &self,
+        cols: usize,
+        x: &Buffer<T, D, IS>,
+        indices: &Buffer<I, D>,
+    ) -> Buffer<T, Self, OS>;
+}
+
+/// Adds rows of a row-major [`Buffer`] to the rows given by an index `Buffer` without gradients.
+/// This is the adjoint of [`IndexSelectRows`].
+pub trait ScatterAddRows<T, I, IS: Shape = (), OS: Shape = (), D: Device = Self>: Device {
+    /// Adds the row `i` of `x` to the row `indices[i]` of a zero initialized output with `rows` rows.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, ScatterAddRows, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// let x = Buffer::from((&device, [
+    ///     1, 2,
+    ///     3, 4,
+    ///     5, 6,
+    /// ]));
+    /// let indices = Buffer::from((&device, [2, 0, 2]));
+    ///
+    /// let out: Buffer<i32> = device.scatter_add_rows(4, 2, &x, &indices);
+    /// assert_eq!(&*out, [
+    ///     3, 4,
+    ///     0, 0,
+    ///     6, 8,
+    ///     0, 0,
+    /// ]);
+    /// ```
+    fn scatter_add_rows(
+        &self,
+        rows: usize,
+        cols: usize,
+        x: &Buffer<T, D, IS>,
+        indices: &Buffer<I, D>,
+    ) -> Buffer<T, Self, OS>;
+}
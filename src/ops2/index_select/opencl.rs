@@ -0,0 +1,207 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::Number,
+    Buffer, CDatatype, OnDropBuffer, OpenCL, Retrieve, Retriever,
+};
+
+use crate::{
+    assign_or_set::{AssignOrSet, Set},
+    IndexSelectRows, ScatterAddRows,
+};
+
+/// Panics if an index does not point to one of the `rows` rows.
+/// The kernels do not check the bounds, hence debug builds validate the indices on the host before a launch.
+/// Reading the indices back stalls the queue, therefore release builds skip the check.
+fn assert_indices_in_bounds<Mods, I>(indices: &Buffer<I, OpenCL<Mods>>, rows: usize)
+where
+    Mods: OnDropBuffer + 'static,
+    I: Number,
+{
+    for idx in indices.read_to_vec() {
+        assert!(
+            idx.as_usize() < rows,
+            "index {} is out of bounds for {rows} rows",
+            idx.as_usize()
+        );
+    }
+}
+
+impl<Mods: Retrieve<Self, T> + 'static, T: CDatatype, I: CDatatype + Number> IndexSelectRows<T, I>
+    for OpenCL<Mods>
+{
+    #[inline]
+    fn index_select_rows(
+        &self,
+        cols: usize,
+        x: &Buffer<T, Self>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self> {
+        if cfg!(debug_assertions) && cols != 0 {
+            assert_indices_in_bounds(indices, x.len() / cols);
+        }
+
+        let mut out = self.retrieve(indices.len() * cols, x).unwrap();
+        cl_index_select_rows::<T, I, Set>(self, cols, x, indices, &mut out).unwrap();
+        out
+    }
+}
+
+impl<Mods: Retrieve<Self, T> + 'static, T: CDatatype, I: CDatatype + Number> ScatterAddRows<T, I>
+    for OpenCL<Mods>
+{
+    #[inline]
+    fn scatter_add_rows(
+        &self,
+        rows: usize,
+        cols: usize,
+        x: &Buffer<T, Self>,
+        indices: &Buffer<I, Self>,
+    ) -> Buffer<T, Self> {
+        if cfg!(debug_assertions) {
+            assert_indices_in_bounds(indices, rows);
+        }
+
+        let mut out = self.retrieve(rows * cols, x).unwrap();
+        cl_scatter_add_rows::<T, I, Set>(self, cols, x, indices, &mut out).unwrap();
+        out
+    }
+}
+
+pub fn cl_index_select_rows<T: CDatatype, I: CDatatype, AOS: AssignOrSet<T>>(
+    device: &CLDevice,
+    cols: usize,
+    x: &CLPtr<T>,
+    indices: &CLPtr<I>,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    if cols == 0 || indices.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void index_select_rows(__global const {dtype}* x, __global const {idx_dtype}* indices, __global {dtype}* out, int cols) {{
+            size_t row = get_global_id(0);
+            size_t col = get_global_id(1);
+
+            out[row * cols + col] {aos} x[(size_t) indices[row] * cols + col];
+        }}
+    ",
+        aos = AOS::STR_OP,
+        dtype = T::C_DTYPE_STR,
+        idx_dtype = I::C_DTYPE_STR,
+    );
+
+    device.launch_kernel(
+        &src,
+        [indices.len(), cols, 0],
+        None,
+        &[x, indices, out, &(cols as i32)],
+    )
+}
+
+/// Every work item sums up all rows of `x` whose index points to its output row.
+/// This avoids (non-existing) atomic float additions for duplicated indices.
+pub fn cl_scatter_add_rows<T: CDatatype, I: CDatatype, AOS: AssignOrSet<T>>(
+    device: &CLDevice,
+    cols: usize,
+    x: &CLPtr<T>,
+    indices: &CLPtr<I>,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    if cols == 0 || out.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void scatter_add_rows(__global const {dtype}* x, __global const {idx_dtype}* indices, __global {dtype}* out, int cols, int idx_len) {{
+            size_t row = get_global_id(0);
+            size_t col = get_global_id(1);
+
+            {dtype} sum = 0;
+            for (size_t i = 0; i < idx_len; i++) {{
+                if ((size_t) indices[i] == row) {{
+                    sum += x[i * cols + col];
+                }}
+            }}
+            out[row * cols + col] {aos} sum;
+        }}
+    ",
+        aos = AOS::STR_OP,
+        dtype = T::C_DTYPE_STR,
+        idx_dtype = I::C_DTYPE_STR,
+    );
+
+    device.launch_kernel(
+        &src,
+        [out.len() / cols, cols, 0],
+        None,
+        &[x, indices, out, &(cols as i32), &(indices.len() as i32)],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{
+        assign_or_set::{Assign, Set},
+        cl_index_select_rows, cl_scatter_add_rows,
+    };
+
+    #[test]
+    fn test_cl_index_select_rows() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]));
+        let indices = Buffer::from((&device, [3, 0, 3]));
+        let mut out = Buffer::<i32, _>::new(&device, 9);
+
+        cl_index_select_rows::<_, _, Set>(&device, 3, &x, &indices, &mut out)?;
+
+        #[rustfmt::skip]
+        let expected = vec![
+            10, 11, 12,
+            1, 2, 3,
+            10, 11, 12,
+        ];
+        assert_eq!(out.read(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_scatter_add_rows() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6, 7, 8, 9]));
+        let indices = Buffer::from((&device, [3, 0, 3]));
+        let mut out = Buffer::from((&device, [1; 12]));
+
+        cl_scatter_add_rows::<_, _, Assign>(&device, 3, &x, &indices, &mut out)?;
+
+        #[rustfmt::skip]
+        let expected = vec![
+            5, 6, 7,
+            1, 1, 1,
+            1, 1, 1,
+            9, 11, 13,
+        ];
+        assert_eq!(out.read(), expected);
+        Ok(())
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_cl_index_select_rows_out_of_bounds() {
+        use crate::IndexSelectRows;
+
+        let device = OpenCL::<custos::Base>::new(0).unwrap();
+
+        let x = Buffer::from((&device, [1, 2, 3, 4]));
+        let indices = Buffer::from((&device, [1, 2]));
+
+        let _out: Buffer<i32, _> = device.index_select_rows(2, &x, &indices);
+    }
+}
@@ -30,3 +30,21 @@ pub use softmax::*;
 
 mod onehot;
 pub use onehot::*;
+
+mod concat;
+pub use concat::*;
+
+mod index_select;
+pub use index_select::*;
+
+mod optim_step;
+pub use optim_step::*;
+
+mod grad_clip;
+pub use grad_clip::*;
+
+mod element_access;
+pub use element_access::*;
+
+mod strided;
+pub use strided::*;
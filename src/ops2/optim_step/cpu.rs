@@ -0,0 +1,303 @@
+use std::ops::{Deref, DerefMut};
+
+use custos::{prelude::Float, Buffer, Device, OnDropBuffer, Shape, CPU};
+
+use crate::{
+    AdagradHyper, AdagradStep, AdamHyper, AdamStep, RmsPropHyper, RmsPropStep, SgdHyper, SgdStep,
+};
+
+pub fn slice_sgd_step<T: Float>(
+    hyper: &SgdHyper<T>,
+    param: &mut [T],
+    grad: &[T],
+    velocity: &mut [T],
+) {
+    for ((param, grad), velocity) in param.iter_mut().zip(grad).zip(velocity) {
+        let mut grad = *grad + hyper.weight_decay * *param;
+
+        if hyper.momentum != T::zero() {
+            *velocity = hyper.momentum * *velocity + (T::one() - hyper.dampening) * grad;
+            grad = if hyper.nesterov {
+                grad + hyper.momentum * *velocity
+            } else {
+                *velocity
+            };
+        }
+
+        *param = *param - hyper.lr * grad;
+    }
+}
+
+pub fn slice_adam_step<T: Float>(
+    hyper: &AdamHyper<T>,
+    param: &mut [T],
+    grad: &[T],
+    m: &mut [T],
+    v: &mut [T],
+) {
+    for (((param, grad), m), v) in param.iter_mut().zip(grad).zip(m).zip(v) {
+        let mut grad = *grad;
+        if hyper.decoupled_weight_decay {
+            *param = *param - hyper.lr * hyper.weight_decay * *param;
+        } else {
+            grad = grad + hyper.weight_decay * *param;
+        }
+
+        *m = hyper.beta1 * *m + (T::one() - hyper.beta1) * grad;
+        *v = hyper.beta2 * *v + (T::one() - hyper.beta2) * grad * grad;
+
+        let m_hat = *m / hyper.bias_correction1;
+        let v_hat = *v / hyper.bias_correction2;
+
+        *param = *param - hyper.lr * m_hat / (v_hat.sqrt() + hyper.eps);
+    }
+}
+
+pub fn slice_rmsprop_step<T: Float>(
+    hyper: &RmsPropHyper<T>,
+    param: &mut [T],
+    grad: &[T],
+    square_avg: &mut [T],
+    momentum_buf: &mut [T],
+) {
+    for (((param, grad), square_avg), momentum_buf) in
+        param.iter_mut().zip(grad).zip(square_avg).zip(momentum_buf)
+    {
+        let grad = *grad + hyper.weight_decay * *param;
+        *square_avg = hyper.alpha * *square_avg + (T::one() - hyper.alpha) * grad * grad;
+
+        let update = grad / (square_avg.sqrt() + hyper.eps);
+        if hyper.momentum != T::zero() {
+            *momentum_buf = hyper.momentum * *momentum_buf + update;
+            *param = *param - hyper.lr * *momentum_buf;
+        } else {
+            *param = *param - hyper.lr * update;
+        }
+    }
+}
+
+pub fn slice_adagrad_step<T: Float>(
+    hyper: &AdagradHyper<T>,
+    param: &mut [T],
+    grad: &[T],
+    sum: &mut [T],
+) {
+    for ((param, grad), sum) in param.iter_mut().zip(grad).zip(sum) {
+        let grad = *grad + hyper.weight_decay * *param;
+        *sum = *sum + grad * grad;
+        *param = *param - hyper.lr * grad / (sum.sqrt() + hyper.eps);
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> SgdStep<T, S, D> for CPU<Mods>
+where
+    T: Float,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn sgd_step(
+        &self,
+        hyper: &SgdHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        velocity: &mut Buffer<T, D, S>,
+    ) {
+        slice_sgd_step(hyper, param, grad, velocity)
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> AdamStep<T, S, D> for CPU<Mods>
+where
+    T: Float,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn adam_step(
+        &self,
+        hyper: &AdamHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        m: &mut Buffer<T, D, S>,
+        v: &mut Buffer<T, D, S>,
+    ) {
+        slice_adam_step(hyper, param, grad, m, v)
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> RmsPropStep<T, S, D> for CPU<Mods>
+where
+    T: Float,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn rmsprop_step(
+        &self,
+        hyper: &RmsPropHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        square_avg: &mut Buffer<T, D, S>,
+        momentum_buf: &mut Buffer<T, D, S>,
+    ) {
+        slice_rmsprop_step(hyper, param, grad, square_avg, momentum_buf)
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D, S> AdagradStep<T, S, D> for CPU<Mods>
+where
+    T: Float,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn adagrad_step(
+        &self,
+        hyper: &AdagradHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        sum: &mut Buffer<T, D, S>,
+    ) {
+        slice_adagrad_step(hyper, param, grad, sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        slice_adagrad_step, slice_adam_step, slice_rmsprop_step, slice_sgd_step,
+        test_utils::roughly_equals, AdagradHyper, AdamHyper, RmsPropHyper, SgdHyper,
+    };
+
+    #[test]
+    fn test_slice_sgd_step_momentum() {
+        let hyper = SgdHyper {
+            lr: 0.1,
+            momentum: 0.5,
+            dampening: 0.,
+            weight_decay: 0.,
+            nesterov: false,
+        };
+
+        let mut param = [1., 2.];
+        let mut velocity = [0.; 2];
+
+        slice_sgd_step(&hyper, &mut param, &[1., -2.], &mut velocity);
+        assert_eq!(velocity, [1., -2.]);
+        roughly_equals(&param, &[0.9, 2.2]);
+
+        slice_sgd_step(&hyper, &mut param, &[1., -2.], &mut velocity);
+        assert_eq!(velocity, [1.5, -3.]);
+        roughly_equals(&param, &[0.75, 2.5]);
+    }
+
+    #[test]
+    fn test_slice_sgd_step_nesterov() {
+        let hyper = SgdHyper {
+            lr: 0.1,
+            momentum: 0.5,
+            dampening: 0.,
+            weight_decay: 0.,
+            nesterov: true,
+        };
+
+        let mut param = [1.];
+        let mut velocity = [0.];
+
+        slice_sgd_step(&hyper, &mut param, &[1.], &mut velocity);
+        assert_eq!(velocity, [1.]);
+        roughly_equals(&param, &[0.85]);
+    }
+
+    #[test]
+    fn test_slice_adam_step() {
+        let hyper = AdamHyper {
+            lr: 0.1,
+            beta1: 0.9,
+            beta2: 0.999,
+            eps: 0.,
+            weight_decay: 0.,
+            decoupled_weight_decay: false,
+            bias_correction1: 1. - 0.9,
+            bias_correction2: 1. - 0.999,
+        };
+
+        let mut param = [1., 1.];
+        let (mut m, mut v) = ([0.; 2], [0.; 2]);
+
+        // the first bias corrected step moves every parameter by lr * sign(grad)
+        slice_adam_step(&hyper, &mut param, &[4., -0.5], &mut m, &mut v);
+        roughly_equals(&param, &[0.9, 1.1]);
+        roughly_equals(&m, &[0.4, -0.05]);
+    }
+
+    #[test]
+    fn test_slice_adamw_decay() {
+        let hyper = AdamHyper {
+            lr: 0.1,
+            beta1: 0.9,
+            beta2: 0.999,
+            eps: 1e-8,
+            weight_decay: 0.5,
+            decoupled_weight_decay: true,
+            bias_correction1: 1. - 0.9,
+            bias_correction2: 1. - 0.999,
+        };
+
+        let mut param = [2.];
+        let (mut m, mut v) = ([0.], [0.]);
+
+        // a zero gradient only applies the decay
+        slice_adam_step(&hyper, &mut param, &[0.], &mut m, &mut v);
+        roughly_equals(&param, &[1.9]);
+    }
+
+    #[test]
+    fn test_slice_rmsprop_step() {
+        let hyper = RmsPropHyper {
+            lr: 0.01,
+            alpha: 0.75,
+            eps: 0.,
+            weight_decay: 0.,
+            momentum: 0.,
+        };
+
+        let mut param = [1.];
+        let (mut square_avg, mut momentum_buf) = ([0.], [0.]);
+
+        slice_rmsprop_step(
+            &hyper,
+            &mut param,
+            &[2.],
+            &mut square_avg,
+            &mut momentum_buf,
+        );
+        assert_eq!(square_avg, [1.]);
+        roughly_equals(&param, &[0.98]);
+    }
+
+    #[test]
+    fn test_slice_adagrad_step() {
+        let hyper = AdagradHyper {
+            lr: 0.5,
+            eps: 0.,
+            weight_decay: 0.,
+        };
+
+        let mut param = [1.];
+        let mut sum = [0.];
+
+        slice_adagrad_step(&hyper, &mut param, &[3.], &mut sum);
+        assert_eq!(sum, [9.]);
+        roughly_equals(&param, &[0.5]);
+
+        slice_adagrad_step(&hyper, &mut param, &[4.], &mut sum);
+        assert_eq!(sum, [25.]);
+        roughly_equals(&param, &[0.1]);
+    }
+}
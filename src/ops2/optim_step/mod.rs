@@ -0,0 +1,103 @@
+//! Device kernels of the optimizers in [`optim`](crate::optim).
+//! Every step updates the parameter and the optimizer state in place.
+
+#[cfg(feature = "cpu")]
+mod cpu;
+#[cfg(feature = "cpu")]
+pub use cpu::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Shape};
+
+/// Hyperparameters of one SGD step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SgdHyper<T> {
+    pub lr: T,
+    pub momentum: T,
+    pub dampening: T,
+    pub weight_decay: T,
+    pub nesterov: bool,
+}
+
+/// Hyperparameters of one Adam(W) step.
+/// The bias corrections are `1 - beta^t` for the current step `t`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdamHyper<T> {
+    pub lr: T,
+    pub beta1: T,
+    pub beta2: T,
+    pub eps: T,
+    pub weight_decay: T,
+    /// Decays the weights directly (AdamW) instead of adding the decay to the gradient.
+    pub decoupled_weight_decay: bool,
+    pub bias_correction1: T,
+    pub bias_correction2: T,
+}
+
+/// Hyperparameters of one RMSProp step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RmsPropHyper<T> {
+    pub lr: T,
+    pub alpha: T,
+    pub eps: T,
+    pub weight_decay: T,
+    pub momentum: T,
+}
+
+/// Hyperparameters of one Adagrad step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdagradHyper<T> {
+    pub lr: T,
+    pub eps: T,
+    pub weight_decay: T,
+}
+
+pub trait SgdStep<T, S: Shape = (), D: Device = Self>: Device {
+    /// `v = momentum * v + (1 - dampening) * g`, `param -= lr * v` (or `lr * (g + momentum * v)` with Nesterov).
+    fn sgd_step(
+        &self,
+        hyper: &SgdHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        velocity: &mut Buffer<T, D, S>,
+    );
+}
+
+pub trait AdamStep<T, S: Shape = (), D: Device = Self>: Device {
+    /// Updates the first (`m`) and second (`v`) moment estimates and the parameter.
+    fn adam_step(
+        &self,
+        hyper: &AdamHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        m: &mut Buffer<T, D, S>,
+        v: &mut Buffer<T, D, S>,
+    );
+}
+
+pub trait RmsPropStep<T, S: Shape = (), D: Device = Self>: Device {
+    /// Updates the running average of the squared gradients and the parameter.
+    fn rmsprop_step(
+        &self,
+        hyper: &RmsPropHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        square_avg: &mut Buffer<T, D, S>,
+        momentum_buf: &mut Buffer<T, D, S>,
+    );
+}
+
+pub trait AdagradStep<T, S: Shape = (), D: Device = Self>: Device {
+    /// Accumulates the squared gradients in `sum` and updates the parameter.
+    fn adagrad_step(
+        &self,
+        hyper: &AdagradHyper<T>,
+        param: &mut Buffer<T, D, S>,
+        grad: &Buffer<T, D, S>,
+        sum: &mut Buffer<T, D, S>,
+    );
+}
@@ -0,0 +1,375 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::Float,
+    Buffer, CDatatype, OnDropBuffer, OpenCL,
+};
+
+use crate::{
+    AdagradHyper, AdagradStep, AdamHyper, AdamStep, RmsPropHyper, RmsPropStep, SgdHyper, SgdStep,
+};
+
+impl<Mods: OnDropBuffer, T: CDatatype + Float> SgdStep<T> for OpenCL<Mods> {
+    #[inline]
+    fn sgd_step(
+        &self,
+        hyper: &SgdHyper<T>,
+        param: &mut Buffer<T, Self>,
+        grad: &Buffer<T, Self>,
+        velocity: &mut Buffer<T, Self>,
+    ) {
+        cl_sgd_step(self, hyper, param, grad, velocity).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + Float> AdamStep<T> for OpenCL<Mods> {
+    #[inline]
+    fn adam_step(
+        &self,
+        hyper: &AdamHyper<T>,
+        param: &mut Buffer<T, Self>,
+        grad: &Buffer<T, Self>,
+        m: &mut Buffer<T, Self>,
+        v: &mut Buffer<T, Self>,
+    ) {
+        cl_adam_step(self, hyper, param, grad, m, v).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + Float> RmsPropStep<T> for OpenCL<Mods> {
+    #[inline]
+    fn rmsprop_step(
+        &self,
+        hyper: &RmsPropHyper<T>,
+        param: &mut Buffer<T, Self>,
+        grad: &Buffer<T, Self>,
+        square_avg: &mut Buffer<T, Self>,
+        momentum_buf: &mut Buffer<T, Self>,
+    ) {
+        cl_rmsprop_step(self, hyper, param, grad, square_avg, momentum_buf).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + Float> AdagradStep<T> for OpenCL<Mods> {
+    #[inline]
+    fn adagrad_step(
+        &self,
+        hyper: &AdagradHyper<T>,
+        param: &mut Buffer<T, Self>,
+        grad: &Buffer<T, Self>,
+        sum: &mut Buffer<T, Self>,
+    ) {
+        cl_adagrad_step(self, hyper, param, grad, sum).unwrap();
+    }
+}
+
+pub fn cl_sgd_step<T: CDatatype>(
+    device: &CLDevice,
+    hyper: &SgdHyper<T>,
+    param: &mut CLPtr<T>,
+    grad: &CLPtr<T>,
+    velocity: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void sgd_step(__global {dtype}* param, __global const {dtype}* grad, __global {dtype}* velocity,
+            {dtype} lr, {dtype} momentum, {dtype} dampening, {dtype} weight_decay, int nesterov)
+        {{
+            size_t id = get_global_id(0);
+
+            {dtype} g = grad[id] + weight_decay * param[id];
+            if (momentum != 0) {{
+                velocity[id] = momentum * velocity[id] + (1 - dampening) * g;
+                g = nesterov ? g + momentum * velocity[id] : velocity[id];
+            }}
+            param[id] -= lr * g;
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[
+            param,
+            grad,
+            velocity,
+            &hyper.lr,
+            &hyper.momentum,
+            &hyper.dampening,
+            &hyper.weight_decay,
+            &(hyper.nesterov as i32),
+        ],
+    )
+}
+
+pub fn cl_adam_step<T: CDatatype>(
+    device: &CLDevice,
+    hyper: &AdamHyper<T>,
+    param: &mut CLPtr<T>,
+    grad: &CLPtr<T>,
+    m: &mut CLPtr<T>,
+    v: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void adam_step(__global {dtype}* param, __global const {dtype}* grad, __global {dtype}* m, __global {dtype}* v,
+            {dtype} lr, {dtype} beta1, {dtype} beta2, {dtype} eps, {dtype} weight_decay, int decoupled,
+            {dtype} bias_correction1, {dtype} bias_correction2)
+        {{
+            size_t id = get_global_id(0);
+
+            {dtype} g = grad[id];
+            if (decoupled) {{
+                param[id] -= lr * weight_decay * param[id];
+            }} else {{
+                g += weight_decay * param[id];
+            }}
+
+            m[id] = beta1 * m[id] + (1 - beta1) * g;
+            v[id] = beta2 * v[id] + (1 - beta2) * g * g;
+
+            {dtype} m_hat = m[id] / bias_correction1;
+            {dtype} v_hat = v[id] / bias_correction2;
+
+            param[id] -= lr * m_hat / (sqrt(v_hat) + eps);
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[
+            param,
+            grad,
+            m,
+            v,
+            &hyper.lr,
+            &hyper.beta1,
+            &hyper.beta2,
+            &hyper.eps,
+            &hyper.weight_decay,
+            &(hyper.decoupled_weight_decay as i32),
+            &hyper.bias_correction1,
+            &hyper.bias_correction2,
+        ],
+    )
+}
+
+pub fn cl_rmsprop_step<T: CDatatype>(
+    device: &CLDevice,
+    hyper: &RmsPropHyper<T>,
+    param: &mut CLPtr<T>,
+    grad: &CLPtr<T>,
+    square_avg: &mut CLPtr<T>,
+    momentum_buf: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void rmsprop_step(__global {dtype}* param, __global const {dtype}* grad, __global {dtype}* square_avg,
+            __global {dtype}* momentum_buf, {dtype} lr, {dtype} alpha, {dtype} eps, {dtype} weight_decay, {dtype} momentum)
+        {{
+            size_t id = get_global_id(0);
+
+            {dtype} g = grad[id] + weight_decay * param[id];
+            square_avg[id] = alpha * square_avg[id] + (1 - alpha) * g * g;
+
+            {dtype} update = g / (sqrt(square_avg[id]) + eps);
+            if (momentum != 0) {{
+                momentum_buf[id] = momentum * momentum_buf[id] + update;
+                param[id] -= lr * momentum_buf[id];
+            }} else {{
+                param[id] -= lr * update;
+            }}
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[
+            param,
+            grad,
+            square_avg,
+            momentum_buf,
+            &hyper.lr,
+            &hyper.alpha,
+            &hyper.eps,
+            &hyper.weight_decay,
+            &hyper.momentum,
+        ],
+    )
+}
+
+pub fn cl_adagrad_step<T: CDatatype>(
+    device: &CLDevice,
+    hyper: &AdagradHyper<T>,
+    param: &mut CLPtr<T>,
+    grad: &CLPtr<T>,
+    sum: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let src = format!(
+        "
+        __kernel void adagrad_step(__global {dtype}* param, __global const {dtype}* grad, __global {dtype}* sum,
+            {dtype} lr, {dtype} eps, {dtype} weight_decay)
+        {{
+            size_t id = get_global_id(0);
+
+            {dtype} g = grad[id] + weight_decay * param[id];
+            sum[id] += g * g;
+            param[id] -= lr * g / (sqrt(sum[id]) + eps);
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    device.launch_kernel(
+        &src,
+        [param.len(), 0, 0],
+        None,
+        &[param, grad, sum, &hyper.lr, &hyper.eps, &hyper.weight_decay],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{
+        cl_adagrad_step, cl_adam_step, cl_rmsprop_step, cl_sgd_step, slice_adagrad_step,
+        slice_adam_step, slice_rmsprop_step, slice_sgd_step, test_utils::roughly_equals,
+        AdagradHyper, AdamHyper, RmsPropHyper, SgdHyper,
+    };
+
+    const PARAM: [f32; 4] = [1., -2., 0.5, 3.];
+    const GRAD: [f32; 4] = [0.3, -1., 2., -0.1];
+
+    #[test]
+    fn test_cl_sgd_step() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+        let hyper = SgdHyper {
+            lr: 0.1,
+            momentum: 0.9,
+            dampening: 0.,
+            weight_decay: 0.01,
+            nesterov: true,
+        };
+
+        let mut param = Buffer::from((&device, PARAM));
+        let grad = Buffer::from((&device, GRAD));
+        let mut velocity = Buffer::from((&device, [0.1; 4]));
+        cl_sgd_step(&device, &hyper, &mut param, &grad, &mut velocity)?;
+
+        let (mut expected, mut expected_velocity) = (PARAM, [0.1; 4]);
+        slice_sgd_step(&hyper, &mut expected, &GRAD, &mut expected_velocity);
+
+        roughly_equals(&param.read(), &expected);
+        roughly_equals(&velocity.read(), &expected_velocity);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_adam_step() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+        let hyper = AdamHyper {
+            lr: 0.1,
+            beta1: 0.9,
+            beta2: 0.999,
+            eps: 1e-8,
+            weight_decay: 0.01,
+            decoupled_weight_decay: true,
+            bias_correction1: 0.19,
+            bias_correction2: 0.002,
+        };
+
+        let mut param = Buffer::from((&device, PARAM));
+        let grad = Buffer::from((&device, GRAD));
+        let mut m = Buffer::from((&device, [0.1; 4]));
+        let mut v = Buffer::from((&device, [0.01; 4]));
+        cl_adam_step(&device, &hyper, &mut param, &grad, &mut m, &mut v)?;
+
+        let mut expected = PARAM;
+        let (mut expected_m, mut expected_v) = ([0.1; 4], [0.01; 4]);
+        slice_adam_step(
+            &hyper,
+            &mut expected,
+            &GRAD,
+            &mut expected_m,
+            &mut expected_v,
+        );
+
+        roughly_equals(&param.read(), &expected);
+        roughly_equals(&m.read(), &expected_m);
+        roughly_equals(&v.read(), &expected_v);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_rmsprop_step() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+        let hyper = RmsPropHyper {
+            lr: 0.01,
+            alpha: 0.99,
+            eps: 1e-8,
+            weight_decay: 0.,
+            momentum: 0.5,
+        };
+
+        let mut param = Buffer::from((&device, PARAM));
+        let grad = Buffer::from((&device, GRAD));
+        let mut square_avg = Buffer::from((&device, [0.2; 4]));
+        let mut momentum_buf = Buffer::from((&device, [0.1; 4]));
+        cl_rmsprop_step(
+            &device,
+            &hyper,
+            &mut param,
+            &grad,
+            &mut square_avg,
+            &mut momentum_buf,
+        )?;
+
+        let mut expected = PARAM;
+        let (mut expected_square_avg, mut expected_momentum_buf) = ([0.2; 4], [0.1; 4]);
+        slice_rmsprop_step(
+            &hyper,
+            &mut expected,
+            &GRAD,
+            &mut expected_square_avg,
+            &mut expected_momentum_buf,
+        );
+
+        roughly_equals(&param.read(), &expected);
+        roughly_equals(&square_avg.read(), &expected_square_avg);
+        roughly_equals(&momentum_buf.read(), &expected_momentum_buf);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_adagrad_step() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+        let hyper = AdagradHyper {
+            lr: 0.5,
+            eps: 1e-10,
+            weight_decay: 0.1,
+        };
+
+        let mut param = Buffer::from((&device, PARAM));
+        let grad = Buffer::from((&device, GRAD));
+        let mut sum = Buffer::from((&device, [1.; 4]));
+        cl_adagrad_step(&device, &hyper, &mut param, &grad, &mut sum)?;
+
+        let (mut expected, mut expected_sum) = (PARAM, [1.; 4]);
+        slice_adagrad_step(&hyper, &mut expected, &GRAD, &mut expected_sum);
+
+        roughly_equals(&param.read(), &expected);
+        roughly_equals(&sum.read(), &expected_sum);
+        Ok(())
+    }
+}
@@ -0,0 +1,461 @@
+use std::ops::{Deref, DerefMut};
+
+use custos::{
+    prelude::Number, AddOperation, AsNoId, Buffer, Combiner, Device, Eval, MayToCLSource,
+    OnDropBuffer, Resolve, Retrieve, Retriever, ToVal, CPU,
+};
+
+use crate::{
+    AddStridedAssign, BinaryStrided, CopyStrided, GemmStrided, GemmStridedGrad, Layout,
+    MulStridedGrad,
+};
+
+#[cfg(feature = "blas")]
+use custos::{GenericBlas, Order, Transpose};
+
+pub fn slice_copy_strided<T: Copy>(x: &[T], layout: Layout, out: &mut [T]) {
+    for row in 0..layout.rows {
+        for col in 0..layout.cols {
+            out[row * layout.cols + col] = x[layout.index(row, col)];
+        }
+    }
+}
+
+pub fn slice_add_strided_assign<T: Number>(dst: &mut [T], layout: Layout, src: &[T], alpha: T) {
+    for row in 0..layout.rows {
+        for col in 0..layout.cols {
+            let idx = layout.index(row, col);
+            dst[idx] = dst[idx] + alpha * src[row * layout.cols + col];
+        }
+    }
+}
+
+/// Computes `out = lhs * rhs` for a m x k window `lhs_layout` and a k x n window `rhs_layout`.
+/// Windows with contiguous rows or columns are passed to BLAS with their leading dimension.
+#[cfg(feature = "blas")]
+pub fn slice_gemm_strided<T: GenericBlas + Number>(
+    lhs: &[T],
+    lhs_layout: Layout,
+    rhs: &[T],
+    rhs_layout: Layout,
+    out: &mut [T],
+) {
+    let (m, k, n) = (lhs_layout.rows, lhs_layout.cols, rhs_layout.cols);
+    assert_eq!(k, rhs_layout.rows, "Inner dimensions of the gemm differ");
+
+    match (blas_layout(lhs_layout), blas_layout(rhs_layout)) {
+        (Some((trans_a, lda)), Some((trans_b, ldb))) => T::blas_gemm(
+            Order::RowMajor,
+            trans_a,
+            trans_b,
+            m,
+            n,
+            k,
+            &lhs[lhs_layout.offset..],
+            lda,
+            &rhs[rhs_layout.offset..],
+            ldb,
+            out,
+            n.max(1),
+        ),
+        _ => naive_gemm_strided(lhs, lhs_layout, rhs, rhs_layout, out),
+    }
+}
+
+/// Computes `out = lhs * rhs` for a m x k window `lhs_layout` and a k x n window `rhs_layout`.
+#[cfg(not(feature = "blas"))]
+pub fn slice_gemm_strided<T: Number>(
+    lhs: &[T],
+    lhs_layout: Layout,
+    rhs: &[T],
+    rhs_layout: Layout,
+    out: &mut [T],
+) {
+    assert_eq!(
+        lhs_layout.cols, rhs_layout.rows,
+        "Inner dimensions of the gemm differ"
+    );
+    naive_gemm_strided(lhs, lhs_layout, rhs, rhs_layout, out)
+}
+
+/// Returns the BLAS transpose flag and leading dimension of a window of a row-major buffer.
+/// `None` if neither the rows nor the columns of the window are contiguous (or the rows or columns overlap).
+#[cfg(feature = "blas")]
+fn blas_layout(layout: Layout) -> Option<(Transpose, usize)> {
+    if layout.col_stride == 1 || layout.cols <= 1 {
+        if layout.rows <= 1 {
+            return Some((Transpose::NoTrans, layout.cols.max(1)));
+        }
+        (layout.row_stride >= layout.cols.max(1)).then_some((Transpose::NoTrans, layout.row_stride))
+    } else if layout.row_stride == 1 || layout.rows <= 1 {
+        if layout.cols <= 1 {
+            return Some((Transpose::Trans, layout.rows.max(1)));
+        }
+        (layout.col_stride >= layout.rows.max(1)).then_some((Transpose::Trans, layout.col_stride))
+    } else {
+        None
+    }
+}
+
+/// The fallback for windows with arbitrary strides.
+fn naive_gemm_strided<T: Number>(
+    lhs: &[T],
+    lhs_layout: Layout,
+    rhs: &[T],
+    rhs_layout: Layout,
+    out: &mut [T],
+) {
+    let (m, k, n) = (lhs_layout.rows, lhs_layout.cols, rhs_layout.cols);
+
+    for row in 0..m {
+        let out_row = &mut out[row * n..(row + 1) * n];
+        out_row.fill(T::zero());
+
+        for inner in 0..k {
+            let lhs_val = lhs[lhs_layout.index(row, inner)];
+            for (col, out) in out_row.iter_mut().enumerate() {
+                *out = *out + lhs_val * rhs[rhs_layout.index(inner, col)];
+            }
+        }
+    }
+}
+
+pub fn slice_binary_strided<T, O>(
+    lhs: &[T],
+    lhs_layout: Layout,
+    rhs: &[T],
+    rhs_layout: Layout,
+    out: &mut [T],
+    f: impl Fn(Resolve<T>, Resolve<T>) -> O,
+) where
+    T: Copy,
+    O: Eval<T> + MayToCLSource,
+{
+    for row in 0..lhs_layout.rows {
+        for col in 0..lhs_layout.cols {
+            let lhs = lhs[lhs_layout.index(row, col)];
+            let rhs = rhs[rhs_layout.index(row, col)];
+            out[row * lhs_layout.cols + col] = f(lhs.to_val(), rhs.to_val()).eval();
+        }
+    }
+}
+
+impl<Mods, T, D> CopyStrided<T, D> for CPU<Mods>
+where
+    T: Copy + 'static,
+    D: Device + 'static,
+    D::Base<T, ()>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn copy_strided(&self, x: &Buffer<T, D>, layout: Layout) -> Buffer<T, Self> {
+        let mut out = self.retrieve(layout.len(), x).unwrap();
+        self.add_op((x, &mut out, layout.no_id()), |(x, out, layout)| {
+            slice_copy_strided(x, **layout, out);
+            Ok(())
+        })
+        .unwrap();
+        out
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D> AddStridedAssign<T, D> for CPU<Mods>
+where
+    T: Number,
+    D: Device,
+    D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn add_strided_assign(
+        &self,
+        dst: &mut Buffer<T, D>,
+        layout: Layout,
+        src: &Buffer<T, D>,
+        alpha: T,
+    ) {
+        slice_add_strided_assign(dst, layout, src, alpha)
+    }
+}
+
+#[cfg(feature = "blas")]
+impl<Mods, T, D> GemmStrided<T, D> for CPU<Mods>
+where
+    T: GenericBlas + Number,
+    D: Device + 'static,
+    D::Base<T, ()>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn gemm_strided(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, Self> {
+        let mut out = self
+            .retrieve(lhs_layout.rows * rhs_layout.cols, (lhs, rhs))
+            .unwrap();
+        self.add_op(
+            (lhs, rhs, &mut out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                slice_gemm_strided(lhs, **lhs_layout, rhs, **rhs_layout, out);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+#[cfg(not(feature = "blas"))]
+impl<Mods, T, D> GemmStrided<T, D> for CPU<Mods>
+where
+    T: Number,
+    D: Device + 'static,
+    D::Base<T, ()>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn gemm_strided(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, Self> {
+        let mut out = self
+            .retrieve(lhs_layout.rows * rhs_layout.cols, (lhs, rhs))
+            .unwrap();
+        self.add_op(
+            (lhs, rhs, &mut out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                slice_gemm_strided(lhs, **lhs_layout, rhs, **rhs_layout, out);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+#[cfg(feature = "blas")]
+impl<Mods: OnDropBuffer, T, D> GemmStridedGrad<T, D> for CPU<Mods>
+where
+    T: GenericBlas + Number,
+    D: Device,
+    D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
+{
+    fn add_gemm_strided(
+        &self,
+        dst: &mut Buffer<T, D>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) {
+        assert_eq!(
+            (dst_layout.rows, dst_layout.cols),
+            (lhs_layout.rows, rhs_layout.cols),
+            "The window of dst must have the dimensions of the product"
+        );
+
+        let mut product = vec![T::zero(); dst_layout.len()];
+        slice_gemm_strided(lhs, lhs_layout, rhs, rhs_layout, &mut product);
+        slice_add_strided_assign(dst, dst_layout, &product, T::one());
+    }
+}
+
+#[cfg(not(feature = "blas"))]
+impl<Mods: OnDropBuffer, T, D> GemmStridedGrad<T, D> for CPU<Mods>
+where
+    T: Number,
+    D: Device,
+    D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
+{
+    fn add_gemm_strided(
+        &self,
+        dst: &mut Buffer<T, D>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) {
+        assert_eq!(
+            (dst_layout.rows, dst_layout.cols),
+            (lhs_layout.rows, rhs_layout.cols),
+            "The window of dst must have the dimensions of the product"
+        );
+
+        let mut product = vec![T::zero(); dst_layout.len()];
+        slice_gemm_strided(lhs, lhs_layout, rhs, rhs_layout, &mut product);
+        slice_add_strided_assign(dst, dst_layout, &product, T::one());
+    }
+}
+
+impl<Mods, T, D> BinaryStrided<T, D> for CPU<Mods>
+where
+    T: Copy + 'static,
+    D: Device + 'static,
+    D::Base<T, ()>: Deref<Target = [T]>,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn binary_strided<O>(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+        f: impl Fn(Resolve<T>, Resolve<T>) -> O + Copy + 'static,
+    ) -> Buffer<T, Self>
+    where
+        O: Eval<T> + MayToCLSource,
+    {
+        assert_eq!(
+            (lhs_layout.rows, lhs_layout.cols),
+            (rhs_layout.rows, rhs_layout.cols),
+            "The windows must have the same dimensions"
+        );
+
+        let mut out = self.retrieve(lhs_layout.len(), (lhs, rhs)).unwrap();
+        self.add_op(
+            (
+                lhs,
+                rhs,
+                &mut out,
+                lhs_layout.no_id(),
+                rhs_layout.no_id(),
+                f.no_id(),
+            ),
+            |(lhs, rhs, out, lhs_layout, rhs_layout, f)| {
+                slice_binary_strided(lhs, **lhs_layout, rhs, **rhs_layout, out, **f);
+                Ok(())
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+impl<Mods: OnDropBuffer, T, D> MulStridedGrad<T, D> for CPU<Mods>
+where
+    T: Number,
+    D: Device,
+    D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
+{
+    fn add_mul_strided(
+        &self,
+        dst: &mut Buffer<T, D>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) {
+        assert!(
+            (dst_layout.rows, dst_layout.cols) == (lhs_layout.rows, lhs_layout.cols)
+                && (lhs_layout.rows, lhs_layout.cols) == (rhs_layout.rows, rhs_layout.cols),
+            "The windows must have the same dimensions"
+        );
+
+        let mut product = vec![T::zero(); dst_layout.len()];
+        slice_binary_strided(
+            lhs,
+            lhs_layout,
+            rhs,
+            rhs_layout,
+            &mut product,
+            |lhs, rhs| lhs.mul(rhs),
+        );
+        slice_add_strided_assign(dst, dst_layout, &product, T::one());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::Combiner;
+
+    use crate::{
+        slice_add_strided_assign, slice_binary_strided, slice_copy_strided, slice_gemm_strided,
+        Layout,
+    };
+
+    #[rustfmt::skip]
+    const X: [f32; 12] = [
+        1., 2., 3.,
+        4., 5., 6.,
+        7., 8., 9.,
+        10., 11., 12.,
+    ];
+
+    #[test]
+    fn test_slice_copy_strided() {
+        let mut out = [0.; 4];
+        slice_copy_strided(
+            &X,
+            Layout::contiguous(4, 3).slice_cols(1, 3).slice_rows(1, 3),
+            &mut out,
+        );
+        assert_eq!(out, [5., 6., 8., 9.]);
+
+        let mut out = [0.; 6];
+        slice_copy_strided(&X, Layout::contiguous(4, 3).slice_rows(0, 2).t(), &mut out);
+        assert_eq!(out, [1., 4., 2., 5., 3., 6.]);
+    }
+
+    #[test]
+    fn test_slice_add_strided_assign() {
+        let mut x = X;
+        let layout = Layout::contiguous(4, 3).slice_cols(2, 3);
+        slice_add_strided_assign(&mut x, layout, &[1., 1., 1., 1.], -2.);
+        assert_eq!([x[2], x[5], x[8], x[11]], [1., 4., 7., 10.]);
+        assert_eq!(x[0], 1.);
+    }
+
+    #[test]
+    fn test_slice_gemm_strided() {
+        // (rows 2..4) * (cols 0..2)
+        let lhs = Layout::contiguous(4, 3).slice_rows(2, 4);
+        let rhs = Layout::contiguous(4, 3).slice_rows(0, 3).slice_cols(0, 2);
+
+        let mut out = [0.; 4];
+        slice_gemm_strided(&X, lhs, &X, rhs, &mut out);
+        assert_eq!(out, [102., 126., 138., 171.]);
+
+        // (rows 0..2)^T * (rows 0..2), the transposed window has a leading dimension of 3
+        let rows = Layout::contiguous(4, 3).slice_rows(0, 2);
+        let mut out = [0.; 9];
+        slice_gemm_strided(&X, rows.t(), &X, rows, &mut out);
+        assert_eq!(out, [17., 22., 27., 22., 29., 36., 27., 36., 45.]);
+
+        // every second row and column, neither the rows nor the columns are contiguous
+        let every_second = Layout {
+            offset: 0,
+            rows: 2,
+            cols: 2,
+            row_stride: 6,
+            col_stride: 2,
+        };
+        let mut out = [0.; 4];
+        slice_gemm_strided(&X, every_second, &X, every_second, &mut out);
+        assert_eq!(out, [22., 30., 70., 102.]);
+    }
+
+    #[test]
+    fn test_slice_binary_strided() {
+        let layout = Layout::contiguous(4, 3);
+        let mut out = [0.; 4];
+        slice_binary_strided(
+            &X,
+            layout.slice_rows(0, 2).slice_cols(0, 2),
+            &X,
+            layout.slice_rows(2, 4).slice_cols(1, 3),
+            &mut out,
+            |a, b| a.mul(b),
+        );
+        assert_eq!(out, [8., 18., 44., 60.]);
+    }
+}
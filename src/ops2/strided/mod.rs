@@ -0,0 +1,264 @@
+//! Ops that read (or write) a strided 2D window of a buffer, used by [`MatrixView`](crate::MatrixView).
+
+#[cfg(feature = "cpu")]
+mod cpu;
+#[cfg(feature = "cpu")]
+pub use cpu::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "opencl")]
+pub use opencl::*;
+
+use custos::{Buffer, Device, Eval, MayToCLSource, Resolve};
+
+/// Describes where the elements of a `rows` x `cols` window are placed in a buffer.
+/// The element at (`row`, `col`) is at `offset + row * row_stride + col * col_stride`.
+///
+/// For a row-major matrix, `row_stride` is the leading dimension (e.g. `lda` in BLAS).
+/// Swapping the strides transposes the window without moving any data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub offset: usize,
+    pub rows: usize,
+    pub cols: usize,
+    pub row_stride: usize,
+    pub col_stride: usize,
+}
+
+impl Layout {
+    /// The layout of a contiguous row-major `rows` x `cols` matrix.
+    #[inline]
+    pub fn contiguous(rows: usize, cols: usize) -> Self {
+        Layout {
+            offset: 0,
+            rows,
+            cols,
+            row_stride: cols,
+            col_stride: 1,
+        }
+    }
+
+    /// Returns the number of elements in the window.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the buffer index of the element at (`row`, `col`).
+    #[inline]
+    pub fn index(&self, row: usize, col: usize) -> usize {
+        self.offset + row * self.row_stride + col * self.col_stride
+    }
+
+    /// Returns `true` if the window is a contiguous row-major block.
+    #[inline]
+    pub fn is_contiguous(&self) -> bool {
+        (self.col_stride == 1 || self.cols <= 1) && (self.row_stride == self.cols || self.rows <= 1)
+    }
+
+    /// Returns `true` if several elements of the window may share a buffer index, e.g. with a zero stride.
+    /// Interleaved rows and columns are treated as overlapping as well.
+    pub fn overlaps(&self) -> bool {
+        match (self.rows > 1, self.cols > 1) {
+            (false, false) => false,
+            (true, false) => self.row_stride == 0,
+            (false, true) => self.col_stride == 0,
+            (true, true) => {
+                let (inner_stride, inner_len, outer_stride) = if self.col_stride <= self.row_stride
+                {
+                    (self.col_stride, self.cols, self.row_stride)
+                } else {
+                    (self.row_stride, self.rows, self.col_stride)
+                };
+                inner_stride == 0 || outer_stride < inner_stride * inner_len
+            }
+        }
+    }
+
+    /// Returns the transposed layout.
+    #[inline]
+    pub fn t(self) -> Self {
+        Layout {
+            offset: self.offset,
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// Returns the layout of the rows `start..end`.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn slice_rows(self, start: usize, end: usize) -> Self {
+        assert!(
+            start <= end && end <= self.rows,
+            "Rows {start}..{end} are out of bounds ({})",
+            self.rows
+        );
+        Layout {
+            offset: self.offset + start * self.row_stride,
+            rows: end - start,
+            ..self
+        }
+    }
+
+    /// Returns the layout of the columns `start..end`.
+    ///
+    /// # Panics
+    /// If the range is out of bounds.
+    #[inline]
+    pub fn slice_cols(self, start: usize, end: usize) -> Self {
+        assert!(
+            start <= end && end <= self.cols,
+            "Columns {start}..{end} are out of bounds ({})",
+            self.cols
+        );
+        Layout {
+            offset: self.offset + start * self.col_stride,
+            cols: end - start,
+            ..self
+        }
+    }
+}
+
+pub trait CopyStrided<T, D: Device = Self>: Device {
+    /// Copies the window `layout` of `x` to a new contiguous buffer.
+    fn copy_strided(&self, x: &Buffer<T, D>, layout: Layout) -> Buffer<T, Self>;
+}
+
+pub trait AddStridedAssign<T, D: Device = Self>: Device {
+    /// Adds `alpha * src` to the window `layout` of `dst`. `src` is contiguous.
+    fn add_strided_assign(
+        &self,
+        dst: &mut Buffer<T, D>,
+        layout: Layout,
+        src: &Buffer<T, D>,
+        alpha: T,
+    );
+}
+
+pub trait GemmStrided<T, D: Device = Self>: Device {
+    /// Multiplies the windows `lhs_layout` (m x k) and `rhs_layout` (k x n) into a contiguous m x n buffer.
+    /// # Example
+    #[cfg_attr(feature = "cpu", doc = "```")]
+    #[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+    /// use sliced::{Buffer, GemmStrided, Layout, CPU};
+    ///
+    /// let device = CPU::<custos::Base>::new();
+    ///
+    /// // 2 x 3
+    /// let x = Buffer::from((&device, [1., 2., 3., 4., 5., 6.]));
+    ///
+    /// // x * x^T without transposing the data
+    /// let layout = Layout::contiguous(2, 3);
+    /// let out = device.gemm_strided(&x, layout, &x, layout.t());
+    /// assert_eq!(out.read(), [14., 32., 32., 77.]);
+    /// ```
+    fn gemm_strided(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, Self>;
+}
+
+pub trait BinaryStrided<T: Copy + 'static, D: Device = Self>: Device {
+    /// Applies `f` to the windows `lhs_layout` and `rhs_layout`, which have the same dimensions.
+    /// The result is contiguous.
+    fn binary_strided<O>(
+        &self,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+        f: impl Fn(Resolve<T>, Resolve<T>) -> O + Copy + 'static,
+    ) -> Buffer<T, Self>
+    where
+        O: Eval<T> + MayToCLSource;
+}
+
+/// Used by the gradient of [`GemmStrided::gemm_strided`], computes into a temporary buffer.
+pub trait GemmStridedGrad<T, D: Device = Self>: Device {
+    /// Adds `lhs * rhs` of the windows `lhs_layout` (m x k) and `rhs_layout` (k x n) to the m x n window `dst_layout` of `dst`.
+    fn add_gemm_strided(
+        &self,
+        dst: &mut Buffer<T, D>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    );
+}
+
+/// Used by the gradient of the element-wise multiplication of windows, computes into a temporary buffer.
+pub trait MulStridedGrad<T, D: Device = Self>: Device {
+    /// Adds the element-wise product of the windows `lhs_layout` and `rhs_layout` to the window `dst_layout` of `dst`.
+    fn add_mul_strided(
+        &self,
+        dst: &mut Buffer<T, D>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, D>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, D>,
+        rhs_layout: Layout,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layout;
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::contiguous(4, 3);
+        assert!(layout.is_contiguous());
+        assert_eq!(layout.index(2, 1), 7);
+
+        let rows = layout.slice_rows(1, 3);
+        assert!(rows.is_contiguous());
+        assert_eq!(rows.index(0, 0), 3);
+
+        let cols = layout.slice_cols(1, 3);
+        assert!(!cols.is_contiguous());
+        assert_eq!((cols.rows, cols.cols), (4, 2));
+        assert_eq!(cols.index(1, 1), 5);
+
+        let t = cols.t();
+        assert_eq!((t.rows, t.cols), (2, 4));
+        assert_eq!(t.index(1, 3), cols.index(3, 1));
+    }
+
+    #[test]
+    fn test_layout_overlaps() {
+        let layout = Layout::contiguous(4, 3);
+        assert!(!layout.overlaps());
+        assert!(!layout.t().overlaps());
+        assert!(!layout.slice_cols(1, 3).overlaps());
+
+        // broadcasts a row
+        let broadcast = Layout {
+            row_stride: 0,
+            ..layout
+        };
+        assert!(broadcast.overlaps());
+        assert!(!broadcast.slice_rows(0, 1).overlaps());
+
+        // the rows share two elements
+        let sliding = Layout {
+            row_stride: 1,
+            ..layout
+        };
+        assert!(sliding.overlaps());
+    }
+}
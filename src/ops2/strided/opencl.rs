@@ -0,0 +1,443 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::Number,
+    AddOperation, AsNoId, Buffer, CDatatype, Combiner, Eval, MayToCLSource, OnDropBuffer, OpenCL,
+    Resolve, Retrieve, Retriever, ToMarker,
+};
+
+use crate::{
+    AddStridedAssign, BinaryStrided, CopyStrided, GemmStrided, GemmStridedGrad, Layout,
+    MulStridedGrad,
+};
+
+impl<Mods, T> CopyStrided<T> for OpenCL<Mods>
+where
+    T: CDatatype,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn copy_strided(&self, x: &Buffer<T, Self>, layout: Layout) -> Buffer<T, Self> {
+        let mut out = self.retrieve(layout.len(), x).unwrap();
+        self.add_op((x, &mut out, layout.no_id()), |(x, out, layout)| {
+            cl_copy_strided(x.device(), x, **layout, out)
+        })
+        .unwrap();
+        out
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype> AddStridedAssign<T> for OpenCL<Mods> {
+    #[inline]
+    fn add_strided_assign(
+        &self,
+        dst: &mut Buffer<T, Self>,
+        layout: Layout,
+        src: &Buffer<T, Self>,
+        alpha: T,
+    ) {
+        cl_add_strided_assign(self, dst, layout, src, alpha).unwrap();
+    }
+}
+
+impl<Mods, T> GemmStrided<T> for OpenCL<Mods>
+where
+    T: CDatatype,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn gemm_strided(
+        &self,
+        lhs: &Buffer<T, Self>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, Self>,
+        rhs_layout: Layout,
+    ) -> Buffer<T, Self> {
+        let mut out = self
+            .retrieve(lhs_layout.rows * rhs_layout.cols, (lhs, rhs))
+            .unwrap();
+        self.add_op(
+            (lhs, rhs, &mut out, lhs_layout.no_id(), rhs_layout.no_id()),
+            |(lhs, rhs, out, lhs_layout, rhs_layout)| {
+                cl_gemm_strided(lhs.device(), lhs, **lhs_layout, rhs, **rhs_layout, out)
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+impl<Mods, T> BinaryStrided<T> for OpenCL<Mods>
+where
+    T: CDatatype,
+    Mods: Retrieve<Self, T> + AddOperation + 'static,
+{
+    #[inline]
+    fn binary_strided<O>(
+        &self,
+        lhs: &Buffer<T, Self>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, Self>,
+        rhs_layout: Layout,
+        f: impl Fn(Resolve<T>, Resolve<T>) -> O + Copy + 'static,
+    ) -> Buffer<T, Self>
+    where
+        O: Eval<T> + MayToCLSource,
+    {
+        assert_eq!(
+            (lhs_layout.rows, lhs_layout.cols),
+            (rhs_layout.rows, rhs_layout.cols),
+            "The windows must have the same dimensions"
+        );
+
+        let mut out = self.retrieve(lhs_layout.len(), (lhs, rhs)).unwrap();
+        self.add_op(
+            (
+                lhs,
+                rhs,
+                &mut out,
+                lhs_layout.no_id(),
+                rhs_layout.no_id(),
+                f.no_id(),
+            ),
+            |(lhs, rhs, out, lhs_layout, rhs_layout, f)| {
+                cl_binary_strided(lhs.device(), lhs, **lhs_layout, rhs, **rhs_layout, out, **f)
+            },
+        )
+        .unwrap();
+        out
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + Number> GemmStridedGrad<T> for OpenCL<Mods> {
+    fn add_gemm_strided(
+        &self,
+        dst: &mut Buffer<T, Self>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, Self>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, Self>,
+        rhs_layout: Layout,
+    ) {
+        assert_eq!(
+            (dst_layout.rows, dst_layout.cols),
+            (lhs_layout.rows, rhs_layout.cols),
+            "The window of dst must have the dimensions of the product"
+        );
+        if dst_layout.is_empty() {
+            return;
+        }
+
+        let mut product = Buffer::<T, _>::new(self, dst_layout.len());
+        cl_gemm_strided(self, lhs, lhs_layout, rhs, rhs_layout, &mut product).unwrap();
+        cl_add_strided_assign(self, dst, dst_layout, &product, T::one()).unwrap();
+    }
+}
+
+impl<Mods: OnDropBuffer, T: CDatatype + Number> MulStridedGrad<T> for OpenCL<Mods> {
+    fn add_mul_strided(
+        &self,
+        dst: &mut Buffer<T, Self>,
+        dst_layout: Layout,
+        lhs: &Buffer<T, Self>,
+        lhs_layout: Layout,
+        rhs: &Buffer<T, Self>,
+        rhs_layout: Layout,
+    ) {
+        assert!(
+            (dst_layout.rows, dst_layout.cols) == (lhs_layout.rows, lhs_layout.cols)
+                && (lhs_layout.rows, lhs_layout.cols) == (rhs_layout.rows, rhs_layout.cols),
+            "The windows must have the same dimensions"
+        );
+        if dst_layout.is_empty() {
+            return;
+        }
+
+        let mut product = Buffer::<T, _>::new(self, dst_layout.len());
+        cl_binary_strided(
+            self,
+            lhs,
+            lhs_layout,
+            rhs,
+            rhs_layout,
+            &mut product,
+            |lhs, rhs| lhs.mul(rhs),
+        )
+        .unwrap();
+        cl_add_strided_assign(self, dst, dst_layout, &product, T::one()).unwrap();
+    }
+}
+
+/// The OpenCL expression of the buffer index of the element (`row`, `col`) of a window.
+/// The layout is passed as the kernel arguments `{name}_offset`, `{name}_row_stride` and `{name}_col_stride`.
+fn cl_index(name: &str, row: &str, col: &str) -> String {
+    format!("{name}_offset + {row} * {name}_row_stride + {col} * {name}_col_stride")
+}
+
+fn cl_layout_params(name: &str) -> String {
+    format!("int {name}_offset, int {name}_row_stride, int {name}_col_stride")
+}
+
+#[inline]
+fn layout_args(layout: Layout) -> [i32; 3] {
+    [
+        layout.offset as i32,
+        layout.row_stride as i32,
+        layout.col_stride as i32,
+    ]
+}
+
+pub fn cl_copy_strided<T: CDatatype>(
+    device: &CLDevice,
+    x: &CLPtr<T>,
+    layout: Layout,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    if layout.is_empty() {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void copy_strided(__global const {dtype}* x, __global {dtype}* out, int cols, {params}) {{
+            size_t id = get_global_id(0);
+            size_t row = id / cols;
+            size_t col = id % cols;
+            out[id] = x[{x_idx}];
+        }}
+    ",
+        dtype = T::C_DTYPE_STR,
+        params = cl_layout_params("x"),
+        x_idx = cl_index("x", "row", "col"),
+    );
+
+    let [offset, row_stride, col_stride] = layout_args(layout);
+    device.launch_kernel(
+        &src,
+        [layout.len(), 0, 0],
+        None,
+        &[
+            x,
+            out,
+            &(layout.cols as i32),
+            &offset,
+            &row_stride,
+            &col_stride,
+        ],
+    )
+}
+
+/// Adds `alpha * src` to the window `layout` of `dst`.
+///
+/// # Panics
+/// If elements of `layout` overlap.
+pub fn cl_add_strided_assign<T: CDatatype>(
+    device: &CLDevice,
+    dst: &mut CLPtr<T>,
+    layout: Layout,
+    src: &CLPtr<T>,
+    alpha: T,
+) -> custos::Result<()> {
+    // every work item adds to its own element, overlapping elements would race
+    assert!(
+        !layout.overlaps(),
+        "The elements of the layout {layout:?} overlap"
+    );
+    if layout.is_empty() {
+        return Ok(());
+    }
+
+    let kernel = format!(
+        "
+        __kernel void add_strided_assign(__global {dtype}* dst, __global const {dtype}* src, {dtype} alpha, int cols, {params}) {{
+            size_t id = get_global_id(0);
+            size_t row = id / cols;
+            size_t col = id % cols;
+            dst[{dst_idx}] += alpha * src[id];
+        }}
+    ",
+        dtype = T::C_DTYPE_STR,
+        params = cl_layout_params("dst"),
+        dst_idx = cl_index("dst", "row", "col"),
+    );
+
+    let [offset, row_stride, col_stride] = layout_args(layout);
+    device.launch_kernel(
+        &kernel,
+        [layout.len(), 0, 0],
+        None,
+        &[
+            dst,
+            src,
+            &alpha,
+            &(layout.cols as i32),
+            &offset,
+            &row_stride,
+            &col_stride,
+        ],
+    )
+}
+
+/// Every work item computes one value of the m x n output.
+pub fn cl_gemm_strided<T: CDatatype>(
+    device: &CLDevice,
+    lhs: &CLPtr<T>,
+    lhs_layout: Layout,
+    rhs: &CLPtr<T>,
+    rhs_layout: Layout,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    let (m, k, n) = (lhs_layout.rows, lhs_layout.cols, rhs_layout.cols);
+    assert_eq!(k, rhs_layout.rows, "Inner dimensions of the gemm differ");
+
+    if m * n == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        __kernel void gemm_strided(__global const {dtype}* lhs, __global const {dtype}* rhs, __global {dtype}* out, int k, int n, {lhs_params}, {rhs_params}) {{
+            size_t id = get_global_id(0);
+            size_t row = id / n;
+            size_t col = id % n;
+
+            {dtype} acc = 0;
+            for (int inner = 0; inner < k; inner++) {{
+                acc += lhs[{lhs_idx}] * rhs[{rhs_idx}];
+            }}
+            out[id] = acc;
+        }}
+    ",
+        dtype = T::C_DTYPE_STR,
+        lhs_params = cl_layout_params("lhs"),
+        rhs_params = cl_layout_params("rhs"),
+        lhs_idx = cl_index("lhs", "row", "inner"),
+        rhs_idx = cl_index("rhs", "inner", "col"),
+    );
+
+    let [lhs_offset, lhs_row_stride, lhs_col_stride] = layout_args(lhs_layout);
+    let [rhs_offset, rhs_row_stride, rhs_col_stride] = layout_args(rhs_layout);
+    device.launch_kernel(
+        &src,
+        [m * n, 0, 0],
+        None,
+        &[
+            lhs,
+            rhs,
+            out,
+            &(k as i32),
+            &(n as i32),
+            &lhs_offset,
+            &lhs_row_stride,
+            &lhs_col_stride,
+            &rhs_offset,
+            &rhs_row_stride,
+            &rhs_col_stride,
+        ],
+    )
+}
+
+pub fn cl_binary_strided<T, O>(
+    device: &CLDevice,
+    lhs: &CLPtr<T>,
+    lhs_layout: Layout,
+    rhs: &CLPtr<T>,
+    rhs_layout: Layout,
+    out: &mut CLPtr<T>,
+    f: impl Fn(Resolve<T>, Resolve<T>) -> O,
+) -> custos::Result<()>
+where
+    T: CDatatype,
+    O: MayToCLSource,
+{
+    if lhs_layout.is_empty() {
+        return Ok(());
+    }
+
+    let lhs_val = format!("lhs[{}]", cl_index("lhs", "row", "col"));
+    let rhs_val = format!("rhs[{}]", cl_index("rhs", "row", "col"));
+
+    let src = format!(
+        "
+        __kernel void binary_strided(__global const {dtype}* lhs, __global const {dtype}* rhs, __global {dtype}* out, int cols, {lhs_params}, {rhs_params}) {{
+            size_t id = get_global_id(0);
+            size_t row = id / cols;
+            size_t col = id % cols;
+            out[id] = {op};
+        }}
+    ",
+        dtype = T::C_DTYPE_STR,
+        lhs_params = cl_layout_params("lhs"),
+        rhs_params = cl_layout_params("rhs"),
+        op = f(lhs_val.to_marker(), rhs_val.to_marker()).to_cl_source()
+    );
+
+    let [lhs_offset, lhs_row_stride, lhs_col_stride] = layout_args(lhs_layout);
+    let [rhs_offset, rhs_row_stride, rhs_col_stride] = layout_args(rhs_layout);
+    device.launch_kernel(
+        &src,
+        [lhs_layout.len(), 0, 0],
+        None,
+        &[
+            lhs,
+            rhs,
+            out,
+            &(lhs_layout.cols as i32),
+            &lhs_offset,
+            &lhs_row_stride,
+            &lhs_col_stride,
+            &rhs_offset,
+            &rhs_row_stride,
+            &rhs_col_stride,
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, Combiner, OpenCL};
+
+    use crate::{AddStridedAssign, BinaryStrided, CopyStrided, GemmStrided, Layout};
+
+    #[rustfmt::skip]
+    const X: [f32; 12] = [
+        1., 2., 3.,
+        4., 5., 6.,
+        7., 8., 9.,
+        10., 11., 12.,
+    ];
+
+    #[test]
+    fn test_cl_strided_ops() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+        let mut x = Buffer::from((&device, X));
+        let layout = Layout::contiguous(4, 3);
+
+        let out = device.copy_strided(&x, layout.slice_rows(0, 2).t());
+        assert_eq!(out.read(), [1., 4., 2., 5., 3., 6.]);
+
+        let out = device.gemm_strided(
+            &x,
+            layout.slice_rows(2, 4),
+            &x,
+            layout.slice_rows(0, 3).slice_cols(0, 2),
+        );
+        assert_eq!(out.read(), [102., 126., 138., 171.]);
+
+        let out = device.binary_strided(
+            &x,
+            layout.slice_rows(0, 2).slice_cols(0, 2),
+            &x,
+            layout.slice_rows(2, 4).slice_cols(1, 3),
+            |a, b| a.mul(b),
+        );
+        assert_eq!(out.read(), [8., 18., 44., 60.]);
+
+        let ones = Buffer::from((&device, [1f32; 4]));
+        device.add_strided_assign(&mut x, layout.slice_cols(2, 3), &ones, -2.);
+        assert_eq!(
+            x.read(),
+            [1., 2., 1., 4., 5., 4., 7., 8., 7., 10., 11., 10.]
+        );
+        Ok(())
+    }
+}
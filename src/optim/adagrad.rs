@@ -0,0 +1,80 @@
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, MayTapeActions, OnNewBuffer, ZeroGrad,
+};
+
+use crate::{nn::Param, AdagradHyper, AdagradStep};
+
+use super::{Optimizer, ParamStates};
+
+/// Adagrad: scales the learning rate of every element by its accumulated squared gradients.
+pub struct Adagrad<'a, T, D: Device> {
+    pub lr: T,
+    pub eps: T,
+    pub weight_decay: T,
+    sums: ParamStates<'a, T, D, 1>,
+}
+
+impl<'a, T: Float, D: Device> Adagrad<'a, T, D> {
+    /// Creates an Adagrad optimizer with `eps = 1e-10`.
+    #[inline]
+    pub fn new(lr: T) -> Self {
+        Adagrad {
+            lr,
+            eps: T::as_generic(1e-10),
+            weight_decay: T::zero(),
+            sums: ParamStates::new(),
+        }
+    }
+
+    #[inline]
+    pub fn eps(mut self, eps: T) -> Self {
+        self.eps = eps;
+        self
+    }
+
+    #[inline]
+    pub fn weight_decay(mut self, weight_decay: T) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
+}
+
+impl<'a, T, D> Optimizer<'a, T, D> for Adagrad<'a, T, D>
+where
+    T: Float + 'static,
+    D: AdagradStep<T>
+        + MayTapeActions
+        + ZeroGrad<T>
+        + Alloc<T>
+        + OnNewBuffer<T, D>
+        + ClearBuf<T>
+        + 'static,
+{
+    fn step(&mut self, params: Vec<Param<'_, 'a, T, D>>) {
+        let hyper = AdagradHyper {
+            lr: self.lr,
+            eps: self.eps,
+            weight_decay: self.weight_decay,
+        };
+
+        for param in params {
+            let grad = param.param.grad();
+            let [sum] = &mut self.sums.next_step(param.param).buffers;
+            param
+                .param
+                .device()
+                .adagrad_step(&hyper, param.param, grad, sum);
+        }
+    }
+
+    #[inline]
+    fn lr(&self) -> T {
+        self.lr
+    }
+
+    #[inline]
+    fn set_lr(&mut self, lr: T) {
+        self.lr = lr;
+    }
+}
@@ -0,0 +1,111 @@
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, MayTapeActions, OnNewBuffer, ZeroGrad,
+};
+
+use crate::{nn::Param, AdamHyper, AdamStep};
+
+use super::{Optimizer, ParamStates};
+
+/// Adam with bias corrected moment estimates.
+/// Use [`Adam::adamw`] for decoupled weight decay (AdamW).
+pub struct Adam<'a, T, D: Device> {
+    pub lr: T,
+    pub beta1: T,
+    pub beta2: T,
+    pub eps: T,
+    pub weight_decay: T,
+    pub decoupled_weight_decay: bool,
+    moments: ParamStates<'a, T, D, 2>,
+}
+
+impl<'a, T: Float, D: Device> Adam<'a, T, D> {
+    /// Creates an Adam optimizer with `beta1 = 0.9`, `beta2 = 0.999` and `eps = 1e-8`.
+    #[inline]
+    pub fn new(lr: T) -> Self {
+        Adam {
+            lr,
+            beta1: T::as_generic(0.9),
+            beta2: T::as_generic(0.999),
+            eps: T::as_generic(1e-8),
+            weight_decay: T::zero(),
+            decoupled_weight_decay: false,
+            moments: ParamStates::new(),
+        }
+    }
+
+    /// Creates an AdamW optimizer, which decays the weights directly instead of adding the decay to the gradient.
+    #[inline]
+    pub fn adamw(lr: T, weight_decay: T) -> Self {
+        Adam {
+            weight_decay,
+            decoupled_weight_decay: true,
+            ..Adam::new(lr)
+        }
+    }
+
+    #[inline]
+    pub fn betas(mut self, beta1: T, beta2: T) -> Self {
+        self.beta1 = beta1;
+        self.beta2 = beta2;
+        self
+    }
+
+    #[inline]
+    pub fn eps(mut self, eps: T) -> Self {
+        self.eps = eps;
+        self
+    }
+
+    #[inline]
+    pub fn weight_decay(mut self, weight_decay: T) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
+}
+
+impl<'a, T, D> Optimizer<'a, T, D> for Adam<'a, T, D>
+where
+    T: Float + 'static,
+    D: AdamStep<T>
+        + MayTapeActions
+        + ZeroGrad<T>
+        + Alloc<T>
+        + OnNewBuffer<T, D>
+        + ClearBuf<T>
+        + 'static,
+{
+    fn step(&mut self, params: Vec<Param<'_, 'a, T, D>>) {
+        for param in params {
+            let grad = param.param.grad();
+            let state = self.moments.next_step(param.param);
+
+            let hyper = AdamHyper {
+                lr: self.lr,
+                beta1: self.beta1,
+                beta2: self.beta2,
+                eps: self.eps,
+                weight_decay: self.weight_decay,
+                decoupled_weight_decay: self.decoupled_weight_decay,
+                bias_correction1: T::one() - self.beta1.powi(state.steps as i32),
+                bias_correction2: T::one() - self.beta2.powi(state.steps as i32),
+            };
+
+            let [m, v] = &mut state.buffers;
+            param
+                .param
+                .device()
+                .adam_step(&hyper, param.param, grad, m, v);
+        }
+    }
+
+    #[inline]
+    fn lr(&self) -> T {
+        self.lr
+    }
+
+    #[inline]
+    fn set_lr(&mut self, lr: T) {
+        self.lr = lr;
+    }
+}
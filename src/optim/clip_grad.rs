@@ -0,0 +1,66 @@
+use custos::{prelude::Float, Alloc, MayTapeActions, ZeroGrad};
+
+use crate::{nn::Param, ClampAssign, ScaleAssign, SumSquares};
+
+/// Rescales the gradients of `params` in place so that their global L2 norm is at most `max_norm`.
+/// The norm is computed over all gradients as if they were concatenated.
+///
+/// Returns the total norm before clipping.
+///
+/// # Example
+#[cfg_attr(all(feature = "cpu", feature = "autograd"), doc = "```")]
+#[cfg_attr(not(all(feature = "cpu", feature = "autograd")), doc = "```ignore")]
+/// use sliced::{
+///     nn::{Linear, Module},
+///     optim::clip_grad_norm,
+///     Matrix, CPU,
+/// };
+///
+/// let device = CPU::<custos::Autograd<custos::Base>>::new();
+/// let mut lin = Linear::<f32, _, 2, 1>::new(&device);
+///
+/// let x = Matrix::from((&device, 1, 2, [100., 200.])).no_grad();
+/// lin.forward(&x).backward();
+///
+/// let total_norm = clip_grad_norm(lin.params(), 1.);
+/// assert!(total_norm > 1.);
+/// ```
+pub fn clip_grad_norm<'a, T, D>(params: Vec<Param<'_, 'a, T, D>>, max_norm: T) -> T
+where
+    T: Float + 'static,
+    D: SumSquares<T> + ScaleAssign<T> + MayTapeActions + ZeroGrad<T> + Alloc<T> + 'static,
+{
+    let total_norm = params
+        .iter()
+        .fold(T::zero(), |acc, param| {
+            acc + param.param.device().sum_squares(param.param.grad())
+        })
+        .sqrt();
+
+    let factor = max_norm / (total_norm + T::as_generic(1e-6));
+    if factor < T::one() {
+        for param in params {
+            param
+                .param
+                .device()
+                .scale_assign(param.param.grad_mut(), factor);
+        }
+    }
+
+    total_norm
+}
+
+/// Clamps the gradients of `params` in place to `[-clip_value, clip_value]`.
+pub fn clip_grad_value<'a, T, D>(params: Vec<Param<'_, 'a, T, D>>, clip_value: T)
+where
+    T: Float + 'static,
+    D: ClampAssign<T> + MayTapeActions + ZeroGrad<T> + Alloc<T> + 'static,
+{
+    for param in params {
+        param.param.device().clamp_assign(
+            param.param.grad_mut(),
+            T::zero() - clip_value,
+            clip_value,
+        );
+    }
+}
@@ -0,0 +1,525 @@
+use std::collections::VecDeque;
+
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, MayTapeActions, Read, WriteBuf, ZeroGrad,
+};
+
+use crate::nn::Module;
+
+/// Limited-memory BFGS with an optional strong Wolfe line search.
+///
+/// Unlike first-order optimizers, L-BFGS evaluates the objective several times per step.
+/// Hence, [`LBFGS::step`] takes a closure that reruns the forward pass, calls `backward()` and returns the loss.
+/// The parameters are flattened on the host, which suits the small problems L-BFGS is meant for.
+///
+/// # Example
+#[cfg_attr(all(feature = "cpu", feature = "autograd"), doc = "```")]
+#[cfg_attr(not(all(feature = "cpu", feature = "autograd")), doc = "```ignore")]
+/// use sliced::{
+///     nn::{Linear, Module},
+///     optim::LBFGS,
+///     BinaryOpsMayGrad, Matrix, Sum, CPU,
+/// };
+///
+/// let device = CPU::<custos::Autograd<custos::Base>>::new();
+///
+/// let mut lin = Linear::<f64, _, 1, 1>::new(&device);
+/// let x = Matrix::from((&device, 3, 1, [0., 1., 2.])).no_grad();
+/// let y = Matrix::from((&device, 3, 1, [1., 3., 5.])).no_grad();
+///
+/// let mut lbfgs = LBFGS::new(1.);
+/// let loss = lbfgs.step(&mut lin, |lin| {
+///     let diff = device.sub(&lin.forward(&x), &y);
+///     let loss = device.mul(&diff, &diff);
+///     loss.backward();
+///     device.sum(&loss)
+/// });
+///
+/// assert!(loss < 1e-8);
+/// ```
+pub struct LBFGS<T> {
+    pub lr: T,
+    /// The maximum number of iterations per step.
+    pub max_iter: usize,
+    /// The maximum number of objective evaluations per step.
+    pub max_eval: usize,
+    /// The number of stored curvature pairs.
+    pub history_size: usize,
+    /// Stops if the largest absolute gradient is below this value.
+    pub tolerance_grad: T,
+    /// Stops if the change of the loss or the parameters is below this value.
+    pub tolerance_change: T,
+    /// Uses a strong Wolfe line search. Otherwise, the step size is fixed to `lr`.
+    pub line_search: bool,
+    /// The curvature pairs `(s, y)` of the previous iterations.
+    history: VecDeque<(Vec<T>, Vec<T>)>,
+}
+
+const C1: f64 = 1e-4;
+const C2: f64 = 0.9;
+const MAX_LINE_SEARCH: usize = 25;
+
+impl<T: Float> LBFGS<T> {
+    #[inline]
+    pub fn new(lr: T) -> Self {
+        LBFGS {
+            lr,
+            max_iter: 20,
+            max_eval: 25,
+            history_size: 10,
+            tolerance_grad: T::as_generic(1e-7),
+            tolerance_change: T::as_generic(1e-9),
+            line_search: true,
+            history: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self.max_eval = max_iter * 5 / 4;
+        self
+    }
+
+    #[inline]
+    pub fn history_size(mut self, history_size: usize) -> Self {
+        self.history_size = history_size;
+        self
+    }
+
+    #[inline]
+    pub fn tolerance_grad(mut self, tolerance_grad: T) -> Self {
+        self.tolerance_grad = tolerance_grad;
+        self
+    }
+
+    #[inline]
+    pub fn tolerance_change(mut self, tolerance_change: T) -> Self {
+        self.tolerance_change = tolerance_change;
+        self
+    }
+
+    #[inline]
+    pub fn line_search(mut self, line_search: bool) -> Self {
+        self.line_search = line_search;
+        self
+    }
+
+    /// Optimises the parameters of `model`.
+    /// Before every call of `closure`, the gradients of the parameters are set to zero.
+    /// `closure` must run the forward pass, call `backward()` and return the loss.
+    /// The returned loss has to match the gradient, e.g. the sum of the buffer `backward()` is called on.
+    ///
+    /// Afterwards, the parameters and their gradients are the ones of the accepted iterate.
+    /// Returns the loss at this iterate.
+    pub fn step<'a, D, M>(&mut self, model: &mut M, mut closure: impl FnMut(&mut M) -> T) -> T
+    where
+        T: Default + 'static,
+        D: Read<T> + WriteBuf<T> + MayTapeActions + ZeroGrad<T> + Alloc<T> + ClearBuf<T> + 'static,
+        M: Module<'a, T, D>,
+    {
+        let mut x = model
+            .params()
+            .iter()
+            .flat_map(|param| param.param.read_to_vec())
+            .collect::<Vec<_>>();
+
+        // the line search may leave the parameters at a rejected trial point
+        let mut last_eval = Vec::new();
+        let loss = self.minimize(&mut x, |x| {
+            last_eval.clear();
+            last_eval.extend_from_slice(x);
+            evaluate(model, &mut closure, x)
+        });
+
+        if last_eval != x {
+            evaluate(model, &mut closure, &x);
+        }
+        loss
+    }
+
+    /// Minimises `objective` in place, starting at `x`.
+    /// `objective` returns the loss and the gradient at the given point.
+    ///
+    /// Returns the loss at the final `x`.
+    pub fn minimize(&mut self, x: &mut [T], mut objective: impl FnMut(&[T]) -> (T, Vec<T>)) -> T {
+        let (mut loss, mut grad) = objective(x);
+        let mut evals = 1;
+
+        if max_abs(&grad) <= self.tolerance_grad {
+            return loss;
+        }
+
+        for iter in 0..self.max_iter {
+            let dir = self.direction(&grad);
+
+            let gtd = dot(&grad, &dir);
+            if gtd > -self.tolerance_change {
+                break;
+            }
+
+            // the first step of an empty history is scaled by the gradient
+            let t = if iter == 0 && self.history.is_empty() {
+                let l1 = grad.iter().fold(T::zero(), |acc, g| acc + g.abs());
+                min(T::one(), T::one() / l1) * self.lr
+            } else {
+                self.lr
+            };
+
+            let (t, new_loss, new_grad, ls_evals) = if self.line_search {
+                strong_wolfe(
+                    &mut objective,
+                    x,
+                    t,
+                    &dir,
+                    loss,
+                    &grad,
+                    gtd,
+                    self.tolerance_change,
+                )
+            } else {
+                let (new_loss, new_grad) = objective(&axpy(x, t, &dir));
+                (t, new_loss, new_grad, 1)
+            };
+            evals += ls_evals;
+
+            for (x, dir) in x.iter_mut().zip(&dir) {
+                *x = *x + t * *dir;
+            }
+
+            let s = dir.iter().map(|dir| t * *dir).collect::<Vec<_>>();
+            let y = new_grad
+                .iter()
+                .zip(&grad)
+                .map(|(new, old)| *new - *old)
+                .collect::<Vec<_>>();
+
+            // skip updates that would break the positive definiteness
+            if dot(&y, &s) > T::as_generic(1e-10) {
+                if self.history.len() == self.history_size {
+                    self.history.pop_front();
+                }
+                self.history.push_back((s.clone(), y));
+            }
+
+            let loss_change = (new_loss - loss).abs();
+            loss = new_loss;
+            grad = new_grad;
+
+            if evals >= self.max_eval
+                || max_abs(&grad) <= self.tolerance_grad
+                || max_abs(&s) <= self.tolerance_change
+                || loss_change < self.tolerance_change
+            {
+                break;
+            }
+        }
+
+        loss
+    }
+
+    /// Computes `-H * grad` with the two-loop recursion.
+    fn direction(&self, grad: &[T]) -> Vec<T> {
+        let mut q = grad.to_vec();
+        let mut alphas = Vec::with_capacity(self.history.len());
+
+        for (s, y) in self.history.iter().rev() {
+            let alpha = dot(s, &q) / dot(y, s);
+            for (q, y) in q.iter_mut().zip(y) {
+                *q = *q - alpha * *y;
+            }
+            alphas.push(alpha);
+        }
+
+        if let Some((s, y)) = self.history.back() {
+            let gamma = dot(s, y) / dot(y, y);
+            for q in q.iter_mut() {
+                *q = *q * gamma;
+            }
+        }
+
+        for ((s, y), alpha) in self.history.iter().zip(alphas.into_iter().rev()) {
+            let beta = dot(y, &q) / dot(y, s);
+            for (q, s) in q.iter_mut().zip(s) {
+                *q = *q + *s * (alpha - beta);
+            }
+        }
+
+        q.into_iter().map(|q| -q).collect()
+    }
+}
+
+/// Writes `x` into the parameters of `model`, clears their gradients and returns the loss and gradient of `closure`.
+fn evaluate<'a, T, D, M>(
+    model: &mut M,
+    closure: &mut impl FnMut(&mut M) -> T,
+    x: &[T],
+) -> (T, Vec<T>)
+where
+    T: Float + Default + 'static,
+    D: Read<T> + WriteBuf<T> + MayTapeActions + ZeroGrad<T> + Alloc<T> + ClearBuf<T> + 'static,
+    M: Module<'a, T, D>,
+{
+    let mut offset = 0;
+    for param in model.params() {
+        let len = param.param.len();
+        param.param.write(&x[offset..offset + len]);
+        param.param.grad_mut().clear();
+        offset += len;
+    }
+
+    let loss = closure(model);
+
+    let grad = model
+        .params()
+        .iter()
+        .flat_map(|param| param.param.grad().read_to_vec())
+        .collect();
+    (loss, grad)
+}
+
+#[inline]
+fn dot<T: Float>(lhs: &[T], rhs: &[T]) -> T {
+    lhs.iter()
+        .zip(rhs)
+        .fold(T::zero(), |acc, (lhs, rhs)| acc + *lhs * *rhs)
+}
+
+#[inline]
+fn max_abs<T: Float>(x: &[T]) -> T {
+    x.iter().fold(T::zero(), |acc, x| max(acc, x.abs()))
+}
+
+#[inline]
+fn axpy<T: Float>(x: &[T], t: T, dir: &[T]) -> Vec<T> {
+    x.iter().zip(dir).map(|(x, dir)| *x + t * *dir).collect()
+}
+
+#[inline]
+fn min<T: Float>(lhs: T, rhs: T) -> T {
+    if lhs < rhs {
+        lhs
+    } else {
+        rhs
+    }
+}
+
+#[inline]
+fn max<T: Float>(lhs: T, rhs: T) -> T {
+    if lhs > rhs {
+        lhs
+    } else {
+        rhs
+    }
+}
+
+/// Minimiser of the cubic interpolating two points and their derivatives, clamped to `bounds`.
+fn cubic_interpolate<T: Float>(
+    (x1, f1, g1): (T, T, T),
+    (x2, f2, g2): (T, T, T),
+    bounds: Option<(T, T)>,
+) -> T {
+    let (lo, hi) = bounds.unwrap_or((min(x1, x2), max(x1, x2)));
+    let three = T::as_generic(3.);
+
+    let d1 = g1 + g2 - three * (f1 - f2) / (x1 - x2);
+    let d2_square = d1 * d1 - g1 * g2;
+    if d2_square < T::zero() {
+        return (lo + hi) / T::as_generic(2.);
+    }
+
+    let two = T::as_generic(2.);
+    let d2 = d2_square.sqrt();
+    let min_pos = if x1 <= x2 {
+        x2 - (x2 - x1) * ((g2 + d2 - d1) / (g2 - g1 + two * d2))
+    } else {
+        x1 - (x1 - x2) * ((g1 + d2 - d1) / (g1 - g2 + two * d2))
+    };
+    min(max(min_pos, lo), hi)
+}
+
+/// A point of the line search: step size, loss, gradient and directional derivative.
+type Point<T> = (T, T, Vec<T>, T);
+
+/// Finds a step size `t` along `dir` that satisfies the strong Wolfe conditions (bracketing and zoom phase).
+/// Returns the step size, the loss and gradient at `x + t * dir`, and the number of evaluations.
+#[allow(clippy::too_many_arguments)]
+fn strong_wolfe<T: Float>(
+    objective: &mut impl FnMut(&[T]) -> (T, Vec<T>),
+    x: &[T],
+    mut t: T,
+    dir: &[T],
+    loss: T,
+    grad: &[T],
+    gtd: T,
+    tolerance_change: T,
+) -> (T, T, Vec<T>, usize) {
+    let (c1, c2) = (T::as_generic(C1), T::as_generic(C2));
+    let dir_norm = max_abs(dir);
+
+    let (mut new_loss, mut new_grad) = objective(&axpy(x, t, dir));
+    let mut gtd_new = dot(&new_grad, dir);
+    let mut evals = 1;
+
+    let mut prev: Point<T> = (T::zero(), loss, grad.to_vec(), gtd);
+    let mut done = false;
+    let mut ls_iter = 0;
+
+    let mut bracket: [Point<T>; 2] = loop {
+        if ls_iter == MAX_LINE_SEARCH {
+            break [
+                (T::zero(), loss, grad.to_vec(), gtd),
+                (t, new_loss, new_grad, gtd_new),
+            ];
+        }
+
+        if new_loss > loss + c1 * t * gtd || (ls_iter > 1 && new_loss >= prev.1) {
+            break [prev, (t, new_loss, new_grad, gtd_new)];
+        }
+
+        if gtd_new.abs() <= -c2 * gtd {
+            done = true;
+            let point = (t, new_loss, new_grad, gtd_new);
+            break [point.clone(), point];
+        }
+
+        if gtd_new >= T::zero() {
+            break [prev, (t, new_loss, new_grad, gtd_new)];
+        }
+
+        // extrapolate
+        let min_step = t + T::as_generic(0.01) * (t - prev.0);
+        let max_step = t * T::as_generic(10.);
+        let next_t = cubic_interpolate(
+            (prev.0, prev.1, prev.3),
+            (t, new_loss, gtd_new),
+            Some((min_step, max_step)),
+        );
+
+        prev = (t, new_loss, new_grad, gtd_new);
+        t = next_t;
+
+        (new_loss, new_grad) = objective(&axpy(x, t, dir));
+        gtd_new = dot(&new_grad, dir);
+        evals += 1;
+        ls_iter += 1;
+    };
+
+    let order = |bracket: &[Point<T>; 2]| {
+        if bracket[0].1 <= bracket[1].1 {
+            (0, 1)
+        } else {
+            (1, 0)
+        }
+    };
+    let (mut low, mut high) = order(&bracket);
+    let mut insufficient_progress = false;
+
+    while !done && ls_iter < MAX_LINE_SEARCH {
+        let (b_min, b_max) = (
+            min(bracket[0].0, bracket[1].0),
+            max(bracket[0].0, bracket[1].0),
+        );
+        if (b_max - b_min) * dir_norm < tolerance_change {
+            break;
+        }
+
+        t = cubic_interpolate(
+            (bracket[0].0, bracket[0].1, bracket[0].3),
+            (bracket[1].0, bracket[1].1, bracket[1].3),
+            None,
+        );
+
+        // keep the trial point away from the boundaries of the bracket
+        let eps = T::as_generic(0.1) * (b_max - b_min);
+        if min(b_max - t, t - b_min) < eps {
+            if insufficient_progress || t >= b_max || t <= b_min {
+                t = if (t - b_max).abs() < (t - b_min).abs() {
+                    b_max - eps
+                } else {
+                    b_min + eps
+                };
+                insufficient_progress = false;
+            } else {
+                insufficient_progress = true;
+            }
+        } else {
+            insufficient_progress = false;
+        }
+
+        let (trial_loss, trial_grad) = objective(&axpy(x, t, dir));
+        let trial_gtd = dot(&trial_grad, dir);
+        evals += 1;
+        ls_iter += 1;
+
+        if trial_loss > loss + c1 * t * gtd || trial_loss >= bracket[low].1 {
+            bracket[high] = (t, trial_loss, trial_grad, trial_gtd);
+            (low, high) = order(&bracket);
+        } else {
+            if trial_gtd.abs() <= -c2 * gtd {
+                done = true;
+            } else if trial_gtd * (bracket[high].0 - bracket[low].0) >= T::zero() {
+                bracket[high] = bracket[low].clone();
+            }
+            bracket[low] = (t, trial_loss, trial_grad, trial_gtd);
+        }
+    }
+
+    let [first, second] = bracket;
+    let (t, new_loss, new_grad, _) = if low == 0 { first } else { second };
+    (t, new_loss, new_grad, evals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LBFGS;
+
+    fn rosenbrock(x: &[f64]) -> (f64, Vec<f64>) {
+        let (a, b) = (x[0], x[1]);
+        let loss = (1. - a).powi(2) + 100. * (b - a * a).powi(2);
+        let grad = vec![-2. * (1. - a) - 400. * a * (b - a * a), 200. * (b - a * a)];
+        (loss, grad)
+    }
+
+    #[test]
+    fn test_lbfgs_rosenbrock() {
+        let mut lbfgs = LBFGS::new(1.).max_iter(100);
+        let mut x = [-1.5, 2.];
+
+        let mut loss = f64::MAX;
+        for _ in 0..10 {
+            loss = lbfgs.minimize(&mut x, rosenbrock);
+        }
+
+        assert!(loss < 1e-10, "loss: {loss}");
+        assert!(
+            (x[0] - 1.).abs() < 1e-4 && (x[1] - 1.).abs() < 1e-4,
+            "{x:?}"
+        );
+    }
+
+    #[test]
+    fn test_lbfgs_quadratic_without_line_search() {
+        // f(x) = sum (i + 1) * x_i²
+        let objective = |x: &[f64]| {
+            let loss = x
+                .iter()
+                .enumerate()
+                .map(|(i, x)| (i + 1) as f64 * x * x)
+                .sum();
+            let grad = x
+                .iter()
+                .enumerate()
+                .map(|(i, x)| 2. * (i + 1) as f64 * x)
+                .collect();
+            (loss, grad)
+        };
+
+        let mut lbfgs = LBFGS::new(0.1).line_search(false).max_iter(200);
+        let mut x = [1., -2., 3.];
+        let loss = lbfgs.minimize(&mut x, objective);
+
+        assert!(loss < 1e-8, "loss: {loss}");
+    }
+}
@@ -0,0 +1,127 @@
+//! Optimizers updating the [`Param`]s of a [`Module`](crate::nn::Module).
+//!
+//! The update steps run as device kernels, see [`SgdStep`](crate::SgdStep) or [`AdamStep`](crate::AdamStep).
+//! The state of an optimizer (e.g. momentum buffers) is kept per parameter buffer.
+//! Therefore, one optimizer can be stepped with the parameters of several modules.
+//!
+//! # Example
+#![cfg_attr(feature = "cpu", doc = "```")]
+#![cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+//! use sliced::{
+//!     nn::{Linear, Module},
+//!     optim::{Adam, Optimizer},
+//!     BinaryOpsMayGrad, Matrix, CPU,
+//! };
+//!
+//! let device = CPU::<custos::Autograd<custos::Base>>::new();
+//!
+//! let mut lin = Linear::<f32, _, 1, 1>::new(&device);
+//! let mut adam = Adam::new(0.1);
+//!
+//! let x = Matrix::from((&device, 2, 1, [1., 2.])).no_grad();
+//! let y = Matrix::from((&device, 2, 1, [3., 5.])).no_grad();
+//!
+//! for _ in 0..100 {
+//!     adam.zero_grad(lin.params());
+//!
+//!     let diff = device.sub(&lin.forward(&x), &y);
+//!     let loss = device.mul(&diff, &diff);
+//!     loss.backward();
+//!
+//!     adam.step(lin.params());
+//! }
+//! ```
+
+mod adagrad;
+mod adam;
+mod clip_grad;
+mod lbfgs;
+mod rmsprop;
+mod scheduler;
+mod sgd;
+
+pub use adagrad::*;
+pub use adam::*;
+pub use clip_grad::*;
+pub use lbfgs::*;
+pub use rmsprop::*;
+pub use scheduler::*;
+pub use sgd::*;
+
+use std::collections::HashMap;
+
+use custos::{
+    prelude::ClearBuf, Alloc, Buffer, Device, HasId, MayTapeActions, OnNewBuffer, ZeroGrad,
+};
+
+use crate::nn::Param;
+
+pub trait Optimizer<'a, T, D: Device> {
+    /// Updates every parameter with its gradient.
+    fn step(&mut self, params: Vec<Param<'_, 'a, T, D>>);
+
+    /// Returns the current learning rate.
+    fn lr(&self) -> T;
+
+    fn set_lr(&mut self, lr: T);
+
+    /// Sets the gradients of `params` to zero.
+    fn zero_grad(&self, params: Vec<Param<'_, 'a, T, D>>)
+    where
+        T: 'static,
+        D: MayTapeActions + ZeroGrad<T> + Alloc<T> + ClearBuf<T> + 'static,
+    {
+        for param in params {
+            param.param.grad_mut().clear();
+        }
+    }
+}
+
+/// The state buffers of one parameter.
+pub(crate) struct ParamState<'a, T, D: Device, const N: usize> {
+    /// The number of steps performed with this parameter, including the current one.
+    pub steps: usize,
+    pub buffers: [Buffer<'a, T, D>; N],
+}
+
+/// Lazily allocates zeroed state buffers for every parameter, identified by the id of its buffer.
+/// The id is the address of the buffer and may be reused after the buffer is dropped.
+/// Hence, a state whose length does not match the parameter anymore is started over.
+pub(crate) struct ParamStates<'a, T, D: Device, const N: usize> {
+    states: HashMap<u64, ParamState<'a, T, D, N>>,
+}
+
+impl<'a, T, D: Device, const N: usize> ParamStates<'a, T, D, N> {
+    #[inline]
+    pub fn new() -> Self {
+        ParamStates {
+            states: HashMap::new(),
+        }
+    }
+
+    /// Returns the state of `param` and increments its step count.
+    pub fn next_step(&mut self, param: &Buffer<'a, T, D>) -> &mut ParamState<'a, T, D, N>
+    where
+        D: Alloc<T> + OnNewBuffer<T, D> + ClearBuf<T>,
+    {
+        let new_state = || ParamState {
+            steps: 0,
+            buffers: core::array::from_fn(|_| {
+                let mut buffer = Buffer::new(param.device(), param.len());
+                buffer.clear();
+                buffer
+            }),
+        };
+
+        let state = self.states.entry(param.id().id).or_insert_with(new_state);
+        if state
+            .buffers
+            .iter()
+            .any(|buffer| buffer.len() != param.len())
+        {
+            *state = new_state();
+        }
+        state.steps += 1;
+        state
+    }
+}
@@ -0,0 +1,98 @@
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, MayTapeActions, OnNewBuffer, ZeroGrad,
+};
+
+use crate::{nn::Param, RmsPropHyper, RmsPropStep};
+
+use super::{Optimizer, ParamStates};
+
+/// RMSProp: divides the gradient by a running average of its magnitude.
+pub struct RmsProp<'a, T, D: Device> {
+    pub lr: T,
+    pub alpha: T,
+    pub eps: T,
+    pub weight_decay: T,
+    pub momentum: T,
+    states: ParamStates<'a, T, D, 2>,
+}
+
+impl<'a, T: Float, D: Device> RmsProp<'a, T, D> {
+    /// Creates a RMSProp optimizer with `alpha = 0.99` and `eps = 1e-8`.
+    #[inline]
+    pub fn new(lr: T) -> Self {
+        RmsProp {
+            lr,
+            alpha: T::as_generic(0.99),
+            eps: T::as_generic(1e-8),
+            weight_decay: T::zero(),
+            momentum: T::zero(),
+            states: ParamStates::new(),
+        }
+    }
+
+    #[inline]
+    pub fn alpha(mut self, alpha: T) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    #[inline]
+    pub fn eps(mut self, eps: T) -> Self {
+        self.eps = eps;
+        self
+    }
+
+    #[inline]
+    pub fn weight_decay(mut self, weight_decay: T) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
+
+    #[inline]
+    pub fn momentum(mut self, momentum: T) -> Self {
+        self.momentum = momentum;
+        self
+    }
+}
+
+impl<'a, T, D> Optimizer<'a, T, D> for RmsProp<'a, T, D>
+where
+    T: Float + 'static,
+    D: RmsPropStep<T>
+        + MayTapeActions
+        + ZeroGrad<T>
+        + Alloc<T>
+        + OnNewBuffer<T, D>
+        + ClearBuf<T>
+        + 'static,
+{
+    fn step(&mut self, params: Vec<Param<'_, 'a, T, D>>) {
+        let hyper = RmsPropHyper {
+            lr: self.lr,
+            alpha: self.alpha,
+            eps: self.eps,
+            weight_decay: self.weight_decay,
+            momentum: self.momentum,
+        };
+
+        for param in params {
+            let grad = param.param.grad();
+            let [square_avg, momentum_buf] = &mut self.states.next_step(param.param).buffers;
+            param
+                .param
+                .device()
+                .rmsprop_step(&hyper, param.param, grad, square_avg, momentum_buf);
+        }
+    }
+
+    #[inline]
+    fn lr(&self) -> T {
+        self.lr
+    }
+
+    #[inline]
+    fn set_lr(&mut self, lr: T) {
+        self.lr = lr;
+    }
+}
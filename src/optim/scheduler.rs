@@ -0,0 +1,366 @@
+use custos::{prelude::Float, Device};
+
+use super::Optimizer;
+
+/// Adjusts the learning rate of an [`Optimizer`].
+/// Whether a step corresponds to an optimizer step or an epoch is up to the training loop.
+///
+/// # Example
+#[cfg_attr(all(feature = "cpu", feature = "autograd"), doc = "```")]
+#[cfg_attr(not(all(feature = "cpu", feature = "autograd")), doc = "```ignore")]
+/// use sliced::{
+///     optim::{Optimizer, Scheduler, StepLR, SGD},
+///     CPU,
+/// };
+///
+/// let mut sgd = SGD::<f32, CPU<custos::Autograd<custos::Base>>>::new(0.1);
+/// let mut scheduler = StepLR::new(2, 0.5);
+///
+/// for _epoch in 0..4 {
+///     // train ...
+///     scheduler.step(&mut sgd);
+/// }
+///
+/// assert_eq!(sgd.lr(), 0.025);
+/// ```
+pub trait Scheduler<T> {
+    /// Advances the schedule by one step and returns the next learning rate.
+    /// `lr` is the current learning rate of the optimizer.
+    fn next_lr(&mut self, lr: T) -> T;
+
+    /// Advances the schedule by one step and updates the learning rate of `optim`.
+    #[inline]
+    fn step<'a, D: Device>(&mut self, optim: &mut impl Optimizer<'a, T, D>) -> T {
+        let lr = self.next_lr(optim.lr());
+        optim.set_lr(lr);
+        optim.lr()
+    }
+}
+
+/// Multiplies the learning rate by `gamma` every `step_size` steps.
+pub struct StepLR<T> {
+    pub step_size: usize,
+    pub gamma: T,
+    steps: usize,
+    base_lr: Option<T>,
+}
+
+impl<T> StepLR<T> {
+    #[inline]
+    pub fn new(step_size: usize, gamma: T) -> Self {
+        assert!(step_size > 0, "step_size must be greater than zero");
+        StepLR {
+            step_size,
+            gamma,
+            steps: 0,
+            base_lr: None,
+        }
+    }
+}
+
+impl<T: Float> Scheduler<T> for StepLR<T> {
+    fn next_lr(&mut self, lr: T) -> T {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+        self.steps += 1;
+        base_lr * self.gamma.powi((self.steps / self.step_size) as i32)
+    }
+}
+
+/// Multiplies the learning rate by `gamma` every step.
+pub struct ExponentialLR<T> {
+    pub gamma: T,
+}
+
+impl<T> ExponentialLR<T> {
+    #[inline]
+    pub fn new(gamma: T) -> Self {
+        ExponentialLR { gamma }
+    }
+}
+
+impl<T: Float> Scheduler<T> for ExponentialLR<T> {
+    #[inline]
+    fn next_lr(&mut self, lr: T) -> T {
+        lr * self.gamma
+    }
+}
+
+/// Cosine annealing from the initial learning rate down to `eta_min`, restarting after `t_0` steps.
+/// The length of the period is multiplied by `t_mult` after every restart (SGDR).
+pub struct CosineAnnealingWarmRestarts<T> {
+    pub t_0: usize,
+    pub t_mult: usize,
+    pub eta_min: T,
+    t_cur: usize,
+    t_i: usize,
+    base_lr: Option<T>,
+}
+
+impl<T: Float> CosineAnnealingWarmRestarts<T> {
+    #[inline]
+    pub fn new(t_0: usize) -> Self {
+        assert!(t_0 > 0, "t_0 must be greater than zero");
+        CosineAnnealingWarmRestarts {
+            t_0,
+            t_mult: 1,
+            eta_min: T::zero(),
+            t_cur: 0,
+            t_i: t_0,
+            base_lr: None,
+        }
+    }
+
+    #[inline]
+    pub fn t_mult(mut self, t_mult: usize) -> Self {
+        assert!(t_mult > 0, "t_mult must be greater than zero");
+        self.t_mult = t_mult;
+        self
+    }
+
+    #[inline]
+    pub fn eta_min(mut self, eta_min: T) -> Self {
+        self.eta_min = eta_min;
+        self
+    }
+}
+
+impl<T: Float> Scheduler<T> for CosineAnnealingWarmRestarts<T> {
+    fn next_lr(&mut self, lr: T) -> T {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+
+        self.t_cur += 1;
+        if self.t_cur >= self.t_i {
+            self.t_cur -= self.t_i;
+            self.t_i *= self.t_mult;
+        }
+
+        let progress = self.t_cur as f64 / self.t_i as f64;
+        let cos = T::as_generic((1. + (std::f64::consts::PI * progress).cos()) / 2.);
+        self.eta_min + (base_lr - self.eta_min) * cos
+    }
+}
+
+/// Linearly increases the learning rate from `start_factor * lr` to `lr` over `warmup_steps` steps.
+///
+/// Call [`Scheduler::step`] once before training to start at the reduced learning rate.
+pub struct LinearWarmup<T> {
+    pub warmup_steps: usize,
+    pub start_factor: T,
+    steps: usize,
+    base_lr: Option<T>,
+}
+
+impl<T: Float> LinearWarmup<T> {
+    #[inline]
+    pub fn new(warmup_steps: usize) -> Self {
+        assert!(warmup_steps > 0, "warmup_steps must be greater than zero");
+        LinearWarmup {
+            warmup_steps,
+            start_factor: T::zero(),
+            steps: 0,
+            base_lr: None,
+        }
+    }
+
+    #[inline]
+    pub fn start_factor(mut self, start_factor: T) -> Self {
+        self.start_factor = start_factor;
+        self
+    }
+}
+
+impl<T: Float> Scheduler<T> for LinearWarmup<T> {
+    fn next_lr(&mut self, lr: T) -> T {
+        let base_lr = *self.base_lr.get_or_insert(lr);
+
+        // the first step yields the start factor
+        let progress = self.steps.min(self.warmup_steps);
+        self.steps += 1;
+
+        let progress = T::from_usize(progress) / T::from_usize(self.warmup_steps);
+        base_lr * (self.start_factor + (T::one() - self.start_factor) * progress)
+    }
+}
+
+/// Whether a lower or a higher metric is better in [`ReduceLROnPlateau`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateauMode {
+    Min,
+    Max,
+}
+
+/// Multiplies the learning rate by `factor` if the metric has not improved for `patience` steps.
+///
+/// The metric improves if it is better than the best metric by a relative `threshold`.
+/// After a reduction, `cooldown` steps pass before the metric is monitored again.
+pub struct ReduceLROnPlateau<T> {
+    pub mode: PlateauMode,
+    pub factor: T,
+    pub patience: usize,
+    pub threshold: T,
+    pub cooldown: usize,
+    pub min_lr: T,
+    best: Option<T>,
+    bad_steps: usize,
+    cooldown_counter: usize,
+}
+
+impl<T: Float> ReduceLROnPlateau<T> {
+    #[inline]
+    pub fn new(mode: PlateauMode) -> Self {
+        ReduceLROnPlateau {
+            mode,
+            factor: T::as_generic(0.1),
+            patience: 10,
+            threshold: T::as_generic(1e-4),
+            cooldown: 0,
+            min_lr: T::zero(),
+            best: None,
+            bad_steps: 0,
+            cooldown_counter: 0,
+        }
+    }
+
+    #[inline]
+    pub fn factor(mut self, factor: T) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    #[inline]
+    pub fn patience(mut self, patience: usize) -> Self {
+        self.patience = patience;
+        self
+    }
+
+    #[inline]
+    pub fn threshold(mut self, threshold: T) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[inline]
+    pub fn cooldown(mut self, cooldown: usize) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    #[inline]
+    pub fn min_lr(mut self, min_lr: T) -> Self {
+        self.min_lr = min_lr;
+        self
+    }
+
+    fn is_better(&self, metric: T, best: T) -> bool {
+        match self.mode {
+            PlateauMode::Min => metric < best - best.abs() * self.threshold,
+            PlateauMode::Max => metric > best + best.abs() * self.threshold,
+        }
+    }
+
+    /// Records `metric` and returns the next learning rate.
+    pub fn next_lr(&mut self, lr: T, metric: T) -> T {
+        match self.best {
+            Some(best) if !self.is_better(metric, best) => self.bad_steps += 1,
+            _ => {
+                self.best = Some(metric);
+                self.bad_steps = 0;
+            }
+        }
+
+        if self.cooldown_counter > 0 {
+            self.cooldown_counter -= 1;
+            self.bad_steps = 0;
+        }
+
+        if self.bad_steps <= self.patience {
+            return lr;
+        }
+
+        self.cooldown_counter = self.cooldown;
+        self.bad_steps = 0;
+
+        let reduced = lr * self.factor;
+        if reduced > self.min_lr {
+            reduced
+        } else {
+            self.min_lr
+        }
+    }
+
+    /// Records `metric` and updates the learning rate of `optim`.
+    #[inline]
+    pub fn step<'a, D: Device>(&mut self, optim: &mut impl Optimizer<'a, T, D>, metric: T) -> T {
+        let lr = self.next_lr(optim.lr(), metric);
+        optim.set_lr(lr);
+        lr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::roughly_equals;
+
+    use super::{
+        CosineAnnealingWarmRestarts, ExponentialLR, LinearWarmup, PlateauMode, ReduceLROnPlateau,
+        Scheduler, StepLR,
+    };
+
+    fn lrs(scheduler: &mut impl Scheduler<f64>, mut lr: f64, steps: usize) -> Vec<f64> {
+        (0..steps)
+            .map(|_| {
+                lr = scheduler.next_lr(lr);
+                lr
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_step_lr() {
+        let lrs = lrs(&mut StepLR::new(2, 0.5), 1., 5);
+        roughly_equals(&lrs, &[1., 0.5, 0.5, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn test_exponential_lr() {
+        let lrs = lrs(&mut ExponentialLR::new(0.5), 1., 3);
+        roughly_equals(&lrs, &[0.5, 0.25, 0.125]);
+    }
+
+    #[test]
+    fn test_cosine_annealing_warm_restarts() {
+        let mut scheduler = CosineAnnealingWarmRestarts::new(4).t_mult(2).eta_min(0.);
+        let lrs = lrs(&mut scheduler, 1., 8);
+        #[rustfmt::skip]
+        roughly_equals(&lrs, &[
+            0.8535534, 0.5, 0.1464466,
+            1., 0.9619398, 0.8535534, 0.6913417, 0.5,
+        ]);
+    }
+
+    #[test]
+    fn test_linear_warmup() {
+        let mut scheduler = LinearWarmup::new(4).start_factor(0.2);
+        let lrs = lrs(&mut scheduler, 1., 6);
+        roughly_equals(&lrs, &[0.2, 0.4, 0.6, 0.8, 1., 1.]);
+    }
+
+    #[test]
+    fn test_reduce_lr_on_plateau() {
+        let mut scheduler = ReduceLROnPlateau::new(PlateauMode::Min)
+            .factor(0.5)
+            .patience(1)
+            .min_lr(0.2);
+
+        let mut lr = 1.;
+        let lrs = [3., 2., 2., 2., 1., 1., 1., 1., 1.]
+            .into_iter()
+            .map(|metric| {
+                lr = scheduler.next_lr(lr, metric);
+                lr
+            })
+            .collect::<Vec<_>>();
+
+        roughly_equals(&lrs, &[1., 1., 1., 0.5, 0.5, 0.5, 0.25, 0.25, 0.2]);
+    }
+}
@@ -0,0 +1,108 @@
+use custos::{
+    prelude::{ClearBuf, Float},
+    Alloc, Device, MayTapeActions, OnNewBuffer, ZeroGrad,
+};
+
+use crate::{nn::Param, SgdHyper, SgdStep};
+
+use super::{Optimizer, ParamStates};
+
+/// Stochastic gradient descent with optional (Nesterov) momentum and weight decay.
+///
+/// The velocity starts at zero, hence `dampening` also affects the first step.
+pub struct SGD<'a, T, D: Device> {
+    pub lr: T,
+    pub momentum: T,
+    pub dampening: T,
+    pub weight_decay: T,
+    pub nesterov: bool,
+    velocities: ParamStates<'a, T, D, 1>,
+}
+
+impl<'a, T: Float, D: Device> SGD<'a, T, D> {
+    #[inline]
+    pub fn new(lr: T) -> Self {
+        SGD {
+            lr,
+            momentum: T::zero(),
+            dampening: T::zero(),
+            weight_decay: T::zero(),
+            nesterov: false,
+            velocities: ParamStates::new(),
+        }
+    }
+
+    #[inline]
+    pub fn momentum(mut self, momentum: T) -> Self {
+        self.momentum = momentum;
+        self
+    }
+
+    #[inline]
+    pub fn dampening(mut self, dampening: T) -> Self {
+        self.dampening = dampening;
+        self
+    }
+
+    #[inline]
+    pub fn weight_decay(mut self, weight_decay: T) -> Self {
+        self.weight_decay = weight_decay;
+        self
+    }
+
+    /// Uses Nesterov momentum. Requires a non-zero momentum.
+    ///
+    /// # Panics
+    /// [`Optimizer::step`] panics if `nesterov` is set and the momentum is zero.
+    #[inline]
+    pub fn nesterov(mut self, nesterov: bool) -> Self {
+        self.nesterov = nesterov;
+        self
+    }
+}
+
+impl<'a, T, D> Optimizer<'a, T, D> for SGD<'a, T, D>
+where
+    T: Float + 'static,
+    D: SgdStep<T>
+        + MayTapeActions
+        + ZeroGrad<T>
+        + Alloc<T>
+        + OnNewBuffer<T, D>
+        + ClearBuf<T>
+        + 'static,
+{
+    fn step(&mut self, params: Vec<Param<'_, 'a, T, D>>) {
+        assert!(
+            !self.nesterov || self.momentum != T::zero(),
+            "Nesterov momentum requires a non-zero momentum"
+        );
+
+        let hyper = SgdHyper {
+            lr: self.lr,
+            momentum: self.momentum,
+            dampening: self.dampening,
+            weight_decay: self.weight_decay,
+            nesterov: self.nesterov,
+        };
+
+        for param in params {
+            let grad = param.param.grad();
+            let [velocity] = &mut self.velocities.next_step(param.param).buffers;
+            param
+                .param
+                .device()
+                .sgd_step(&hyper, param.param, grad, velocity);
+        }
+    }
+
+    #[inline]
+    fn lr(&self) -> T {
+        self.lr
+    }
+
+    #[inline]
+    fn set_lr(&mut self, lr: T) {
+        self.lr = lr;
+    }
+}
@@ -5,6 +5,8 @@ use std::{
 
 use custos::{Buffer, Combiner, Device, Eval, MayDim2, Resolve, Shape};
 
+use crate::{with_rng, Rng};
+
 mod cpu;
 
 #[cfg(feature = "cpu")]
@@ -37,7 +39,112 @@ pub trait BinaryEWMayGrad<T, S: Shape = (), D: Device = Self>: Device {
 }
 
 pub trait RandOp<T, S: Shape = (), D: Device = Self>: Device {
-    fn rand(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T);
+    /// Fills `x` with uniform values in `[lo, hi)` drawn from `rng`.
+    /// All devices produce the same values for the same generator state.
+    fn rand_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, lo: T, hi: T);
+
+    /// Fills `x` with uniform values in `[lo, hi)` drawn from the default generator of the current thread.
+    /// See [`set_seed`](crate::set_seed).
+    #[inline]
+    fn rand(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
+        with_rng(|rng| self.rand_with(rng, x, lo, hi))
+    }
+}
+
+/// Samples from common non-uniform distributions.
+/// Like [`RandOp`], the value at index `i` only depends on the generator state and `i`.
+pub trait Distributions<T, S: Shape = (), D: Device = Self>: Device {
+    /// Fills `x` with normally distributed values (Box–Muller).
+    fn randn_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, mean: T, std: T);
+
+    /// Fills `x` with normally distributed values that lie in `[lo, hi]`.
+    /// Values outside of the bounds are resampled a few times,
+    /// afterwards the CDF is inverted instead (e.g. for bounds far in the tails).
+    ///
+    /// # Panics
+    /// If `lo >= hi`.
+    fn trunc_normal_with(
+        &self,
+        rng: &mut Rng,
+        x: &mut Buffer<T, D, S>,
+        mean: T,
+        std: T,
+        lo: T,
+        hi: T,
+    );
+
+    /// Fills `x` with ones (with probability `p`) and zeros.
+    fn bernoulli_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, p: T);
+
+    /// Samples one class index per row of `probs` (with `classes` columns) and writes it to `out`.
+    /// The rows of `probs` do not need to be normalized.
+    fn categorical_with(
+        &self,
+        rng: &mut Rng,
+        classes: usize,
+        probs: &Buffer<T, D, S>,
+        out: &mut Buffer<T, D>,
+    );
+
+    #[inline]
+    fn randn(&self, x: &mut Buffer<T, D, S>, mean: T, std: T) {
+        with_rng(|rng| self.randn_with(rng, x, mean, std))
+    }
+
+    #[inline]
+    fn trunc_normal(&self, x: &mut Buffer<T, D, S>, mean: T, std: T, lo: T, hi: T) {
+        with_rng(|rng| self.trunc_normal_with(rng, x, mean, std, lo, hi))
+    }
+
+    #[inline]
+    fn bernoulli(&self, x: &mut Buffer<T, D, S>, p: T) {
+        with_rng(|rng| self.bernoulli_with(rng, x, p))
+    }
+
+    #[inline]
+    fn categorical(&self, classes: usize, probs: &Buffer<T, D, S>, out: &mut Buffer<T, D>) {
+        with_rng(|rng| self.categorical_with(rng, classes, probs, out))
+    }
+}
+
+/// Samples uniformly distributed integers. Works for integer and float buffers.
+pub trait RandInt<T, S: Shape = (), D: Device = Self>: Device {
+    /// Fills `x` with integers in `[lo, hi)`.
+    ///
+    /// # Panics
+    /// If `lo >= hi` or if the range does not fit into 64 bits.
+    fn rand_int_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, lo: T, hi: T);
+
+    #[inline]
+    fn rand_int(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
+        with_rng(|rng| self.rand_int_with(rng, x, lo, hi))
+    }
 }
 
+/// Converts values to and from `i128`, so that the range of [`RandInt`] can be computed without overflowing `T`.
+pub trait WideInt: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_wide_int {
+    ($($t:ty),*) => {
+        $(
+            impl WideInt for $t {
+                #[inline]
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                #[inline]
+                fn from_i128(value: i128) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_wide_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
 //pub trait SumOp
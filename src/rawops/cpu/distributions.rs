@@ -0,0 +1,376 @@
+use std::ops::{Deref, DerefMut};
+
+use custos::{
+    impl_stack,
+    prelude::{Float, Number},
+    Buffer, Device, OnDropBuffer, Shape, CPU,
+};
+
+use crate::{uniform_from_bits, Distributions, RandInt, Rng, WideInt};
+
+#[cfg(feature = "stack")]
+use custos::Stack;
+
+/// Transforms the first two words of `block` to a standard normal value (Box–Muller).
+#[inline]
+pub fn normal_from_block(block: [u32; 4]) -> f64 {
+    // (0, 1] to avoid ln(0)
+    let u1 = 1. - uniform_from_bits(block[0]);
+    let u2 = uniform_from_bits(block[1]);
+    (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos()
+}
+
+/// The number of rejection sampling attempts of [`trunc_normal_slice_with`] before it inverts the CDF instead.
+pub const TRUNC_NORMAL_ATTEMPTS: u32 = 16;
+
+/// The complementary error function, with a relative error below 1.2e-7 (Numerical Recipes `erfcc`).
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let r = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0. {
+        r
+    } else {
+        2. - r
+    }
+}
+
+/// The cumulative distribution function of the standard normal distribution.
+#[inline]
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// The inverse of [`normal_cdf`], with a relative error below 1.2e-9 (Acklam's algorithm).
+pub fn normal_icdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.383577518672690e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549671348740151e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+
+    if p < P_LOW {
+        tail((-2. * p.ln()).sqrt())
+    } else if p <= 1. - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    } else {
+        -tail((-2. * (1. - p).ln()).sqrt())
+    }
+}
+
+/// Maps the uniform value `u` to a standard normal value in `[lo, hi]` by inverting the CDF.
+pub fn trunc_normal_icdf(u: f64, lo: f64, hi: f64) -> f64 {
+    // the CDF rounds to 1 in the right tail, hence the window is mirrored to the left one
+    if lo > 0. {
+        return -trunc_normal_icdf(u, -hi, -lo);
+    }
+    let (cdf_lo, cdf_hi) = (normal_cdf(lo), normal_cdf(hi));
+    // if both CDFs underflow to 0, the result is NaN and min replaces it with the bound closest to the mean
+    normal_icdf(cdf_lo + u * (cdf_hi - cdf_lo)).min(hi).max(lo)
+}
+
+pub fn randn_slice_with<T: Float>(rng: &mut Rng, slice: &mut [T], mean: T, std: T) {
+    for (idx, value) in slice.iter_mut().enumerate() {
+        *value = T::as_generic(normal_from_block(rng.block_at(idx as u64))) * std + mean;
+    }
+    rng.advance(slice.len() as u64);
+}
+
+pub fn trunc_normal_slice_with<T: Float>(
+    rng: &mut Rng,
+    slice: &mut [T],
+    mean: T,
+    std: T,
+    lo: T,
+    hi: T,
+) {
+    assert!(lo < hi, "trunc_normal: lo must be smaller than hi");
+
+    for (idx, value) in slice.iter_mut().enumerate() {
+        // every attempt uses its own sub-stream
+        let sample = (0..TRUNC_NORMAL_ATTEMPTS)
+            .map(|attempt| {
+                T::as_generic(normal_from_block(rng.block_at_with(idx as u64, attempt))) * std
+                    + mean
+            })
+            .find(|sample| *sample >= lo && *sample <= hi);
+
+        *value = sample.unwrap_or_else(|| {
+            // the window holds little mass (e.g. far in a tail), the CDF is inverted instead
+            let u = uniform_from_bits(rng.block_at_with(idx as u64, TRUNC_NORMAL_ATTEMPTS)[0]);
+            let (a, b) = ((lo - mean) / std, (hi - mean) / std);
+            let sample = T::as_generic(trunc_normal_icdf(u, a.as_f64(), b.as_f64())) * std + mean;
+
+            // rounding of the scaling
+            if sample < lo {
+                lo
+            } else if sample > hi {
+                hi
+            } else {
+                sample
+            }
+        });
+    }
+    rng.advance(slice.len() as u64);
+}
+
+pub fn bernoulli_slice_with<T: Float>(rng: &mut Rng, slice: &mut [T], p: T) {
+    for (idx, value) in slice.iter_mut().enumerate() {
+        let uniform = T::as_generic(uniform_from_bits(rng.block_at(idx as u64)[0]));
+        *value = if uniform < p { T::one() } else { T::zero() };
+    }
+    rng.advance(slice.len() as u64);
+}
+
+/// Writes one sampled class index per row of `probs` to `out`.
+pub fn categorical_slice_with<T: Float>(rng: &mut Rng, classes: usize, probs: &[T], out: &mut [T]) {
+    for (idx, (row, out)) in probs.chunks(classes).zip(out.iter_mut()).enumerate() {
+        let total = row.iter().copied().fold(T::zero(), |acc, prob| acc + prob);
+        let threshold = T::as_generic(uniform_from_bits(rng.block_at(idx as u64)[0])) * total;
+
+        let mut cumulative = T::zero();
+        // rounding errors may prevent reaching the threshold
+        let mut class = classes - 1;
+        for (current, prob) in row.iter().enumerate() {
+            cumulative = cumulative + *prob;
+            if threshold < cumulative {
+                class = current;
+                break;
+            }
+        }
+        *out = T::from_usize(class);
+    }
+    rng.advance(out.len() as u64);
+}
+
+/// Fills `slice` with integers in `[lo, hi)`.
+/// Uses a 64-bit multiply-shift reduction, therefore the bias is negligible.
+/// The range is computed in `i128`, hence signed and narrow types do not overflow.
+pub fn rand_int_slice_with<T: WideInt>(rng: &mut Rng, slice: &mut [T], lo: T, hi: T) {
+    let (lo, hi) = (lo.to_i128(), hi.to_i128());
+    assert!(lo < hi, "rand_int: lo must be smaller than hi");
+    let range = u64::try_from(hi - lo).expect("rand_int: the range must fit into 64 bits") as u128;
+
+    for (idx, value) in slice.iter_mut().enumerate() {
+        let block = rng.block_at(idx as u64);
+        let bits = block[0] as u128 | (block[1] as u128) << 32;
+        *value = T::from_i128(lo + ((bits * range) >> 64) as i128);
+    }
+    rng.advance(slice.len() as u64);
+}
+
+#[impl_stack]
+impl<Mods: OnDropBuffer, T, D, S> Distributions<T, S, D> for CPU<Mods>
+where
+    T: Float,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+    D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn randn_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, mean: T, std: T) {
+        randn_slice_with(rng, x, mean, std)
+    }
+
+    #[inline]
+    fn trunc_normal_with(
+        &self,
+        rng: &mut Rng,
+        x: &mut Buffer<T, D, S>,
+        mean: T,
+        std: T,
+        lo: T,
+        hi: T,
+    ) {
+        trunc_normal_slice_with(rng, x, mean, std, lo, hi)
+    }
+
+    #[inline]
+    fn bernoulli_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, p: T) {
+        bernoulli_slice_with(rng, x, p)
+    }
+
+    #[inline]
+    fn categorical_with(
+        &self,
+        rng: &mut Rng,
+        classes: usize,
+        probs: &Buffer<T, D, S>,
+        out: &mut Buffer<T, D>,
+    ) {
+        categorical_slice_with(rng, classes, probs, out)
+    }
+}
+
+#[impl_stack]
+impl<Mods: OnDropBuffer, T, D, S> RandInt<T, S, D> for CPU<Mods>
+where
+    T: Number + WideInt,
+    S: Shape,
+    D: Device,
+    D::Base<T, S>: Deref<Target = [T]> + DerefMut,
+{
+    #[inline]
+    fn rand_int_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
+        rand_int_slice_with(rng, x, lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bernoulli_slice_with, categorical_slice_with, normal_cdf, normal_icdf, rand_int_slice_with,
+        randn_slice_with, trunc_normal_slice_with, Rng,
+    };
+
+    #[test]
+    fn test_randn_slice_moments() {
+        let mut x = vec![0f64; 20_000];
+        randn_slice_with(&mut Rng::with_seed(0), &mut x, 2., 3.);
+
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        let var = x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / x.len() as f64;
+
+        assert!((mean - 2.).abs() < 0.1, "mean: {mean}");
+        assert!((var.sqrt() - 3.).abs() < 0.1, "std: {}", var.sqrt());
+    }
+
+    #[test]
+    fn test_trunc_normal_slice_bounds() {
+        let mut x = vec![0f32; 10_000];
+        trunc_normal_slice_with(&mut Rng::with_seed(0), &mut x, 0., 1., -0.5, 0.5);
+        assert!(x.iter().all(|x| (-0.5..=0.5).contains(x)));
+    }
+
+    #[test]
+    fn test_trunc_normal_slice_no_mass_on_bounds() {
+        // P(x in [2, 3]) ~ 2%, clamping would put most of the values on the bounds
+        let mut x = vec![0f64; 1000];
+        trunc_normal_slice_with(&mut Rng::with_seed(0), &mut x, 0., 1., 2., 3.);
+        assert!(x.iter().all(|&x| x > 2. && x < 3.));
+
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        assert!((mean - 2.32).abs() < 0.05, "mean: {mean}");
+    }
+
+    #[test]
+    fn test_trunc_normal_slice_far_tail() {
+        // P(x in [8, 9]) ~ 6e-16, rejection sampling alone would not terminate
+        let mut x = vec![0f64; 1000];
+        trunc_normal_slice_with(&mut Rng::with_seed(0), &mut x, 0., 1., 8., 9.);
+        assert!(x.iter().all(|x| (8. ..=9.).contains(x)));
+
+        // the mass is concentrated at the lower bound, the mean is ~ 8 + 1 / 8
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        assert!((mean - 8.12).abs() < 0.02, "mean: {mean}");
+
+        let mut x = vec![0f32; 1000];
+        trunc_normal_slice_with(&mut Rng::with_seed(0), &mut x, 0., 1., -41., -40.);
+        assert!(x.iter().all(|x| (-41. ..=-40.).contains(x)));
+
+        let mean = x.iter().sum::<f32>() / x.len() as f32;
+        assert!((mean + 40.).abs() < 0.1, "mean: {mean}");
+    }
+
+    #[test]
+    fn test_normal_icdf() {
+        for x in [-5., -2., -0.3, 0., 1., 3.] {
+            assert!((normal_icdf(normal_cdf(x)) - x).abs() < 1e-5, "x: {x}");
+        }
+    }
+
+    #[test]
+    fn test_bernoulli_slice() {
+        let mut x = vec![0f32; 10_000];
+        bernoulli_slice_with(&mut Rng::with_seed(0), &mut x, 0.3);
+
+        assert!(x.iter().all(|&x| x == 0. || x == 1.));
+        let ones = x.iter().sum::<f32>();
+        assert!((2700. ..3300.).contains(&ones), "ones: {ones}");
+    }
+
+    #[test]
+    fn test_rand_int_slice() {
+        let mut x = vec![0i32; 10_000];
+        rand_int_slice_with(&mut Rng::with_seed(0), &mut x, -3, 4);
+
+        assert!(x.iter().all(|x| (-3..4).contains(x)));
+        for val in -3..4 {
+            assert!(x.contains(&val));
+        }
+    }
+
+    #[test]
+    fn test_rand_int_slice_narrow_full_range() {
+        let mut x = vec![0i8; 10_000];
+        rand_int_slice_with(&mut Rng::with_seed(0), &mut x, -128, 127);
+        assert!(x.contains(&-128) && x.contains(&126));
+        assert!(!x.contains(&127));
+
+        let mut x = vec![0u64; 1000];
+        rand_int_slice_with(&mut Rng::with_seed(0), &mut x, u64::MAX - 10, u64::MAX);
+        assert!(x.iter().all(|&x| x >= u64::MAX - 10 && x < u64::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rand_int_slice_empty_range() {
+        rand_int_slice_with(&mut Rng::with_seed(0), &mut [0u8; 4], 3, 3);
+    }
+
+    #[test]
+    fn test_categorical_slice() {
+        #[rustfmt::skip]
+        let probs = [
+            0., 1., 0.,
+            0., 0., 5.,
+            1., 0., 0.,
+        ];
+        let mut out = [0f32; 3];
+        categorical_slice_with(&mut Rng::with_seed(0), 3, &probs, &mut out);
+        assert_eq!(out, [1., 2., 0.]);
+    }
+}
@@ -1,6 +1,9 @@
 mod col_op_grad;
+mod distributions;
 mod l2_norm;
 mod rand;
 
 pub use col_op_grad::*;
+pub use distributions::*;
 pub use l2_norm::*;
+pub use rand::*;
@@ -2,13 +2,22 @@ use std::ops::{Deref, DerefMut};
 
 use custos::{prelude::Float, Buffer, Device, OnDropBuffer, Shape, CPU};
 
-use crate::RandOp;
+use crate::{uniform_from_bits, with_rng, RandOp, Rng};
 
+/// Fills `slice` with uniform values in `[lo, hi)` using the default generator of the current thread.
+#[inline]
 pub fn rand_slice<T: PartialOrd + Copy + Float>(slice: &mut [T], lo: T, hi: T) {
-    let rng = fastrand::Rng::new();
-    for value in slice {
-        *value = T::as_generic(rng.f64()) * (hi - (lo)) + (lo);
+    with_rng(|rng| rand_slice_with(rng, slice, lo, hi))
+}
+
+/// Fills `slice` with uniform values in `[lo, hi)`.
+/// The value at index `i` is derived from the counter block `rng.counter() + i`.
+pub fn rand_slice_with<T: PartialOrd + Copy + Float>(rng: &mut Rng, slice: &mut [T], lo: T, hi: T) {
+    for (idx, value) in slice.iter_mut().enumerate() {
+        let uniform = T::as_generic(uniform_from_bits(rng.block_at(idx as u64)[0]));
+        *value = uniform * (hi - lo) + lo;
     }
+    rng.advance(slice.len() as u64);
 }
 
 //#[impl_stack]
@@ -20,7 +29,30 @@ where
     D::Base<T, S>: Deref<Target = [T]> + DerefMut,
 {
     #[inline]
-    fn rand(&self, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
-        rand_slice(x, lo, hi)
+    fn rand_with(&self, rng: &mut Rng, x: &mut Buffer<T, D, S>, lo: T, hi: T) {
+        rand_slice_with(rng, x, lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{rand_slice_with, Rng};
+
+    #[test]
+    fn test_rand_slice_with_seed() {
+        let mut lhs = [0f32; 100];
+        let mut rhs = [0f32; 100];
+
+        let mut rng = Rng::with_seed(3);
+        rand_slice_with(&mut rng, &mut lhs, -2., 2.);
+        assert_eq!(rng.counter(), 100);
+
+        rand_slice_with(&mut Rng::with_seed(3), &mut rhs, -2., 2.);
+        assert_eq!(lhs, rhs);
+        assert!(lhs.iter().all(|val| (-2. ..2.).contains(val)));
+
+        // subsequent fills continue the stream
+        rand_slice_with(&mut rng, &mut rhs, -2., 2.);
+        assert_ne!(lhs, rhs);
     }
 }
@@ -0,0 +1,541 @@
+use custos::{
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::{Float, Number},
+    Buffer, CDatatype, OnDropBuffer, OpenCL,
+};
+
+use crate::{Distributions, RandInt, Rng, WideInt, CL_PHILOX4X32, TRUNC_NORMAL_ATTEMPTS};
+
+impl<T: Float + CDatatype, Mods: OnDropBuffer + 'static> Distributions<T> for OpenCL<Mods> {
+    #[inline]
+    fn randn_with(&self, rng: &mut Rng, x: &mut Buffer<T, Self>, mean: T, std: T) {
+        cl_randn(self, rng, x, mean, std).unwrap();
+    }
+
+    #[inline]
+    fn trunc_normal_with(
+        &self,
+        rng: &mut Rng,
+        x: &mut Buffer<T, Self>,
+        mean: T,
+        std: T,
+        lo: T,
+        hi: T,
+    ) {
+        cl_trunc_normal(self, rng, x, mean, std, lo, hi).unwrap();
+    }
+
+    #[inline]
+    fn bernoulli_with(&self, rng: &mut Rng, x: &mut Buffer<T, Self>, p: T) {
+        cl_bernoulli(self, rng, x, p).unwrap();
+    }
+
+    #[inline]
+    fn categorical_with(
+        &self,
+        rng: &mut Rng,
+        classes: usize,
+        probs: &Buffer<T, Self>,
+        out: &mut Buffer<T, Self>,
+    ) {
+        cl_categorical(self, rng, classes, probs, out).unwrap();
+    }
+}
+
+impl<T: Number + WideInt + CDatatype, Mods: OnDropBuffer + 'static> RandInt<T> for OpenCL<Mods> {
+    #[inline]
+    fn rand_int_with(&self, rng: &mut Rng, x: &mut Buffer<T, Self>, lo: T, hi: T) {
+        cl_rand_int(self, rng, x, lo, hi).unwrap();
+    }
+}
+
+/// Philox helpers shared by the distribution kernels. Matches [`Rng::block_at_with`] and [`normal_from_block`](crate::normal_from_block).
+/// The helpers compute in `real`, which is `double` for `double` buffers and `float` otherwise.
+/// Hence, devices without `cl_khr_fp64` are supported, but `float` results differ slightly from the CPU ones.
+/// `@` is replaced by the suffix of the `real` literals.
+const CL_DISTRIBUTION_HELPERS: &str = "
+    uint4 block_at(uint key0, uint key1, uint ctr_lo, uint ctr_hi, size_t offset, uint sub) {
+        ulong ctr = ((ulong) ctr_hi << 32 | ctr_lo) + offset;
+        return philox4x32((uint4) ((uint) ctr, (uint) (ctr >> 32), sub, 0), (uint2) (key0, key1));
+    }
+
+    real uniform_from_bits(uint bits) {
+        return (real) (bits >> 8) * (1.0@ / 16777216.0@);
+    }
+
+    real normal_from_block(uint4 block) {
+        // (0, 1] to avoid log(0)
+        real u1 = 1.0@ - uniform_from_bits(block.x);
+        real u2 = uniform_from_bits(block.y);
+        return sqrt(-2.0@ * log(u1)) * cos(2.0@ * REAL_PI * u2);
+    }
+
+    real normal_cdf(real x) {
+        return 0.5@ * erfc(-x / sqrt(2.0@));
+    }
+
+    // Acklam's algorithm, see normal_icdf on the CPU
+    real normal_icdf_tail(real q) {
+        return (((((-7.784894002430293e-03@ * q - 3.223964580411365e-01@) * q - 2.400758277161838e+00@) * q
+            - 2.549671348740151e+00@) * q + 4.374664141464968e+00@) * q + 2.938163982698783e+00@)
+            / ((((7.784695709041462e-03@ * q + 3.224671290700398e-01@) * q + 2.445134137142996e+00@) * q
+            + 3.754408661907416e+00@) * q + 1.0@);
+    }
+
+    real normal_icdf(real p) {
+        if (p < 0.02425@) {
+            return normal_icdf_tail(sqrt(-2.0@ * log(p)));
+        }
+        if (p > 1.0@ - 0.02425@) {
+            return -normal_icdf_tail(sqrt(-2.0@ * log(1.0@ - p)));
+        }
+        real q = p - 0.5@;
+        real r = q * q;
+        return (((((-3.969683028665376e+01@ * r + 2.209460984245205e+02@) * r - 2.759285104469687e+02@) * r
+            + 1.383577518672690e+02@) * r - 3.066479806614716e+01@) * r + 2.506628277459239e+00@) * q
+            / (((((-5.447609879822406e+01@ * r + 1.615858368580409e+02@) * r - 1.556989798598866e+02@) * r
+            + 6.680131188771972e+01@) * r - 1.328068155288572e+01@) * r + 1.0@);
+    }
+
+    // see trunc_normal_icdf on the CPU
+    real trunc_normal_icdf(real u, real lo, real hi) {
+        // the CDF rounds to 1 in the right tail, hence the window is mirrored to the left one
+        real sign = 1.0@;
+        if (lo > 0.0@) {
+            real tmp = lo;
+            lo = -hi;
+            hi = -tmp;
+            sign = -1.0@;
+        }
+        real cdf_lo = normal_cdf(lo);
+        real cdf_hi = normal_cdf(hi);
+        // if both CDFs underflow to 0, the result is NaN and fmin replaces it with the bound closest to the mean
+        return sign * fmax(fmin(normal_icdf(cdf_lo + u * (cdf_hi - cdf_lo)), hi), lo);
+    }
+";
+
+/// Returns the kernel prelude with the Philox block function and the helpers for buffers of `dtype`.
+fn distribution_prelude(dtype: &str) -> String {
+    let (extension, real, suffix, pi) = match dtype {
+        "double" => (
+            "#pragma OPENCL EXTENSION cl_khr_fp64 : enable",
+            "double",
+            "",
+            "M_PI",
+        ),
+        _ => ("", "float", "f", "M_PI_F"),
+    };
+    let helpers = CL_DISTRIBUTION_HELPERS.replace('@', suffix);
+
+    format!(
+        "
+        {extension}
+        // fused multiply-adds would change the results compared to the CPU
+        #pragma OPENCL FP_CONTRACT OFF
+
+        typedef {real} real;
+        #define REAL_PI {pi}
+
+        {CL_PHILOX4X32}
+        {helpers}
+    "
+    )
+}
+
+/// Fills `x` with normally distributed values on the device.
+/// Produces the same values as [`randn_slice_with`](crate::randn_slice_with) up to rounding, `float` buffers are sampled in single precision.
+pub fn cl_randn<T: CDatatype>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    x: &mut CLPtr<T>,
+    mean: T,
+    std: T,
+) -> custos::Result<()> {
+    if x.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        {prelude}
+
+        __kernel void randn_philox(__global {dtype}* x, uint key0, uint key1, uint ctr_lo, uint ctr_hi, {dtype} mean, {dtype} std) {{
+            size_t id = get_global_id(0);
+            uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, 0);
+            x[id] = ({dtype}) normal_from_block(block) * std + mean;
+        }}
+    ",
+        prelude = distribution_prelude(T::C_DTYPE_STR),
+        dtype = T::C_DTYPE_STR
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [x.len(), 0, 0],
+        None,
+        &[
+            x,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &mean,
+            &std,
+        ],
+    )?;
+    rng.advance(x.len() as u64);
+    Ok(())
+}
+
+/// Fills `x` with normally distributed values in `[lo, hi]` on the device.
+/// Like [`trunc_normal_slice_with`](crate::trunc_normal_slice_with), values outside of the bounds are resampled from the next sub-stream
+/// and the CDF is inverted after [`TRUNC_NORMAL_ATTEMPTS`] attempts.
+pub fn cl_trunc_normal<T: CDatatype + PartialOrd>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    x: &mut CLPtr<T>,
+    mean: T,
+    std: T,
+    lo: T,
+    hi: T,
+) -> custos::Result<()> {
+    assert!(lo < hi, "trunc_normal: lo must be smaller than hi");
+
+    if x.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        {prelude}
+
+        __kernel void trunc_normal_philox(__global {dtype}* x, uint key0, uint key1, uint ctr_lo, uint ctr_hi, {dtype} mean, {dtype} std, {dtype} lo, {dtype} hi) {{
+            size_t id = get_global_id(0);
+
+            for (uint attempt = 0; attempt < {attempts}; attempt++) {{
+                uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, attempt);
+                {dtype} sample = ({dtype}) normal_from_block(block) * std + mean;
+                if (sample >= lo && sample <= hi) {{
+                    x[id] = sample;
+                    return;
+                }}
+            }}
+
+            // the window holds little mass (e.g. far in a tail), the CDF is inverted instead
+            uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, {attempts});
+            real u = uniform_from_bits(block.x);
+            {dtype} sample = ({dtype}) trunc_normal_icdf(u, (real) ((lo - mean) / std), (real) ((hi - mean) / std)) * std + mean;
+            // rounding of the scaling
+            x[id] = sample < lo ? lo : (sample > hi ? hi : sample);
+        }}
+    ",
+        prelude = distribution_prelude(T::C_DTYPE_STR),
+        dtype = T::C_DTYPE_STR,
+        attempts = TRUNC_NORMAL_ATTEMPTS
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [x.len(), 0, 0],
+        None,
+        &[
+            x,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &mean,
+            &std,
+            &lo,
+            &hi,
+        ],
+    )?;
+    rng.advance(x.len() as u64);
+    Ok(())
+}
+
+/// Fills `x` with ones (with probability `p`) and zeros on the device.
+/// Produces the same values as [`bernoulli_slice_with`](crate::bernoulli_slice_with).
+pub fn cl_bernoulli<T: CDatatype>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    x: &mut CLPtr<T>,
+    p: T,
+) -> custos::Result<()> {
+    if x.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        {prelude}
+
+        __kernel void bernoulli_philox(__global {dtype}* x, uint key0, uint key1, uint ctr_lo, uint ctr_hi, {dtype} p) {{
+            size_t id = get_global_id(0);
+            uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, 0);
+            {dtype} uniform = ({dtype}) (block.x >> 8) * (({dtype}) 1.0 / ({dtype}) 16777216.0);
+            x[id] = uniform < p ? 1 : 0;
+        }}
+    ",
+        prelude = distribution_prelude(T::C_DTYPE_STR),
+        dtype = T::C_DTYPE_STR
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [x.len(), 0, 0],
+        None,
+        &[
+            x,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &p,
+        ],
+    )?;
+    rng.advance(x.len() as u64);
+    Ok(())
+}
+
+/// Writes one sampled class index per row of `probs` (with `classes` columns) to `out` on the device.
+/// Produces the same values as [`categorical_slice_with`](crate::categorical_slice_with).
+pub fn cl_categorical<T: CDatatype>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    classes: usize,
+    probs: &CLPtr<T>,
+    out: &mut CLPtr<T>,
+) -> custos::Result<()> {
+    if out.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        {prelude}
+
+        __kernel void categorical_philox(__global const {dtype}* probs, __global {dtype}* out, uint key0, uint key1, uint ctr_lo, uint ctr_hi, long classes) {{
+            size_t id = get_global_id(0);
+            __global const {dtype}* row = probs + id * classes;
+
+            {dtype} total = 0;
+            for (long current = 0; current < classes; current++) {{
+                total += row[current];
+            }}
+
+            uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, 0);
+            {dtype} uniform = ({dtype}) (block.x >> 8) * (({dtype}) 1.0 / ({dtype}) 16777216.0);
+            {dtype} threshold = uniform * total;
+
+            {dtype} cumulative = 0;
+            // rounding errors may prevent reaching the threshold
+            long chosen = classes - 1;
+            for (long current = 0; current < classes; current++) {{
+                cumulative += row[current];
+                if (threshold < cumulative) {{
+                    chosen = current;
+                    break;
+                }}
+            }}
+            out[id] = ({dtype}) chosen;
+        }}
+    ",
+        prelude = distribution_prelude(T::C_DTYPE_STR),
+        dtype = T::C_DTYPE_STR
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [out.len(), 0, 0],
+        None,
+        &[
+            probs,
+            out,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &classes,
+        ],
+    )?;
+    rng.advance(out.len() as u64);
+    Ok(())
+}
+
+/// Fills `x` with integers in `[lo, hi)` on the device.
+/// Produces the same values as [`rand_int_slice_with`](crate::rand_int_slice_with).
+pub fn cl_rand_int<T: CDatatype + WideInt>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    x: &mut CLPtr<T>,
+    lo: T,
+    hi: T,
+) -> custos::Result<()> {
+    let (lo, hi) = (lo.to_i128(), hi.to_i128());
+    assert!(lo < hi, "rand_int: lo must be smaller than hi");
+    let range = u64::try_from(hi - lo).expect("rand_int: the range must fit into 64 bits");
+
+    if x.len() == 0 {
+        return Ok(());
+    }
+
+    // the sum wraps in 64 bits, integers keep the lower bits, floats have to be converted from the signed value
+    let value = match T::C_DTYPE_STR {
+        "float" | "double" => "(long) value",
+        _ => "value",
+    };
+
+    let src = format!(
+        "
+        {prelude}
+
+        __kernel void rand_int_philox(__global {dtype}* x, uint key0, uint key1, uint ctr_lo, uint ctr_hi, ulong lo, ulong range) {{
+            size_t id = get_global_id(0);
+            uint4 block = block_at(key0, key1, ctr_lo, ctr_hi, id, 0);
+            ulong bits = (ulong) block.y << 32 | block.x;
+            ulong value = lo + mul_hi(bits, range);
+            x[id] = ({dtype}) ({value});
+        }}
+    ",
+        prelude = distribution_prelude(T::C_DTYPE_STR),
+        dtype = T::C_DTYPE_STR
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [x.len(), 0, 0],
+        None,
+        &[
+            x,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &(lo as u64 as usize),
+            &(range as usize),
+        ],
+    )?;
+    rng.advance(x.len() as u64);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{
+        categorical_slice_with, cl_categorical, cl_rand_int, cl_randn, cl_trunc_normal,
+        rand_int_slice_with, randn_slice_with, trunc_normal_slice_with, Rng,
+    };
+
+    #[test]
+    fn test_cl_randn_matches_cpu() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut rng = Rng::with_seed(3);
+        let mut cpu_rng = rng;
+
+        let mut x = Buffer::<f32, _>::new(&device, 1000);
+        cl_randn(&device, &mut rng, &mut x, 1., 2.)?;
+
+        let mut expected = vec![0.; 1000];
+        randn_slice_with(&mut cpu_rng, &mut expected, 1., 2.);
+
+        // computed in float on the device
+        for (actual, expected) in x.read().iter().zip(&expected) {
+            assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+        }
+        assert_eq!(rng, cpu_rng);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_randn_f64_matches_cpu() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut x = Buffer::<f64, _>::new(&device, 1000);
+        cl_randn(&device, &mut Rng::with_seed(3), &mut x, 1., 2.)?;
+
+        let mut expected = vec![0.; 1000];
+        randn_slice_with(&mut Rng::with_seed(3), &mut expected, 1., 2.);
+
+        for (actual, expected) in x.read().iter().zip(&expected) {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_trunc_normal_far_tail() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut x = Buffer::<f32, _>::new(&device, 1000);
+        cl_trunc_normal(&device, &mut Rng::with_seed(2), &mut x, 0., 1., 8., 9.)?;
+
+        let mut expected = vec![0.; 1000];
+        trunc_normal_slice_with(&mut Rng::with_seed(2), &mut expected, 0., 1., 8., 9.);
+
+        for (actual, expected) in x.read().iter().zip(&expected) {
+            assert!((8. ..=9.).contains(actual));
+            assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_rand_int_matches_cpu() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        let mut x = Buffer::<i32, _>::new(&device, 1000);
+        cl_rand_int(&device, &mut Rng::with_seed(1), &mut x, -7, 12)?;
+
+        let mut expected = vec![0; 1000];
+        rand_int_slice_with(&mut Rng::with_seed(1), &mut expected, -7, 12);
+        assert_eq!(x.read(), expected);
+
+        let mut x = Buffer::<f32, _>::new(&device, 1000);
+        cl_rand_int(&device, &mut Rng::with_seed(1), &mut x, -7., 12.)?;
+
+        let mut expected = vec![0.; 1000];
+        rand_int_slice_with(&mut Rng::with_seed(1), &mut expected, -7., 12.);
+        assert_eq!(x.read(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cl_categorical_matches_cpu() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        #[rustfmt::skip]
+        let probs = [
+            0.2, 0.3, 0.5,
+            1., 1., 1.,
+            0., 0., 2.,
+            0.7, 0.1, 0.2,
+        ];
+        let cl_probs = Buffer::from((&device, probs));
+        let mut out = Buffer::<f32, _>::new(&device, 4);
+        cl_categorical(&device, &mut Rng::with_seed(5), 3, &cl_probs, &mut out)?;
+
+        let mut expected = [0.; 4];
+        categorical_slice_with(&mut Rng::with_seed(5), 3, &probs, &mut expected);
+        assert_eq!(out.read(), expected);
+        Ok(())
+    }
+}
@@ -1,2 +1,4 @@
+mod distributions;
 mod rand;
+pub use distributions::*;
 pub use rand::*;
@@ -1,13 +1,112 @@
 use custos::{
-    prelude::{cpu_exec_unary_may_unified_mut, Float},
-    Buffer, OnDropBuffer, OpenCL,
+    opencl::{CLDevice, CLPtr, KernelLaunch},
+    prelude::Float,
+    Buffer, CDatatype, OnDropBuffer, OpenCL,
 };
 
-use crate::RandOp;
+use crate::{RandOp, Rng};
 
-impl<T: Float, Mods: OnDropBuffer + 'static> RandOp<T> for OpenCL<Mods> {
+impl<T: Float + CDatatype, Mods: OnDropBuffer + 'static> RandOp<T> for OpenCL<Mods> {
     #[inline]
-    fn rand(&self, x: &mut Buffer<T, Self>, lo: T, hi: T) {
-        cpu_exec_unary_may_unified_mut(self, x, |cpu, x| cpu.rand(x, lo, hi)).unwrap();
+    fn rand_with(&self, rng: &mut Rng, x: &mut Buffer<T, Self>, lo: T, hi: T) {
+        cl_rand(self, rng, x, lo, hi).unwrap();
+    }
+}
+
+/// The Philox4x32-10 block function as OpenCL C. Matches [`philox4x32`](crate::philox4x32).
+pub const CL_PHILOX4X32: &str = "
+    uint4 philox4x32(uint4 ctr, uint2 key) {
+        for (int round = 0; round < 10; round++) {
+            if (round > 0) {
+                key.x += 0x9E3779B9;
+                key.y += 0xBB67AE85;
+            }
+            uint hi0 = mul_hi((uint) 0xD2511F53, ctr.x);
+            uint lo0 = 0xD2511F53 * ctr.x;
+            uint hi1 = mul_hi((uint) 0xCD9E8D57, ctr.z);
+            uint lo1 = 0xCD9E8D57 * ctr.z;
+            ctr = (uint4) (hi1 ^ ctr.y ^ key.x, lo1, hi0 ^ ctr.w ^ key.y, lo0);
+        }
+        return ctr;
+    }
+";
+
+/// Fills `x` with uniform values in `[lo, hi)` on the device.
+/// Produces the same values as [`rand_slice_with`](crate::rand_slice_with) for the same generator state.
+pub fn cl_rand<T: CDatatype>(
+    device: &CLDevice,
+    rng: &mut Rng,
+    x: &mut CLPtr<T>,
+    lo: T,
+    hi: T,
+) -> custos::Result<()> {
+    if x.len() == 0 {
+        return Ok(());
+    }
+
+    let src = format!(
+        "
+        // fused multiply-adds would change the results compared to the CPU
+        #pragma OPENCL FP_CONTRACT OFF
+
+        {CL_PHILOX4X32}
+
+        __kernel void rand_philox(__global {dtype}* x, uint key0, uint key1, uint ctr_lo, uint ctr_hi, {dtype} lo, {dtype} hi) {{
+            size_t id = get_global_id(0);
+            ulong ctr = ((ulong) ctr_hi << 32 | ctr_lo) + id;
+
+            uint4 block = philox4x32((uint4) ((uint) ctr, (uint) (ctr >> 32), 0, 0), (uint2) (key0, key1));
+            {dtype} uniform = ({dtype}) (block.x >> 8) * (({dtype}) 1.0 / ({dtype}) 16777216.0);
+            x[id] = uniform * (hi - lo) + lo;
+        }}
+    ",
+        dtype = T::C_DTYPE_STR
+    );
+
+    let [key0, key1] = rng.key();
+    let counter = rng.counter();
+
+    device.launch_kernel(
+        &src,
+        [x.len(), 0, 0],
+        None,
+        &[
+            x,
+            &key0,
+            &key1,
+            &(counter as u32),
+            &((counter >> 32) as u32),
+            &lo,
+            &hi,
+        ],
+    )?;
+    rng.advance(x.len() as u64);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use custos::{Buffer, OpenCL};
+
+    use crate::{cl_rand, rand_slice_with, Rng};
+
+    #[test]
+    fn test_cl_rand_matches_cpu() -> custos::Result<()> {
+        let device = OpenCL::<custos::Base>::new(0)?;
+
+        // crosses the boundary of the lower counter word
+        let mut rng = Rng::with_seed(u64::MAX - 3);
+        rng.advance(u32::MAX as u64 - 10);
+        let mut cpu_rng = rng;
+
+        let mut x = Buffer::<f32, _>::new(&device, 100);
+        cl_rand(&device, &mut rng, &mut x, -1., 3.)?;
+
+        let mut expected = vec![0.; 100];
+        rand_slice_with(&mut cpu_rng, &mut expected, -1., 3.);
+
+        assert_eq!(x.read(), expected);
+        assert_eq!(rng, cpu_rng);
+        Ok(())
     }
 }
@@ -0,0 +1,284 @@
+//! A seedable, counter-based random number generator (Philox4x32-10).
+//!
+//! The `n`-th value of a fill only depends on the seed and the counter `counter + n`.
+//! Hence, values can be generated in parallel and every device produces the same sequence for a given seed.
+
+use core::cell::RefCell;
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+const PHILOX_M0: u32 = 0xD251_1F53;
+const PHILOX_M1: u32 = 0xCD9E_8D57;
+const PHILOX_W0: u32 = 0x9E37_79B9;
+const PHILOX_W1: u32 = 0xBB67_AE85;
+
+/// Converts the upper 24 bits of `bits` to a float in `[0, 1)`.
+/// 24 bits are exactly representable by `f32` and `f64`, therefore both types share one stream.
+#[inline]
+pub fn uniform_from_bits(bits: u32) -> f64 {
+    (bits >> 8) as f64 * (1. / 16_777_216.)
+}
+
+#[inline]
+fn mulhilo(a: u32, b: u32) -> (u32, u32) {
+    let product = a as u64 * b as u64;
+    ((product >> 32) as u32, product as u32)
+}
+
+/// Computes one block of Philox4x32-10.
+///
+/// # Example
+/// ```
+/// use sliced::philox4x32;
+///
+/// assert_eq!(
+///     philox4x32([0; 4], [0; 2]),
+///     [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+/// );
+/// ```
+pub fn philox4x32(mut ctr: [u32; 4], mut key: [u32; 2]) -> [u32; 4] {
+    for round in 0..10 {
+        if round > 0 {
+            key[0] = key[0].wrapping_add(PHILOX_W0);
+            key[1] = key[1].wrapping_add(PHILOX_W1);
+        }
+        let (hi0, lo0) = mulhilo(PHILOX_M0, ctr[0]);
+        let (hi1, lo1) = mulhilo(PHILOX_M1, ctr[2]);
+        ctr = [hi1 ^ ctr[1] ^ key[0], lo1, hi0 ^ ctr[3] ^ key[1], lo0];
+    }
+    ctr
+}
+
+/// A seedable random number generator.
+/// Every generated value consumes one counter block of four 32-bit words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rng {
+    seed: u64,
+    counter: u64,
+}
+
+impl Rng {
+    /// Creates a generator that starts at the beginning of the stream of `seed`.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        Rng { seed, counter: 0 }
+    }
+
+    /// Creates a generator with a seed that differs between runs.
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Rng::with_seed(hasher.finish())
+    }
+
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the index of the next counter block.
+    #[inline]
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Restarts the stream with `seed`.
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        *self = Rng::with_seed(seed);
+    }
+
+    /// Skips `blocks` counter blocks.
+    #[inline]
+    pub fn advance(&mut self, blocks: u64) {
+        self.counter = self.counter.wrapping_add(blocks);
+    }
+
+    #[inline]
+    pub fn key(&self) -> [u32; 2] {
+        [self.seed as u32, (self.seed >> 32) as u32]
+    }
+
+    /// Returns the block at `counter() + offset` without advancing the generator.
+    #[inline]
+    pub fn block_at(&self, offset: u64) -> [u32; 4] {
+        self.block_at_with(offset, 0)
+    }
+
+    /// Returns the block at `counter() + offset` of the sub-stream `sub`.
+    /// Sub-streams provide further values for one index, e.g. for rejection sampling.
+    #[inline]
+    pub fn block_at_with(&self, offset: u64, sub: u32) -> [u32; 4] {
+        let ctr = self.counter.wrapping_add(offset);
+        philox4x32([ctr as u32, (ctr >> 32) as u32, sub, 0], self.key())
+    }
+
+    /// Returns the next block of four random words.
+    #[inline]
+    pub fn next_block(&mut self) -> [u32; 4] {
+        let block = self.block_at(0);
+        self.advance(1);
+        block
+    }
+
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        self.next_block()[0]
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        let block = self.next_block();
+        block[0] as u64 | (block[1] as u64) << 32
+    }
+
+    /// Returns a float in `[0, 1)`.
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        uniform_from_bits(self.next_u32())
+    }
+
+    /// Returns an integer in `[0, bound)` by multiplying 64 random bits with `bound`.
+    ///
+    /// # Panics
+    /// If `bound` is zero.
+    #[inline]
+    pub fn next_below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "The bound must be greater than zero");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Shuffles `values` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for idx in (1..values.len()).rev() {
+            let other = self.next_below(idx as u64 + 1) as usize;
+            values.swap(idx, other);
+        }
+    }
+
+    /// Splits off an independent stream.
+    /// The seed of the new generator is derived from the next block of this generator.
+    #[inline]
+    pub fn fork(&mut self) -> Rng {
+        let block = self.next_block();
+        // the upper words differ from the ones used by `next_u64`
+        Rng::with_seed(block[2] as u64 | (block[3] as u64) << 32)
+    }
+}
+
+impl Default for Rng {
+    #[inline]
+    fn default() -> Self {
+        Rng::from_entropy()
+    }
+}
+
+thread_local! {
+    static DEFAULT_RNG: RefCell<Rng> = RefCell::new(Rng::from_entropy());
+}
+
+/// Seeds the default generator of the current thread, which is used by [`RandOp::rand`](crate::RandOp::rand).
+///
+/// # Example
+#[cfg_attr(feature = "cpu", doc = "```")]
+#[cfg_attr(not(feature = "cpu"), doc = "```ignore")]
+/// use sliced::{Buffer, RandOp, CPU};
+///
+/// let device = CPU::<custos::Base>::new();
+///
+/// let mut lhs = Buffer::<f32, _>::new(&device, 10);
+/// let mut rhs = Buffer::<f32, _>::new(&device, 10);
+///
+/// sliced::set_seed(42);
+/// device.rand(&mut lhs, -1., 1.);
+///
+/// sliced::set_seed(42);
+/// device.rand(&mut rhs, -1., 1.);
+///
+/// assert_eq!(&*lhs, &*rhs);
+/// ```
+#[inline]
+pub fn set_seed(seed: u64) {
+    with_rng(|rng| rng.set_seed(seed))
+}
+
+/// Calls `f` with the default generator of the current thread.
+#[inline]
+pub fn with_rng<R>(f: impl FnOnce(&mut Rng) -> R) -> R {
+    DEFAULT_RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{philox4x32, Rng};
+
+    #[test]
+    fn test_philox_known_answers() {
+        assert_eq!(
+            philox4x32([0; 4], [0; 2]),
+            [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+        );
+        assert_eq!(
+            philox4x32([u32::MAX; 4], [u32::MAX; 2]),
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+    }
+
+    #[test]
+    fn test_rng_reproducible() {
+        let mut lhs = Rng::with_seed(7);
+        let mut rhs = Rng::with_seed(7);
+
+        for _ in 0..100 {
+            assert_eq!(lhs.next_u64(), rhs.next_u64());
+        }
+
+        rhs.set_seed(8);
+        assert_ne!(lhs.next_u64(), rhs.next_u64());
+    }
+
+    #[test]
+    fn test_rng_fork() {
+        let mut rng = Rng::with_seed(7);
+        let mut forked = rng.fork();
+
+        assert_eq!(rng.counter(), 1);
+        assert_ne!(rng.seed(), forked.seed());
+        assert_ne!(rng.next_u64(), forked.next_u64());
+
+        // forking is deterministic as well
+        let mut other = Rng::with_seed(7);
+        assert_eq!(other.fork(), Rng::with_seed(forked.seed()));
+    }
+
+    #[test]
+    fn test_rng_shuffle() {
+        let mut values = (0..100).collect::<Vec<_>>();
+        Rng::with_seed(3).shuffle(&mut values);
+
+        assert_ne!(values, (0..100).collect::<Vec<_>>());
+
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+
+        let mut again = (0..100).collect::<Vec<_>>();
+        Rng::with_seed(3).shuffle(&mut again);
+        assert_eq!(values, again);
+
+        let mut rng = Rng::with_seed(1);
+        assert!((0..1000).all(|_| rng.next_below(7) < 7));
+    }
+
+    #[test]
+    fn test_uniform_range() {
+        let mut rng = Rng::with_seed(0);
+        for _ in 0..10_000 {
+            let val = rng.next_f64();
+            assert!((0. ..1.).contains(&val));
+            assert!(((val as f32) as f64) == val);
+        }
+    }
+}
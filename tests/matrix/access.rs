@@ -0,0 +1,74 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_matrix_access_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    #[rustfmt::skip]
+    let mut x = Matrix::from((&device, 3, 2, [
+        1., 2.,
+        3., 4.,
+        5., 6.,
+    ]));
+
+    assert_eq!(x.get(2, 1), 6.);
+    x.set(1, 0, -3.);
+    assert_eq!(x.read(), [1., 2., -3., 4., 5., 6.]);
+
+    assert_eq!(x.row(2), [5., 6.]);
+    assert_eq!(x.col(0), [1., -3., 5.]);
+    assert_eq!(
+        x.rows_iter().collect::<Vec<_>>(),
+        [vec![1., 2.], vec![-3., 4.], vec![5., 6.]]
+    );
+    assert_eq!(x.to_vec2d(), [vec![1., 2.], vec![-3., 4.], vec![5., 6.]]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_matrix_get_out_of_bounds() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 2, 2, [1., 2., 3., 4.]));
+    x.get(0, 2);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_matrix_access_stack() {
+    use custos::{Dim2, Stack, WithShape};
+    use sliced::Matrix;
+
+    let device = Stack::new();
+
+    let mut x: Matrix<i32, _, Dim2<2, 2>> = Matrix::with(&device, [[1, 2], [3, 4]]);
+    x.set(0, 0, 9);
+
+    assert_eq!(x.get(0, 0), 9);
+    assert_eq!(x.col(1), [2, 4]);
+    assert_eq!(x.to_vec2d(), [vec![9, 2], vec![3, 4]]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_matrix_access_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use sliced::Matrix;
+
+    let device = OpenCL::<custos::Base>::new(0)?;
+
+    let mut x = Matrix::from((&device, 2, 3, [1f32, 2., 3., 4., 5., 6.]));
+    x.set(1, 2, 0.5);
+
+    assert_eq!(x.get(1, 2), 0.5);
+    assert_eq!(x.row(0), [1., 2., 3.]);
+    assert_eq!(x.col(1), [2., 5.]);
+    assert_eq!(x.to_vec2d(), [vec![1., 2., 3.], vec![4., 5., 0.5]]);
+    Ok(())
+}
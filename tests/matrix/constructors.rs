@@ -0,0 +1,83 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_matrix_constructors_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    let zeros = Matrix::<f32, _>::zeros(&device, 2, 3);
+    assert_eq!((zeros.rows(), zeros.cols()), (2, 3));
+    assert_eq!(zeros.read(), [0.; 6]);
+
+    let ones = Matrix::<f32, _>::ones(&device, 3, 2);
+    assert_eq!(ones.read(), [1.; 6]);
+
+    let full = Matrix::full(&device, 1, 4, 7i32);
+    assert_eq!(full.read(), [7; 4]);
+
+    #[rustfmt::skip]
+    assert_eq!(Matrix::<f32, _>::eye(&device, 3).read(), [
+        1., 0., 0.,
+        0., 1., 0.,
+        0., 0., 1.,
+    ]);
+
+    let arange = Matrix::arange(&device, 2, 3, 1., 0.5);
+    assert_eq!(arange.read(), [1., 1.5, 2., 2.5, 3., 3.5]);
+
+    let linspace = Matrix::linspace(&device, 1, 5, 0., 1.);
+    assert_eq!(linspace.read(), [0., 0.25, 0.5, 0.75, 1.]);
+
+    let from_fn = Matrix::from_fn(&device, 2, 2, |row, col| (row * 10 + col) as f32);
+    assert_eq!(from_fn.read(), [0., 1., 10., 11.]);
+
+    let x = Matrix::from((&device, [[1., 2., 3.], [4., 5., 6.]]));
+    assert_eq!((x.rows(), x.cols()), (2, 3));
+    assert_eq!(x.read(), [1., 2., 3., 4., 5., 6.]);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_matrix_constructors_stack() {
+    use custos::{Dim2, Stack, WithShape};
+    use sliced::Matrix;
+
+    let device = Stack::new();
+
+    let eye = Matrix::<f32, _, Dim2<2, 2>>::eye(&device, 2);
+    assert_eq!(eye.read_to_vec(), [1., 0., 0., 1.]);
+
+    let ones = Matrix::<f32, _, Dim2<2, 3>>::ones(&device, 2, 3);
+    assert_eq!(ones.read_to_vec(), [1.; 6]);
+
+    let x: Matrix<f32, _, Dim2<2, 2>> = Matrix::with(&device, [[1., 2.], [3., 4.]]);
+    assert_eq!(x.read_to_vec(), [1., 2., 3., 4.]);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+#[should_panic]
+fn test_matrix_constructors_stack_wrong_shape() {
+    use custos::{Dim2, Stack};
+    use sliced::Matrix;
+
+    let device = Stack::new();
+    Matrix::<f32, _, Dim2<2, 2>>::zeros(&device, 2, 3);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_matrix_constructors_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use sliced::Matrix;
+
+    let device = OpenCL::<custos::Base>::new(0)?;
+
+    let eye = Matrix::<f32, _>::eye(&device, 2);
+    assert_eq!(eye.read(), [1., 0., 0., 1.]);
+
+    let linspace = Matrix::linspace(&device, 2, 2, -1., 1.);
+    sliced::test_utils::roughly_equals(&linspace.read(), &[-1., -1. / 3., 1. / 3., 1.]);
+    Ok(())
+}
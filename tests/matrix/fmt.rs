@@ -0,0 +1,60 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_display_matrix() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 2, 3, [1., -2.5, 3., 4., 5., 6.]));
+
+    assert_eq!(
+        format!("{x:.2}"),
+        "Matrix(2x3, device: CPU<Base>, requires_grad: false)\n\
+         [[ 1.00, -2.50,  3.00],\n \
+         [ 4.00,  5.00,  6.00]]"
+    );
+    assert_eq!(
+        format!("{x}"),
+        "Matrix(2x3, device: CPU<Base>, requires_grad: false)\n\
+         [[   1, -2.5,    3],\n \
+         [   4,    5,    6]]"
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_display_matrix_elided() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 12, 11, (0..132).collect::<Vec<i32>>()));
+    let out = format!("{x}");
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[1], "[[  0,   1,   2, ...,   8,   9,  10],");
+    assert_eq!(lines[4], " ...,");
+    assert_eq!(lines[7], " [121, 122, 123, ..., 129, 130, 131]]");
+
+    // the alternate flag prints every value
+    assert_eq!(format!("{x:#}").lines().count(), 13);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_debug_matrix() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Matrix::from((&device, 1, 2, [1f32, 2.])).require_grad();
+
+    assert_eq!(
+        format!("{x:?}"),
+        "Matrix(1x2, device: CPU<Autograd<Base>>, requires_grad: true)\n[[1.0, 2.0]]"
+    );
+}
@@ -1,3 +1,6 @@
+mod access;
+mod constructors;
+mod fmt;
 mod l2_norm_cols;
 mod math;
 mod min_fn;
@@ -5,3 +8,4 @@ mod relu;
 mod softmax;
 mod tanh;
 mod transpose;
+mod view;
@@ -0,0 +1,135 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_matrix_view_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    #[rustfmt::skip]
+    let x = Matrix::from((&device, 3, 3, [
+        1., 2., 3.,
+        4., 5., 6.,
+        7., 8., 9.,
+    ]));
+
+    let rows = x.view_rows(1..);
+    assert!(rows.is_contiguous());
+    assert_eq!((rows.rows(), rows.cols()), (2, 3));
+    assert_eq!(rows.to_matrix().read(), [4., 5., 6., 7., 8., 9.]);
+
+    let cols = x.view_cols(..=1);
+    assert!(!cols.is_contiguous());
+    assert_eq!(cols.to_matrix().read(), [1., 2., 4., 5., 7., 8.]);
+    assert_eq!(cols.t().to_matrix().read(), [1., 4., 7., 2., 5., 8.]);
+
+    // x + x^T
+    let out = x.view().add(&x.view_t());
+    assert_eq!(out.read(), [2., 6., 10., 6., 10., 14., 10., 14., 18.]);
+
+    // the leading dimension of the column view is 3
+    let out = x.view_cols(1..).gemm(&x.view_rows(..2).view_cols(..2));
+    assert_eq!(out.read(), [14., 19., 29., 40., 44., 61.]);
+}
+
+#[cfg(feature = "cpu")]
+#[cfg(feature = "autograd")]
+#[test]
+fn test_matrix_view_gemm_grad() {
+    use custos::{Autograd, Base, CPU};
+    use sliced::Matrix;
+
+    let device = CPU::<Autograd<Base>>::new();
+
+    let x = Matrix::from((&device, 4, 3, (0..12).map(|x| x as f32).collect::<Vec<_>>()));
+    let w = Matrix::from((&device, 2, 3, [1., 0., 1., 0., 2., 0.]));
+
+    let out = x.view_rows(1..3).gemm(&w.view_t());
+    assert_eq!(out.read(), [8., 8., 14., 14.]);
+
+    out.backward();
+
+    // only the viewed rows receive a gradient
+    #[rustfmt::skip]
+    assert_eq!(x.grad().read(), [
+        0., 0., 0.,
+        1., 2., 1.,
+        1., 2., 1.,
+        0., 0., 0.,
+    ]);
+    assert_eq!(w.grad().read(), [9., 11., 13., 9., 11., 13.]);
+}
+
+#[cfg(feature = "cpu")]
+#[cfg(feature = "autograd")]
+#[test]
+fn test_matrix_view_heads_grad() {
+    use custos::{Autograd, Base, CPU};
+    use sliced::Matrix;
+
+    let device = CPU::<Autograd<Base>>::new();
+
+    let x = Matrix::from((&device, 2, 4, [1., 2., 3., 4., 5., 6., 7., 8.]));
+
+    let (head1, head2) = (x.view_cols(..2), x.view_cols(2..));
+
+    let out = head1.mul(&head2);
+    assert_eq!(out.read(), [3., 8., 35., 48.]);
+    out.backward();
+    assert_eq!(x.grad().read(), [3., 4., 1., 2., 7., 8., 5., 6.]);
+}
+
+#[cfg(feature = "cpu")]
+#[cfg(feature = "autograd")]
+#[test]
+fn test_matrix_view_sub_grad() {
+    use custos::{Autograd, Base, CPU};
+    use sliced::Matrix;
+
+    let device = CPU::<Autograd<Base>>::new();
+
+    let x = Matrix::from((&device, 2, 4, [1., 2., 3., 4., 5., 6., 7., 8.]));
+
+    let out = x.view_cols(..2).sub(&x.view_cols(2..));
+    assert_eq!(out.read(), [-2., -2., -2., -2.]);
+
+    out.backward();
+    assert_eq!(x.grad().read(), [1., 1., -1., -1., 1., 1., -1., -1.]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+#[should_panic]
+fn test_matrix_view_overlapping_layout() {
+    use custos::CPU;
+    use sliced::{Layout, Matrix, MatrixView};
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+
+    // every row is the first row
+    let layout = Layout {
+        row_stride: 0,
+        ..Layout::contiguous(2, 3)
+    };
+    MatrixView::new(&x, layout);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_matrix_view_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use sliced::Matrix;
+
+    let device = OpenCL::<custos::Base>::new(0)?;
+
+    let x = Matrix::from((&device, 2, 3, [1f32, 2., 3., 4., 5., 6.]));
+
+    let out = x.view().gemm(&x.view_t());
+    assert_eq!(out.read(), [14., 32., 32., 77.]);
+
+    let out = x.view_cols(1..).mul(&x.view_cols(..2));
+    assert_eq!(out.read(), [2., 6., 20., 30.]);
+    Ok(())
+}
@@ -1,50 +1,10 @@
-use custos::{
-    prelude::Float, Alloc, Buffer, Device, IsShapeIndep, MayTapeActions, OnNewBuffer, TapeActions,
-    ZeroGrad,
-};
-
-use sliced::{GemmMayGrad, Matrix, RandOp, RowOpMayGrad};
+use custos::{Alloc, IsShapeIndep, OnNewBuffer, TapeActions};
 
-pub struct Linear<'a, T, D: Device, const I: usize, const O: usize> {
-    weights: Matrix<'a, T, D>,
-    bias: Matrix<'a, T, D>,
-}
-
-impl<'a, T: Float, D: Device + OnNewBuffer<T, D, ()>, const I: usize, const O: usize>
-    Linear<'a, T, D, I, O>
-{
-    pub fn new(device: &'a D) -> Self
-    where
-        D: RandOp<T> + Alloc<T>,
-    {
-        let mut weights = Matrix::new(device, I, O);
-        device.rand(&mut weights, -T::one() / T::two(), T::one() / T::two());
-        //let mut weights = Matrix::from((device, I, O, vec![T::one(); I*O]));
-
-        Linear {
-            weights,
-            bias: Matrix::new(device, 1, O),
-        }
-    }
-
-    #[inline]
-    pub fn forward(&self, inputs: &Matrix<'a, T, D>) -> Matrix<'a, T, D>
-    where
-        D: GemmMayGrad<T> + RowOpMayGrad<T>,
-    {
-        //inputs.gemm(&self.weights).add_row(&self.bias)
-        let mut out = inputs.gemm(&self.weights);
-        out.add_row_mut(&self.bias);
-        out
-    }
-
-    pub fn params<'b>(&'b mut self) -> Vec<Param<'b, 'a, T, D>>
-    where
-        D: IsShapeIndep,
-    {
-        vec![Param::new(&mut self.weights), Param::new(&mut self.bias)]
-    }
-}
+use sliced::{
+    nn::{Linear, Module},
+    optim::{Optimizer, SGD},
+    Matrix,
+};
 
 pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     device: &'a D,
@@ -66,71 +26,58 @@ pub fn create_sine<'a, D: Alloc<f32> + IsShapeIndep + OnNewBuffer<f32, D>>(
     (x, y)
 }
 
-pub struct Param<'a, 'b, T, D: Device> {
-    param: &'a mut Buffer<'b, T, D>,
-}
-
-impl<'a, 'b, T, D: Device> Param<'a, 'b, T, D> {
-    pub fn new(param: &'a mut Buffer<'b, T, D>) -> Self {
-        Param { param }
-    }
-}
-
-pub struct SGD<T> {
-    lr: T,
-}
+#[test]
+fn test_linear_zero_bias() {
+    use custos::CPU;
 
-#[cfg(feature = "autograd")]
-use custos::prelude::{ClearBuf, One, WriteBuf};
-
-#[cfg(feature = "autograd")]
-use core::ops::{Mul, SubAssign};
-use std::ops::{Deref, DerefMut};
-
-#[cfg(feature = "autograd")]
-impl<T: Copy + One + Mul<Output = T> + SubAssign + 'static> SGD<T> {
-    pub fn zero_grad<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: ZeroGrad<T> + MayTapeActions + WriteBuf<T> + Alloc<T> + ClearBuf<T> + 'static,
-    {
-        for param in params {
-            param.param.grad_mut().clear();
-        }
-    }
+    let device = CPU::<custos::Autograd<custos::Cached<custos::Base>>>::new();
 
-    pub fn step<D>(&self, params: Vec<Param<T, D>>)
-    where
-        D: ZeroGrad<T> + WriteBuf<T> + Alloc<T> + MayTapeActions + 'static,
-        D::Base<T, ()>: Deref<Target = [T]> + DerefMut,
-    {
-        for param in params {
-            let grad = param.param.grad();
-            for (value, grad) in param.param.iter_mut().zip(grad.iter()) {
-                *value -= *grad * self.lr
-            }
-        }
+    // the cached allocation of a dropped layer must not leak into the bias of a new one
+    for _ in 0..2 {
+        let mut lin = Linear::<f32, _, 2, 3>::new(&device);
+        assert_eq!(lin.bias.read(), [0.; 3]);
+        lin.bias.write(&[1., 2., 3.]);
     }
 }
 
-#[cfg(feature = "autograd")]
 #[test]
 #[cfg_attr(miri, ignore)]
-fn test_mnist() {
+fn test_classify_csv() {
     use custos::CPU;
-    use purpur::CSVLoader;
+    use sliced::{io::CsvLoader, Onehot};
 
     let device = CPU::<custos::Autograd<custos::Base>>::new();
 
-    let loader = CSVLoader::new(true);
-    let Ok(loaded_data) =
-        loader.load::<f32, _>("../gradients-fallback/datasets/digit-recognizer/train.csv")
-    else {
-        return;
-    };
+    // the label is the index of the brighter pixel
+    let csv = "label,pixel0,pixel1\n0,1,0\n1,0,1\n0,0.9,0.1\n1,0.05,0.95\n";
+    let data = CsvLoader::new().read::<f32>(csv.as_bytes()).unwrap();
 
-    let mut lin1 = Linear::<f32, _, 1, 64>::new(&device);
-    let mut lin2 = Linear::<f32, _, 64, 64>::new(&device);
-    let mut lin3 = Linear::<f32, _, 64, 1>::new(&device);
+    let x = data.features_matrix(&device);
+    let y = device.onehot(&data.labels_matrix(&device));
+    let labels = data.labels_buffer(&device).read();
+
+    let mut lin = Linear::<f32, _, 2, 2>::new(&device);
+    let mut sgd = SGD::new(0.1);
+
+    let mut losses = Vec::new();
+    for _ in 0..200 {
+        unsafe {
+            device.gradients_mut().unwrap().zero_grad();
+        };
+
+        let loss = (&lin.forward(&x) - &y).squared();
+        losses.push(loss.read().iter().sum::<f32>());
+        loss.backward();
+
+        sgd.step(lin.params());
+    }
+    assert!(losses[199] < losses[0] / 2., "losses: {losses:?}");
+
+    let out = lin.forward(&x).read();
+    for (row, label) in out.chunks(2).zip(labels) {
+        let predicted = if row[0] > row[1] { 0. } else { 1. };
+        assert_eq!(predicted, label);
+    }
 }
 
 #[test]
@@ -146,12 +93,11 @@ fn test_nn() {
     let mut lin3 = Linear::<f32, _, 64, 1>::new(&device);
 
     let (x, y) = create_sine(&device, 0, 1000);
-    let sgd = SGD { lr: 0.0001 };
+    let mut sgd = SGD::new(0.0001);
 
     let start = Instant::now();
 
     for _ in 0..1000 {
-        #[cfg(feature = "autograd")]
         unsafe {
             device.gradients_mut().unwrap().zero_grad();
         };
@@ -166,16 +112,13 @@ fn test_nn() {
 
         let loss = (&out - &y).squared();
 
-        #[cfg(feature = "autograd")]
-        {
-            loss.backward();
+        loss.backward();
 
-            //println!("lin1 dweights grad: {:?}", lin1.weights.grad());
+        //println!("lin1 dweights grad: {:?}", lin1.weights.grad());
 
-            sgd.step(lin1.params());
-            sgd.step(lin2.params());
-            sgd.step(lin3.params());
-        }
+        sgd.step(lin1.params());
+        sgd.step(lin2.params());
+        sgd.step(lin3.params());
     }
 
     println!("elapsed: {:?}", start.elapsed());
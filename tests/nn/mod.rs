@@ -1 +1,5 @@
+// the optimizers require autograd
+#[cfg(feature = "autograd")]
 mod linear;
+mod sequential;
+mod state_dict;
@@ -0,0 +1,70 @@
+use sliced::{
+    nn::{Linear, Module, ReLU, Sequential, Sigmoid, Softmax, Tanh},
+    Matrix,
+};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_sequential_forward() {
+    use custos::CPU;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let mut net = Sequential::new()
+        .add(Linear::<f32, _, 3, 16>::new(&device))
+        .add(ReLU)
+        .add(Linear::<f32, _, 16, 8>::new(&device))
+        .add(Tanh)
+        .add(Linear::<f32, _, 8, 4>::new(&device))
+        .add(Sigmoid)
+        .add(Softmax);
+
+    let x = Matrix::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+    let out = net.forward(&x);
+
+    assert_eq!((out.rows(), out.cols()), (2, 4));
+    for row in out.read().chunks(4) {
+        assert!((row.iter().sum::<f32>() - 1.).abs() < 1e-5);
+    }
+
+    assert_eq!(net.len(), 7);
+    assert_eq!(net.params().len(), 6);
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_sequential_train_step() {
+    use custos::CPU;
+    use sliced::{
+        optim::{Optimizer, SGD},
+        BinaryOpsMayGrad, Mean,
+    };
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+    sliced::set_seed(0);
+
+    let mut net = Sequential::new()
+        .add(Linear::<f32, _, 1, 8>::new(&device))
+        .add(Tanh)
+        .add(Linear::<f32, _, 8, 1>::new(&device));
+
+    let x = Matrix::from((&device, 4, 1, [-1., -0.5, 0.5, 1.])).no_grad();
+    let y = Matrix::from((&device, 4, 1, [1., 0.25, 0.25, 1.])).no_grad();
+
+    let mut sgd = SGD::new(0.1);
+    let mut losses = vec![];
+
+    for _ in 0..50 {
+        sgd.zero_grad(net.params());
+
+        let out = net.forward(&x);
+        let diff = device.sub(&out, &y);
+        let loss = device.mul(&diff, &diff);
+        losses.push(device.mean(&loss));
+
+        loss.backward();
+        sgd.step(net.params());
+    }
+
+    assert!(losses.last().unwrap() < &losses[0]);
+}
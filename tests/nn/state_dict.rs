@@ -0,0 +1,95 @@
+use sliced::{
+    nn::{Linear, Module, ReLU, Sequential, StateDictError},
+    Matrix,
+};
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_named_params() {
+    use custos::CPU;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let mut net = Sequential::new()
+        .add(Linear::<f32, _, 3, 4>::new(&device))
+        .add(ReLU)
+        .add(Linear::<f32, _, 4, 2>::new(&device));
+
+    let params = net
+        .named_params()
+        .into_iter()
+        .map(|param| (param.name, param.param.rows(), param.param.cols()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        params,
+        [
+            ("0.weights".to_string(), 3, 4),
+            ("0.bias".to_string(), 1, 4),
+            ("2.weights".to_string(), 4, 2),
+            ("2.bias".to_string(), 1, 2),
+        ]
+    );
+    assert_eq!(net.params().len(), 4);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_state_dict_roundtrip() {
+    use custos::CPU;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+    sliced::set_seed(0);
+
+    let mut lin1 = Linear::<f32, _, 2, 3>::new(&device);
+    let mut lin2 = Linear::<f32, _, 2, 3>::new(&device);
+
+    let state_dict = lin1.state_dict();
+    assert_eq!(state_dict.names().collect::<Vec<_>>(), ["bias", "weights"]);
+    assert_eq!(state_dict.get("weights").unwrap().shape, [2, 3]);
+
+    lin2.load_state_dict(&state_dict).unwrap();
+    assert_eq!(lin2.weights.read(), lin1.weights.read());
+    assert_eq!(lin2.bias.read(), lin1.bias.read());
+
+    let x = Matrix::from((&device, 1, 2, [1., -2.]));
+    assert_eq!(lin1.forward(&x).read(), lin2.forward(&x).read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_load_state_dict_errors() {
+    use custos::CPU;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let mut lin = Linear::<f32, _, 2, 3>::new(&device);
+    let weights = lin.weights.read().to_vec();
+
+    let mut state_dict = lin.state_dict();
+    state_dict.insert("weights", vec![3, 2], vec![0.; 6]);
+    assert_eq!(
+        lin.load_state_dict(&state_dict),
+        Err(StateDictError::ShapeMismatch {
+            name: "weights".into(),
+            expected: vec![2, 3],
+            found: vec![3, 2],
+        })
+    );
+
+    // nothing was written
+    assert_eq!(lin.weights.read(), weights);
+
+    state_dict.remove("weights");
+    assert_eq!(
+        lin.load_state_dict(&state_dict),
+        Err(StateDictError::MissingParam("weights".into()))
+    );
+
+    let mut state_dict = lin.state_dict();
+    state_dict.insert("other", vec![1], vec![0.]);
+    assert_eq!(
+        lin.load_state_dict(&state_dict),
+        Err(StateDictError::UnexpectedParam("other".into()))
+    );
+}
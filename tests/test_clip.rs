@@ -0,0 +1,96 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_clip_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::ClipMayGrad;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Buffer::from((&device, [-3., -1., 0., 1., 2., 5.]));
+    let out = device.clip(&x, -1., 2.);
+    assert_eq!(out.read(), [-1., -1., 0., 1., 2., 2.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+
+        // the bounds are inclusive
+        assert_eq!(x.grad().read(), [0., 1., 1., 1., 1., 0.]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_clip_straight_through_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::ClipMayGrad;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Buffer::from((&device, [-3., 0.5, 5.]));
+    let out = device.clip_with(&x, 0., 1., true);
+    assert_eq!(out.read(), [0., 0.5, 1.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(x.grad().read(), [1., 1., 1.]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_clip_matrix_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    #[rustfmt::skip]
+    let x = Matrix::from((&device, 2, 2, [
+        0.5, -2.,
+        3., 0.25,
+    ]));
+    let out = x.clip(0., 1.);
+    assert_eq!((out.rows(), out.cols()), (2, 2));
+    assert_eq!(out.read(), [0.5, 0., 1., 0.25]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(x.grad().read(), [1., 0., 0., 1.]);
+    }
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_clip_cl() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL};
+    use sliced::ClipMayGrad;
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    let x = Buffer::from((&device, [-3f32, -1., 0., 1., 2., 5.]));
+    let out = device.clip(&x, -1., 2.);
+    assert_eq!(out.read(), [-1., -1., 0., 1., 2., 2.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(x.grad().read(), [0., 1., 1., 1., 1., 0.]);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_clip_glob_import_cpu() {
+    // `Clip` is an alias of `ClipMayGrad`, hence `clip` is not ambiguous
+    use sliced::*;
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Buffer::from((&device, [-3., 0., 5.]));
+    let out = device.clip(&x, -1., 2.);
+    assert_eq!(out.read(), [-1., 0., 2.]);
+}
@@ -0,0 +1,162 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_concat_rows_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, ConcatMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    // 1 x 3
+    let lhs = Buffer::from((&device, [1, 2, 3]));
+    // 2 x 3
+    let rhs = Buffer::from((&device, [4, 5, 6, 7, 8, 9]));
+
+    let out: Buffer<_, _> = device.concat_rows(&lhs, &rhs);
+    assert_eq!(out.read(), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3, 4, 1, -2, 5, 6, 7]));
+        let weighted = device.mul(&out, &weights);
+        weighted.backward();
+
+        assert_eq!(lhs.grad().read(), [2, -1, 3]);
+        assert_eq!(rhs.grad().read(), [4, 1, -2, 5, 6, 7]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_concat_cols_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, ConcatMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    // 2 x 1
+    let lhs = Buffer::from((&device, [1, 4]));
+    // 2 x 2
+    let rhs = Buffer::from((&device, [2, 3, 5, 6]));
+
+    let out: Buffer<_, _> = device.concat_cols(1, 2, &lhs, &rhs);
+    assert_eq!(out.read(), [1, 2, 3, 4, 5, 6]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3, 4, 1, -2]));
+        let weighted = device.mul(&out, &weights);
+        weighted.backward();
+
+        assert_eq!(lhs.grad().read(), [2, 4]);
+        assert_eq!(rhs.grad().read(), [-1, 3, 1, -2]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_split_cols_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, SplitMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    // 2 x 3
+    let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+
+    let (left, right): (Buffer<_, _>, Buffer<_, _>) = device.split_cols(3, 1, &x);
+    assert_eq!(left.read(), [1, 4]);
+    assert_eq!(right.read(), [2, 3, 5, 6]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [3, -2, 1, 4]));
+        let weighted = device.mul(&right, &weights);
+        weighted.backward();
+
+        assert_eq!(x.grad().read(), [0, 3, -2, 0, 1, 4]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_concat_split_matrix_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Matrix::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+    let (top, bottom) = x.split_rows::<(), ()>(1);
+
+    assert_eq!((top.rows(), top.cols()), (1, 3));
+    assert_eq!((bottom.rows(), bottom.cols()), (1, 3));
+
+    let out = bottom.concat_cols::<(), ()>(&top);
+    assert_eq!((out.rows(), out.cols()), (1, 6));
+    assert_eq!(out.read(), [4., 5., 6., 1., 2., 3.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(x.grad().read(), [1.; 6]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_concat_split_empty_side_cpu() {
+    use custos::CPU;
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 2, 3, [1., 2., 3., 4., 5., 6.]));
+
+    let (left, right) = x.split_cols::<(), ()>(0);
+    assert_eq!((left.rows(), left.cols()), (2, 0));
+    assert_eq!(right.read(), [1., 2., 3., 4., 5., 6.]);
+
+    let (top, bottom) = x.split_rows::<(), ()>(2);
+    assert_eq!(top.read(), [1., 2., 3., 4., 5., 6.]);
+    assert_eq!((bottom.rows(), bottom.cols()), (0, 3));
+
+    let out = left.concat_cols::<(), ()>(&right);
+    assert_eq!((out.rows(), out.cols()), (2, 3));
+    assert_eq!(out.read(), [1., 2., 3., 4., 5., 6.]);
+
+    let out = top.concat_rows::<(), ()>(&bottom);
+    assert_eq!((out.rows(), out.cols()), (2, 3));
+    assert_eq!(out.read(), [1., 2., 3., 4., 5., 6.]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_concat_split_cl() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL};
+    use sliced::{BinaryOpsMayGrad, ConcatMayGrad, SplitMayGrad};
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    // 2 x 1
+    let lhs = Buffer::from((&device, [1, 4]));
+    // 2 x 2
+    let rhs = Buffer::from((&device, [2, 3, 5, 6]));
+
+    let out: Buffer<_, _> = device.concat_cols(1, 2, &lhs, &rhs);
+    assert_eq!(out.read(), [1, 2, 3, 4, 5, 6]);
+
+    let (top, bottom): (Buffer<_, _>, Buffer<_, _>) = device.split_rows(3, 1, &out);
+    assert_eq!(top.read(), [1, 2, 3]);
+    assert_eq!(bottom.read(), [4, 5, 6]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3]));
+        let weighted = device.mul(&bottom, &weights);
+        weighted.backward();
+
+        assert_eq!(lhs.grad().read(), [0, 2]);
+        assert_eq!(rhs.grad().read(), [0, 0, -1, 3]);
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_matrix_csv_roundtrip() {
+    use custos::CPU;
+    use sliced::{io::CsvLoader, Matrix};
+
+    let device = CPU::<custos::Base>::new();
+
+    #[rustfmt::skip]
+    let x = Matrix::from((&device, 3, 2, [
+        0.25f32, -1.,
+        3., 4.5,
+        1e-3, 7.,
+    ]));
+
+    let path = std::env::temp_dir().join("sliced_test_matrix.csv");
+    x.write_csv(&path).unwrap();
+
+    let data = CsvLoader::new()
+        .has_header(false)
+        .label_column(None)
+        .load::<f32>(&path)
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let read = data.features_matrix(&device);
+    assert_eq!((read.rows(), read.cols()), (3, 2));
+    assert_eq!(read.read(), x.read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_csv_labels_onehot() {
+    use custos::CPU;
+    use sliced::{io::CsvLoader, Onehot};
+
+    let device = CPU::<custos::Base>::new();
+
+    let csv = "label,pixel0,pixel1\n2,0,255\n0,128,64\n1,1,1\n";
+    let data = CsvLoader::new().read::<f32>(csv.as_bytes()).unwrap();
+
+    let x = data.features_matrix(&device);
+    assert_eq!((x.rows(), x.cols()), (3, 2));
+    assert_eq!(x.read(), [0., 255., 128., 64., 1., 1.]);
+
+    let y = data.labels_matrix(&device);
+    assert_eq!((y.rows(), y.cols()), (3, 1));
+
+    let onehot = device.onehot(&y);
+    #[rustfmt::skip]
+    assert_eq!(onehot.read(), [
+        0., 0., 1.,
+        1., 0., 0.,
+        0., 1., 0.,
+    ]);
+
+    assert_eq!(data.labels_buffer(&device).read(), [2., 0., 1.]);
+}
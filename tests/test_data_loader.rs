@@ -0,0 +1,103 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_data_loader_covers_all_rows() {
+    use custos::CPU;
+    use sliced::{data::DataLoader, Matrix};
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 7, 2, (0..14).map(|x| x as f32).collect::<Vec<_>>()));
+    let y = Matrix::from((&device, 7, 1, (0..7).map(|x| x as f32).collect::<Vec<_>>()));
+
+    let mut loader = DataLoader::new(&x, &y, 3).seed(42);
+    assert_eq!(loader.len(), 3);
+
+    let mut seen = vec![];
+    let mut sizes = vec![];
+    for (x_batch, y_batch) in loader.iter() {
+        sizes.push(x_batch.rows());
+        let features = x_batch.read_to_vec();
+        for (row, label) in y_batch.read_to_vec().into_iter().enumerate() {
+            assert_eq!(features[row * 2], label * 2.);
+            assert_eq!(features[row * 2 + 1], label * 2. + 1.);
+            seen.push(label as usize);
+        }
+    }
+    assert_eq!(sizes, [3, 3, 1]);
+
+    assert_ne!(seen, (0..7).collect::<Vec<_>>());
+    seen.sort();
+    assert_eq!(seen, (0..7).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_data_loader_drop_last_sequential() {
+    use custos::CPU;
+    use sliced::{data::DataLoader, Matrix};
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((&device, 5, 1, [0., 1., 2., 3., 4.]));
+    let y = Matrix::from((&device, 5, 1, [0., 1., 2., 3., 4.]));
+
+    let mut loader = DataLoader::new(&x, &y, 2).shuffle(false).drop_last(true);
+    assert_eq!(loader.len(), 2);
+
+    let batches = loader
+        .iter()
+        .map(|(x, _)| x.read_to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(batches, [vec![0., 1.], vec![2., 3.]]);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_data_loader_seed_reproducible() {
+    use custos::CPU;
+    use sliced::{data::DataLoader, Matrix};
+
+    let device = CPU::<custos::Base>::new();
+
+    let x = Matrix::from((
+        &device,
+        10,
+        1,
+        (0..10).map(|x| x as f32).collect::<Vec<_>>(),
+    ));
+
+    let epoch = |seed| {
+        DataLoader::new(&x, &x, 4)
+            .seed(seed)
+            .iter()
+            .flat_map(|(x, _)| x.read_to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(epoch(3), epoch(3));
+
+    // every epoch is shuffled differently
+    let mut loader = DataLoader::new(&x, &x, 10).seed(3);
+    let first = loader.iter().next().unwrap().0.read_to_vec();
+    let second = loader.iter().next().unwrap().0.read_to_vec();
+    assert_ne!(first, second);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_data_loader_cl() {
+    use custos::OpenCL;
+    use sliced::{data::DataLoader, Matrix};
+
+    let device = OpenCL::<custos::Base>::new(0).unwrap();
+
+    let x = Matrix::from((&device, 4, 2, [0., 1., 2., 3., 4., 5., 6., 7.]));
+    let y = Matrix::from((&device, 4, 1, [0., 1., 2., 3.]));
+
+    let mut loader = DataLoader::new(&x, &y, 2).shuffle(false);
+    let batches = loader
+        .iter()
+        .map(|(x, y)| (x.read(), y.read()))
+        .collect::<Vec<_>>();
+    assert_eq!(batches[0], (vec![0., 1., 2., 3.], vec![0., 1.]));
+    assert_eq!(batches[1], (vec![4., 5., 6., 7.], vec![2., 3.]));
+}
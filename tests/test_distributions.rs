@@ -0,0 +1,71 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_distributions_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{Distributions, RandInt, Rng};
+
+    let device = CPU::<custos::Base>::new();
+
+    let mut x = Buffer::<f32, _>::new(&device, 10_000);
+    device.randn_with(&mut Rng::with_seed(0), &mut x, 0., 1.);
+    let mean = x.iter().sum::<f32>() / x.len() as f32;
+    assert!(mean.abs() < 0.05, "mean: {mean}");
+
+    device.trunc_normal_with(&mut Rng::with_seed(0), &mut x, 0., 1., -2., 2.);
+    assert!(x.iter().all(|x| (-2. ..=2.).contains(x)));
+
+    device.bernoulli_with(&mut Rng::with_seed(0), &mut x, 0.5);
+    assert!(x.iter().all(|&x| x == 0. || x == 1.));
+
+    let mut classes = Buffer::<u8, _>::new(&device, 100);
+    device.rand_int_with(&mut Rng::with_seed(0), &mut classes, 0, 10);
+    assert!(classes.iter().all(|&class| class < 10));
+
+    let probs = Buffer::from((&device, [0.5, 0.5, 0., 0., 0., 1.]));
+    let mut out = Buffer::<f32, _>::new(&device, 2);
+    device.categorical(3, &probs, &mut out);
+    assert!(out[0] == 0. || out[0] == 1.);
+    assert_eq!(out[1], 2.);
+}
+
+#[cfg(feature = "stack")]
+#[test]
+fn test_distributions_stack() {
+    use custos::{Buffer, Stack};
+    use sliced::{Distributions, RandInt, Rng};
+
+    let device = Stack::new();
+
+    let mut x = Buffer::<f32, _, custos::Dim1<100>>::new(&device, 100);
+    device.randn_with(&mut Rng::with_seed(0), &mut x, 0., 1.);
+    assert!(x.iter().any(|&x| x != 0.));
+
+    let mut ints = Buffer::<i32, _, custos::Dim1<100>>::new(&device, 100);
+    device.rand_int_with(&mut Rng::with_seed(0), &mut ints, -5, 5);
+    assert!(ints.iter().all(|x| (-5..5).contains(x)));
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_distributions_cpu_cl_equal() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL, CPU};
+    use sliced::{Distributions, Rng};
+
+    let cpu = CPU::<custos::Base>::new();
+    let cl = OpenCL::<custos::Base>::new(0)?;
+
+    let mut lhs = Buffer::<f32, _>::new(&cpu, 1000);
+    let mut rhs = Buffer::<f32, _>::new(&cl, 1000);
+
+    cpu.trunc_normal_with(&mut Rng::with_seed(9), &mut lhs, 1., 2., -1., 3.);
+    cl.trunc_normal_with(&mut Rng::with_seed(9), &mut rhs, 1., 2., -1., 3.);
+    // f32 samples are computed in float on the device
+    for (lhs, rhs) in lhs.read().iter().zip(rhs.read()) {
+        assert!((lhs - rhs).abs() < 1e-4, "{lhs} != {rhs}");
+    }
+
+    cpu.bernoulli_with(&mut Rng::with_seed(9), &mut lhs, 0.2);
+    cl.bernoulli_with(&mut Rng::with_seed(9), &mut rhs, 0.2);
+    assert_eq!(lhs.read(), rhs.read());
+    Ok(())
+}
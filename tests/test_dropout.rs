@@ -0,0 +1,72 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_dropout_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::DropoutMayGrad;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Buffer::from((&device, [1f32; 1000]));
+    let out = device.dropout(&x, 0.25, true);
+
+    let out = out.read();
+    let kept = out.iter().filter(|&&val| val != 0.).count();
+
+    // the kept activations are scaled by 1 / (1 - p)
+    assert!(out.iter().all(|&val| val == 0. || val == 1. / 0.75));
+    assert!((650..850).contains(&kept), "kept: {kept}");
+
+    #[cfg(feature = "autograd")]
+    {
+        let out = device.dropout(&x, 0.5, true);
+        out.backward();
+
+        // the gradient uses the same mask as the forward pass
+        assert_eq!(x.grad().read(), out.read());
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_dropout_eval_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::DropoutMayGrad;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Buffer::from((&device, [1., 2., 3., 4.]));
+    let out = device.dropout(&x, 0.5, false);
+    assert_eq!(out.read(), [1., 2., 3., 4.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(x.grad().read(), [1.; 4]);
+    }
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_dropout_matrix_cl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use sliced::Matrix;
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    let x = Matrix::from((&device, 10, 10, [2f32; 100]));
+    let out = x.dropout(0.5, true);
+
+    assert_eq!((out.rows(), out.cols()), (10, 10));
+    assert!(out.read().iter().all(|&val| val == 0. || val == 4.));
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+
+        let grad = x.grad().read();
+        for (grad, out) in grad.iter().zip(out.read()) {
+            assert_eq!(*grad * 2., out);
+        }
+    }
+    Ok(())
+}
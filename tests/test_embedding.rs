@@ -0,0 +1,77 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_embedding_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, EmbeddingMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    // vocabulary of 4, dim 2
+    #[rustfmt::skip]
+    let weights = Buffer::from((&device, [
+        0.1, 0.2,
+        0.3, 0.4,
+        0.5, 0.6,
+        0.7, 0.8,
+    ]));
+    let tokens = Buffer::from((&device, [3usize, 0, 3]));
+
+    let out: Buffer<_, _> = device.embedding(2, &weights, &tokens);
+    assert_eq!(out.read(), [0.7, 0.8, 0.1, 0.2, 0.7, 0.8]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let scale = Buffer::from((&device, [1., 2., 3., 4., 5., 6.]));
+        let scaled = device.mul(&out, &scale);
+        scaled.backward();
+
+        assert_eq!(weights.grad().read(), [3., 4., 0., 0., 0., 0., 6., 8.]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_embedding_matrix_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let weights = Matrix::from((&device, 3, 2, [1., 2., 3., 4., 5., 6.]));
+    let tokens = Buffer::from((&device, [2usize, 2]));
+
+    let out = weights.embedding::<_, ()>(&tokens);
+    assert_eq!((out.rows(), out.cols()), (2, 2));
+    assert_eq!(out.read(), [5., 6., 5., 6.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        out.backward();
+        assert_eq!(weights.grad().read(), [0., 0., 0., 0., 2., 2.]);
+    }
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_embedding_cl() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL};
+    use sliced::{BinaryOpsMayGrad, EmbeddingMayGrad};
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    let weights = Buffer::from((&device, [1, 2, 3, 4, 5, 6, 7, 8]));
+    let tokens = Buffer::from((&device, [3i32, 0, 3]));
+
+    let out: Buffer<_, _> = device.embedding(2, &weights, &tokens);
+    assert_eq!(out.read(), [7, 8, 1, 2, 7, 8]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let scale = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+        let scaled = device.mul(&out, &scale);
+        scaled.backward();
+
+        assert_eq!(weights.grad().read(), [3, 4, 0, 0, 0, 0, 6, 8]);
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+#[cfg(feature = "cpu")]
+fn write_idx(path: &std::path::Path, dims: &[u32], data: &[u8]) {
+    let mut bytes = vec![0, 0, 0x08, dims.len() as u8];
+    for dim in dims {
+        bytes.extend_from_slice(&dim.to_be_bytes());
+    }
+    bytes.extend_from_slice(data);
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_load_mnist() {
+    use custos::CPU;
+    use sliced::{io::load_mnist, Onehot};
+
+    let dir = std::env::temp_dir().join("sliced_test_mnist");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // three 2x2 images
+    write_idx(
+        &dir.join("t10k-images-idx3-ubyte"),
+        &[3, 2, 2],
+        &[0, 255, 51, 102, 255, 255, 0, 0, 153, 0, 0, 204],
+    );
+    // the dotted name variant
+    write_idx(&dir.join("t10k-labels.idx1-ubyte"), &[3], &[7, 0, 2]);
+
+    let device = CPU::<custos::Base>::new();
+    let (images, labels) = load_mnist::<f32, _>(&device, &dir, false).unwrap();
+
+    assert_eq!((images.rows(), images.cols()), (3, 4));
+    #[rustfmt::skip]
+    assert_eq!(images.read(), [
+        0., 1., 0.2, 0.4,
+        1., 1., 0., 0.,
+        0.6, 0., 0., 0.8,
+    ]);
+    assert_eq!(labels.read(), [7., 0., 2.]);
+    assert_eq!(device.onehot(&labels).len(), 3 * 8);
+
+    // there is no training set
+    assert!(load_mnist::<f32, _>(&device, &dir, true).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_load_mnist_mismatched_counts() {
+    use custos::CPU;
+    use sliced::io::load_mnist;
+
+    let dir = std::env::temp_dir().join("sliced_test_mnist_mismatch");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    write_idx(&dir.join("train-images-idx3-ubyte"), &[1, 1, 2], &[0, 255]);
+    write_idx(&dir.join("train-labels-idx1-ubyte"), &[2], &[1, 2]);
+
+    let device = CPU::<custos::Base>::new();
+    assert!(load_mnist::<f32, _>(&device, &dir, true).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
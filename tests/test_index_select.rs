@@ -0,0 +1,113 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_index_select_rows_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, IndexSelectRowsMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    #[rustfmt::skip]
+    let x = Buffer::from((&device, [
+        1, 2,
+        3, 4,
+        5, 6,
+    ]));
+    let indices = Buffer::from((&device, [2usize, 0, 2]));
+
+    let out: Buffer<_, _> = device.index_select_rows(2, &x, &indices);
+    assert_eq!(out.read(), [5, 6, 1, 2, 5, 6]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3, 4, 1, -2]));
+        let weighted = device.mul(&out, &weights);
+        weighted.backward();
+
+        // the duplicated row 2 accumulates both gradients
+        assert_eq!(x.grad().read(), [3, 4, 0, 0, 3, -3]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_scatter_add_rows_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{BinaryOpsMayGrad, ScatterAddRowsMayGrad};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    #[rustfmt::skip]
+    let x = Buffer::from((&device, [
+        1, 2,
+        3, 4,
+        5, 6,
+    ]));
+    let indices = Buffer::from((&device, [1usize, 0, 1]));
+
+    let out: Buffer<_, _> = device.scatter_add_rows(3, 2, &x, &indices);
+    assert_eq!(out.read(), [3, 4, 6, 8, 0, 0]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3, 4, 1, -2]));
+        let weighted = device.mul(&out, &weights);
+        weighted.backward();
+
+        assert_eq!(x.grad().read(), [3, 4, 2, -1, 3, 4]);
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_index_select_rows_matrix_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::Matrix;
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let x = Matrix::from((&device, 3, 2, [1., 2., 3., 4., 5., 6.]));
+    let indices = Buffer::from((&device, [1usize, 1]));
+
+    let out = x.index_select_rows::<_, ()>(&indices);
+    assert_eq!((out.rows(), out.cols()), (2, 2));
+    assert_eq!(out.read(), [3., 4., 3., 4.]);
+
+    let scattered = out.scatter_add_rows::<_, ()>(3, &indices);
+    assert_eq!((scattered.rows(), scattered.cols()), (3, 2));
+    assert_eq!(scattered.read(), [0., 0., 6., 8., 0., 0.]);
+
+    #[cfg(feature = "autograd")]
+    {
+        scattered.backward();
+        assert_eq!(x.grad().read(), [0., 0., 2., 2., 0., 0.]);
+    }
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_index_select_scatter_add_rows_cl() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL};
+    use sliced::{BinaryOpsMayGrad, IndexSelectRowsMayGrad, ScatterAddRowsMayGrad};
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    let x = Buffer::from((&device, [1, 2, 3, 4, 5, 6]));
+    let indices = Buffer::from((&device, [2i32, 0, 2]));
+
+    let out: Buffer<_, _> = device.index_select_rows(2, &x, &indices);
+    assert_eq!(out.read(), [5, 6, 1, 2, 5, 6]);
+
+    let scattered: Buffer<_, _> = device.scatter_add_rows(3, 2, &out, &indices);
+    assert_eq!(scattered.read(), [1, 2, 0, 0, 10, 12]);
+
+    #[cfg(feature = "autograd")]
+    {
+        let weights = Buffer::from((&device, [2, -1, 3, 4, 1, -2]));
+        let weighted = device.mul(&scattered, &weights);
+        weighted.backward();
+
+        assert_eq!(out.grad().read(), [1, -2, 2, -1, 1, -2]);
+        assert_eq!(x.grad().read(), [2, -1, 0, 0, 2, -4]);
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "cpu")]
+fn close(lhs: f64, rhs: f64) -> bool {
+    (lhs - rhs).abs() < 1e-6
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_classification_metrics() {
+    use custos::CPU;
+    use sliced::{
+        metrics::{
+            accuracy, f1_score, precision, recall, top_k_accuracy, Average, ConfusionMatrix,
+        },
+        Buffer, Matrix,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    #[rustfmt::skip]
+    let scores = Matrix::from((&device, 5, 3, [
+        0.7, 0.2, 0.1,
+        0.1, 0.8, 0.1,
+        0.3, 0.4, 0.3,
+        0.2, 0.2, 0.6,
+        0.5, 0.1, 0.4,
+    ]));
+    let labels = Buffer::from((&device, [0., 1., 2., 2., 2.]));
+
+    assert!(close(accuracy(&scores, &labels), 0.6));
+    assert!(close(top_k_accuracy(&scores, &labels, 1), 0.6));
+    assert!(close(top_k_accuracy(&scores, &labels, 2), 1.));
+
+    let confusion = ConfusionMatrix::new(&scores, &labels);
+    assert_eq!(confusion.classes(), 3);
+    #[rustfmt::skip]
+    assert_eq!(confusion.counts(), [
+        1, 0, 0,
+        0, 1, 0,
+        1, 1, 1,
+    ]);
+    assert_eq!(confusion.get(2, 0), 1);
+
+    // per class precision: 1/2, 1/2, 1; recall: 1, 1, 1/3
+    assert!(close(precision(&scores, &labels, Average::Macro), 2. / 3.));
+    assert!(close(recall(&scores, &labels, Average::Macro), 7. / 9.));
+    assert!(close(precision(&scores, &labels, Average::Micro), 0.6));
+    assert!(close(recall(&scores, &labels, Average::Micro), 0.6));
+
+    let class_f1 = [2. / 3., 2. / 3., 0.5];
+    let macro_f1 = class_f1.iter().sum::<f64>() / 3.;
+    assert!(close(f1_score(&scores, &labels, Average::Macro), macro_f1));
+    assert!(close(f1_score(&scores, &labels, Average::Micro), 0.6));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_binary_scores() {
+    use custos::CPU;
+    use sliced::{
+        metrics::{accuracy, roc_auc, top_k_accuracy},
+        Buffer, Matrix,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let scores = Matrix::from((&device, 4, 1, [0.1, 0.4, 0.35, 0.8]));
+    let labels = Buffer::from((&device, [0., 0., 1., 1.]));
+
+    assert!(close(accuracy(&scores, &labels), 0.75));
+    assert!(close(top_k_accuracy(&scores, &labels, 1), 0.75));
+    assert!(close(top_k_accuracy(&scores, &labels, 2), 1.));
+    assert!(close(roc_auc(&scores, &labels), 0.75));
+
+    // ties count half
+    let scores = Buffer::from((&device, [0.5, 0.5, 0.5, 0.9]));
+    assert!(close(roc_auc(&scores, &labels), 0.75));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_classification_metrics_empty() {
+    use custos::CPU;
+    use sliced::{
+        metrics::{accuracy, top_k_accuracy},
+        Buffer, Matrix,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let scores = Matrix::from((&device, 0, 3, Vec::<f64>::new()));
+    let labels = Buffer::from((&device, Vec::<f64>::new()));
+
+    assert_eq!(accuracy(&scores, &labels), 0.);
+    assert_eq!(top_k_accuracy(&scores, &labels, 1), 0.);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_regression_metrics() {
+    use custos::CPU;
+    use sliced::{
+        metrics::{r2_score, rmse},
+        Buffer,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let targets = Buffer::from((&device, [3., -0.5, 2., 7.]));
+    let preds = Buffer::from((&device, [2.5, 0., 2., 8.]));
+
+    assert!(close(rmse(&preds, &targets), 0.375f64.sqrt()));
+    assert!((r2_score(&preds, &targets) - 0.948608).abs() < 1e-5);
+    assert!(close(r2_score(&targets, &targets), 1.));
+}
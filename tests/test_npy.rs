@@ -0,0 +1,79 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_matrix_npy_roundtrip() {
+    use custos::CPU;
+    use sliced::{io::load_npy, Matrix};
+
+    let device = CPU::<custos::Base>::new();
+
+    #[rustfmt::skip]
+    let x = Matrix::from((&device, 2, 3, [
+        1f32, 2., 3.,
+        4., 5., 6.,
+    ]));
+
+    let path = std::env::temp_dir().join("sliced_test_matrix.npy");
+    x.write_npy(&path).unwrap();
+
+    let read = Matrix::<f32, _>::read_npy(&device, &path).unwrap();
+    assert_eq!((read.rows(), read.cols()), (2, 3));
+    assert_eq!(read.read(), x.read());
+
+    assert_eq!(load_npy::<f32>(&path).unwrap().shape, [2, 3]);
+    // the f32 values are converted
+    let read = Matrix::<f64, _>::read_npy(&device, &path).unwrap();
+    assert_eq!(read.read(), [1., 2., 3., 4., 5., 6.]);
+    assert!(Matrix::<i32, _>::read_npy(&device, &path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_read_npy_requires_two_dims() {
+    use custos::CPU;
+    use sliced::{
+        io::{load_npy, save_npy},
+        Matrix,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let path = std::env::temp_dir().join("sliced_test_3d.npy");
+    save_npy(&path, &[2, 1, 2], &[1i32, 2, 3, 4]).unwrap();
+
+    assert!(Matrix::<i32, _>::read_npy(&device, &path).is_err());
+
+    // N-D arrays are loaded into flat buffers
+    let buf = load_npy::<i32>(&path).unwrap().to_buffer(&device);
+    assert_eq!(buf.read(), [1, 2, 3, 4]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_npz_roundtrip() {
+    use custos::CPU;
+    use sliced::{
+        io::{load_npz, save_npz},
+        nn::StateDict,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let mut arrays = StateDict::new();
+    arrays.insert("features", vec![2, 2], vec![0.5f64, 1.5, 2.5, 3.5]);
+    arrays.insert("labels", vec![2], vec![0., 1.]);
+
+    let path = std::env::temp_dir().join("sliced_test_arrays.npz");
+    save_npz(&path, &arrays).unwrap();
+
+    let loaded = load_npz::<f64>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, arrays);
+
+    let features = loaded.get("features").unwrap().to_matrix(&device);
+    assert_eq!((features.rows(), features.cols()), (2, 2));
+}
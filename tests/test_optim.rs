@@ -0,0 +1,268 @@
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+fn fit_line<'a, O>(device: &'a custos::CPU<custos::Autograd<custos::Base>>, mut optim: O) -> f32
+where
+    O: sliced::optim::Optimizer<'a, f32, custos::CPU<custos::Autograd<custos::Base>>>,
+{
+    use sliced::{
+        nn::{Linear, Module},
+        BinaryOpsMayGrad, Matrix, Mean,
+    };
+
+    sliced::set_seed(0);
+    let mut lin = Linear::<f32, _, 1, 1>::new(device);
+
+    // y = 2x + 1
+    let x = Matrix::from((device, 4, 1, [-1., 0., 1., 2.])).no_grad();
+    let y = Matrix::from((device, 4, 1, [-1., 1., 3., 5.])).no_grad();
+
+    let mut loss_val = 0.;
+    for _ in 0..300 {
+        optim.zero_grad(lin.params());
+
+        let diff = device.sub(&lin.forward(&x), &y);
+        let loss = device.mul(&diff, &diff);
+        loss_val = device.mean(&loss);
+        loss.backward();
+
+        optim.step(lin.params());
+    }
+    loss_val
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_optimizers_fit_line_cpu() {
+    use custos::CPU;
+    use sliced::optim::{Adagrad, Adam, RmsProp, SGD};
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let sgd = fit_line(&device, SGD::new(0.05));
+    let momentum = fit_line(&device, SGD::new(0.02).momentum(0.9));
+    let nesterov = fit_line(&device, SGD::new(0.02).momentum(0.9).nesterov(true));
+    let adam = fit_line(&device, Adam::new(0.1));
+    let adamw = fit_line(&device, Adam::adamw(0.1, 1e-4));
+    let rmsprop = fit_line(&device, RmsProp::new(0.02));
+    let adagrad = fit_line(&device, Adagrad::new(0.5));
+
+    for (name, loss) in [
+        ("sgd", sgd),
+        ("momentum", momentum),
+        ("nesterov", nesterov),
+        ("adam", adam),
+        ("adamw", adamw),
+        ("rmsprop", rmsprop),
+        ("adagrad", adagrad),
+    ] {
+        assert!(loss < 0.01, "{name} did not converge, loss: {loss}");
+    }
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_optimizer_state_per_param() {
+    use custos::{Buffer, CPU};
+    use sliced::{
+        nn::Param,
+        optim::{Optimizer, SGD},
+    };
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let mut lhs = Buffer::from((&device, [1f32, 1.])).require_grad();
+    let mut rhs = Buffer::from((&device, [1f32, 1.])).require_grad();
+
+    lhs.grad_mut().write(&[1., 1.]);
+    rhs.grad_mut().write(&[2., 2.]);
+
+    let mut sgd = SGD::new(0.5).momentum(1.);
+
+    // stepping the parameters separately must not mix up the velocities
+    for _ in 0..2 {
+        sgd.step(vec![Param::new(&mut lhs)]);
+        sgd.step(vec![Param::new(&mut rhs)]);
+    }
+
+    // velocities: 1, 2 -> param: 1 - 0.5 - 1
+    assert_eq!(lhs.read(), [-0.5, -0.5]);
+    // velocities: 2, 4 -> param: 1 - 1 - 2
+    assert_eq!(rhs.read(), [-2., -2.]);
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+#[should_panic]
+fn test_sgd_nesterov_without_momentum() {
+    use custos::{Buffer, CPU};
+    use sliced::{
+        nn::Param,
+        optim::{Optimizer, SGD},
+    };
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+
+    let mut param = Buffer::from((&device, [1f32, 1.])).require_grad();
+
+    let mut sgd = SGD::new(0.5).nesterov(true);
+    sgd.step(vec![Param::new(&mut param)]);
+}
+
+#[cfg(all(feature = "opencl", feature = "autograd"))]
+#[test]
+fn test_adam_cl() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL};
+    use sliced::{
+        nn::Param,
+        optim::{Adam, Optimizer},
+    };
+
+    let device = OpenCL::<custos::Autograd<custos::Base>>::new(0)?;
+
+    let mut param = Buffer::from((&device, [1f32, 1.])).require_grad();
+    param.grad_mut().write(&[4., -0.5]);
+
+    let mut adam = Adam::new(0.1);
+    adam.step(vec![Param::new(&mut param)]);
+
+    // the first step moves every parameter by lr * sign(grad)
+    sliced::test_utils::roughly_equals(&param.read(), &[0.9, 1.1]);
+    Ok(())
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_lbfgs_fit_line() {
+    use sliced::{
+        nn::{Linear, Module},
+        optim::LBFGS,
+        BinaryOpsMayGrad, Matrix, Sum,
+    };
+
+    let device = custos::CPU::<custos::Autograd<custos::Base>>::new();
+
+    sliced::set_seed(0);
+    let mut lin = Linear::<f64, _, 1, 1>::new(&device);
+
+    // y = 2x + 1
+    let x = Matrix::from((&device, 4, 1, [-1., 0., 1., 2.])).no_grad();
+    let y = Matrix::from((&device, 4, 1, [-1., 1., 3., 5.])).no_grad();
+
+    let mut lbfgs = LBFGS::new(1.);
+    let mut evals = 0;
+    let loss = lbfgs.step(&mut lin, |lin| {
+        evals += 1;
+        let diff = device.sub(&lin.forward(&x), &y);
+        let loss = device.mul(&diff, &diff);
+        loss.backward();
+        device.sum(&loss)
+    });
+
+    assert!(loss < 1e-8, "loss: {loss}");
+    assert!(evals > 1);
+
+    let weights = lin.weights.read_to_vec();
+    let bias = lin.bias.read_to_vec();
+    assert!((weights[0] - 2.).abs() < 1e-3);
+    assert!((bias[0] - 1.).abs() < 1e-3);
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_lbfgs_step_params_are_iterate() {
+    use sliced::{
+        nn::{Linear, Module},
+        optim::LBFGS,
+        BinaryOpsMayGrad, Matrix, Sum,
+    };
+
+    let device = custos::CPU::<custos::Autograd<custos::Base>>::new();
+
+    sliced::set_seed(1);
+    let mut lin = Linear::<f64, _, 1, 1>::new(&device);
+
+    let (xs, ys) = ([-1., 0., 1., 2.], [-1., 1., 3., 5.]);
+    let x = Matrix::from((&device, 4, 1, xs)).no_grad();
+    let y = Matrix::from((&device, 4, 1, ys)).no_grad();
+
+    let flat_params = |lin: &mut Linear<'_, f64, _, 1, 1>| {
+        lin.params()
+            .iter()
+            .flat_map(|param| param.param.read_to_vec())
+            .collect::<Vec<_>>()
+    };
+    let mut expected = flat_params(&mut lin);
+
+    let loss = LBFGS::new(1.).max_iter(3).step(&mut lin, |lin| {
+        let diff = device.sub(&lin.forward(&x), &y);
+        let loss = device.mul(&diff, &diff);
+        loss.backward();
+        device.sum(&loss)
+    });
+
+    // the same objective on the host
+    let expected_loss = LBFGS::new(1.)
+        .max_iter(3)
+        .minimize(&mut expected, |params| {
+            let (weight, bias) = (params[0], params[1]);
+            let residuals = xs.iter().zip(&ys).map(|(x, y)| (weight * x + bias - y, *x));
+            let loss = residuals.clone().map(|(r, _)| r * r).sum::<f64>();
+            let grad = vec![
+                residuals.clone().map(|(r, x)| 2. * r * x).sum(),
+                residuals.map(|(r, _)| 2. * r).sum(),
+            ];
+            (loss, grad)
+        });
+
+    let params = flat_params(&mut lin);
+    assert!((loss - expected_loss).abs() < 1e-9);
+    for (param, expected) in params.iter().zip(&expected) {
+        assert!(
+            (param - expected).abs() < 1e-9,
+            "{params:?} != {expected:?}"
+        );
+    }
+
+    // the gradients belong to the accepted iterate as well
+    let diff = xs
+        .iter()
+        .zip(&ys)
+        .map(|(x, y)| params[0] * x + params[1] - y)
+        .collect::<Vec<_>>();
+    let bias_grad = diff.iter().map(|r| 2. * r).sum::<f64>();
+    assert!((lin.bias.grad().read_to_vec()[0] - bias_grad).abs() < 1e-9);
+}
+
+#[cfg(all(feature = "cpu", feature = "autograd"))]
+#[test]
+fn test_clip_grad() {
+    use sliced::{
+        nn::{Linear, Module},
+        optim::{clip_grad_norm, clip_grad_value},
+        test_utils::roughly_equals,
+        Matrix,
+    };
+
+    let device = custos::CPU::<custos::Autograd<custos::Base>>::new();
+    let mut lin = Linear::<f32, _, 2, 1>::new(&device);
+
+    let x = Matrix::from((&device, 1, 2, [3., 4.])).no_grad();
+    lin.forward(&x).backward();
+
+    // weights grad: [3, 4], bias grad: [1]
+    let total_norm = clip_grad_norm(lin.params(), 1.);
+    roughly_equals(&[total_norm], &[26f32.sqrt()]);
+
+    let norm = (lin.weights.grad().iter().map(|x| x * x).sum::<f32>()
+        + lin.bias.grad()[0] * lin.bias.grad()[0])
+        .sqrt();
+    roughly_equals(&[norm], &[1.]);
+
+    // below max_norm: unchanged
+    let weights_grad = lin.weights.grad().to_vec();
+    clip_grad_norm(lin.params(), 2.);
+    assert_eq!(lin.weights.grad().to_vec(), weights_grad);
+
+    clip_grad_value(lin.params(), 0.5);
+    roughly_equals(lin.weights.grad(), &[0.5, 0.5]);
+    roughly_equals(lin.bias.grad(), &[26f32.sqrt().recip()]);
+}
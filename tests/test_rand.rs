@@ -0,0 +1,46 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_rand_seeded_cpu() {
+    use custos::{Buffer, CPU};
+    use sliced::{RandOp, Rng};
+
+    let device = CPU::<custos::Base>::new();
+
+    let mut lhs = Buffer::<f64, _>::new(&device, 100);
+    let mut rhs = Buffer::<f64, _>::new(&device, 100);
+
+    sliced::set_seed(1);
+    device.rand(&mut lhs, -1., 1.);
+    device.rand(&mut rhs, -1., 1.);
+    assert_ne!(&*lhs, &*rhs);
+
+    sliced::set_seed(1);
+    device.rand(&mut rhs, -1., 1.);
+    assert_eq!(&*lhs, &*rhs);
+
+    // a forked stream is independent of the parent
+    let mut rng = Rng::with_seed(1);
+    let mut forked = rng.fork();
+    device.rand_with(&mut rng, &mut lhs, -1., 1.);
+    device.rand_with(&mut forked, &mut rhs, -1., 1.);
+    assert_ne!(&*lhs, &*rhs);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_rand_cpu_cl_equal() -> custos::Result<()> {
+    use custos::{Buffer, OpenCL, CPU};
+    use sliced::{RandOp, Rng};
+
+    let cpu = CPU::<custos::Base>::new();
+    let cl = OpenCL::<custos::Base>::new(0)?;
+
+    let mut lhs = Buffer::<f32, _>::new(&cpu, 1000);
+    let mut rhs = Buffer::<f32, _>::new(&cl, 1000);
+
+    cpu.rand_with(&mut Rng::with_seed(5), &mut lhs, -3., 3.);
+    cl.rand_with(&mut Rng::with_seed(5), &mut rhs, -3., 3.);
+
+    assert_eq!(lhs.read(), rhs.read());
+    Ok(())
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "cpu")]
+#[test]
+fn test_safetensors_linear_roundtrip() {
+    use custos::CPU;
+    use sliced::{
+        io::{load_safetensors, save_safetensors},
+        nn::{Linear, Module},
+        Matrix,
+    };
+
+    let device = CPU::<custos::Autograd<custos::Base>>::new();
+    sliced::set_seed(0);
+
+    let mut lin = Linear::<f32, _, 3, 2>::new(&device);
+    let path = std::env::temp_dir().join("sliced_test_linear.safetensors");
+    save_safetensors(&path, &lin.state_dict()).unwrap();
+
+    let state_dict = load_safetensors::<f32>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let weights: Matrix<f32, _> = state_dict.get("weights").unwrap().to_matrix(&device);
+    assert_eq!((weights.rows(), weights.cols()), (3, 2));
+    assert_eq!(weights.read(), lin.weights.read());
+
+    let mut restored = Linear::<f32, _, 3, 2>::new(&device);
+    restored.load_state_dict(&state_dict).unwrap();
+    assert_eq!(restored.bias.read(), lin.bias.read());
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn test_safetensors_buffers() {
+    use custos::{Buffer, CPU};
+    use sliced::{
+        io::{read_safetensors, write_safetensors},
+        nn::StateDict,
+    };
+
+    let device = CPU::<custos::Base>::new();
+
+    let indices = Buffer::from((&device, [3i32, -1, 4]));
+    let mut state_dict = StateDict::new();
+    state_dict.insert_buffer("indices", &indices);
+
+    let mut bytes = vec![];
+    write_safetensors(&mut bytes, &state_dict).unwrap();
+
+    let read = read_safetensors::<i32>(&mut &bytes[..]).unwrap();
+    let entry = read.get("indices").unwrap();
+    assert_eq!(entry.shape, [3]);
+    assert_eq!(entry.to_buffer(&device).read(), [3, -1, 4]);
+}
+
+#[cfg(feature = "opencl")]
+#[test]
+fn test_safetensors_to_opencl() -> custos::Result<()> {
+    use custos::OpenCL;
+    use sliced::{
+        io::{read_safetensors, write_safetensors},
+        nn::StateDict,
+    };
+
+    let device = OpenCL::<custos::Base>::new(0)?;
+
+    let mut state_dict = StateDict::new();
+    state_dict.insert("x", vec![2, 2], vec![1f32, 2., 3., 4.]);
+
+    let mut bytes = vec![];
+    write_safetensors(&mut bytes, &state_dict)?;
+
+    let x = read_safetensors::<f32>(&mut &bytes[..])?
+        .get("x")
+        .unwrap()
+        .to_matrix(&device);
+    assert_eq!((x.rows(), x.cols()), (2, 2));
+    assert_eq!(x.read(), [1., 2., 3., 4.]);
+    Ok(())
+}